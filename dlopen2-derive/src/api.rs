@@ -1,12 +1,17 @@
-use super::common::{get_fields, symbol_name};
+use super::common::{find_mangler, get_fields, symbol_name, symbol_name_cstr};
 use quote::quote;
-use syn::{DeriveInput, Field};
+use syn::{Data, DataStruct, DeriveInput, Expr, Field, Fields, Path, Type};
 
 pub fn impl_library_api(ast: &DeriveInput) -> proc_macro2::TokenStream {
     let name = &ast.ident;
     let fields = get_fields(ast, "SymBorApi");
 
-    let tok_iter = fields.named.iter().map(field_to_tokens);
+    let mangler = find_mangler(ast);
+
+    let tok_iter = fields
+        .named
+        .iter()
+        .map(|field| field_to_tokens(field, mangler.as_ref()));
     let q = quote! {
         impl<'a> SymBorApi<'a> for #name<'a> {
             unsafe fn load(lib: &'a ::dlopen2::symbor::Library) -> ::std::result::Result<#name<'a>,::dlopen2::Error> {
@@ -20,16 +25,77 @@ pub fn impl_library_api(ast: &DeriveInput) -> proc_macro2::TokenStream {
     q
 }
 
-fn field_to_tokens(field: &Field) -> proc_macro2::TokenStream {
+/// Wraps the type of every field that is not optional yet in `Option`.
+pub fn make_all_optional(ast: &mut DeriveInput) -> Result<(), syn::Error> {
+    let fields = match &mut ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &ast.ident,
+                "dlopen2_all_optional can be only applied to structures with named fields",
+            ));
+        }
+    };
+    for field in fields.named.iter_mut() {
+        if !is_option(&field.ty) {
+            let ty = &field.ty;
+            field.ty = syn::parse_quote!(::std::option::Option<#ty>);
+        }
+    }
+    Ok(())
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Group(group) => is_option(&group.elem),
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+fn field_to_tokens(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
     let field_name = &field.ident;
-    let symbol_name = symbol_name(field);
+    let name = symbol_name(field);
+    //the mangled name is recorded too, so it only gets computed once
+    if let Some(mangler) = mangler {
+        let name = match name {
+            Expr::Path(path) => quote! {{
+                const NAME: &str = #path;
+                NAME
+            }},
+            name => quote!(#name),
+        };
+        return quote! {
+            #field_name: {
+                let name = ::std::string::String::from(#mangler(#name));
+                let raw_result = lib.ptr_or_null_cstr::<()>(
+                    ::std::ffi::CString::new(name.as_str())?.as_c_str()
+                );
+                ::dlopen2::symbor::FromRawResult::from_named_raw_result(
+                    raw_result,
+                    ::std::borrow::Cow::Owned(name),
+                )?
+            }
+        };
+    }
+    let symbol_name = symbol_name_cstr(field, None);
 
     quote! {
         #field_name: {
             let raw_result = lib.ptr_or_null_cstr::<()>(
-                ::std::ffi::CStr::from_bytes_with_nul_unchecked(concat!(#symbol_name, "\0").as_bytes())
+                #symbol_name
             );
-            ::dlopen2::symbor::FromRawResult::from_raw_result(raw_result)?
+            ::dlopen2::symbor::FromRawResult::from_named_raw_result(
+                raw_result,
+                ::std::borrow::Cow::Borrowed(#name),
+            )?
         }
     }
 }
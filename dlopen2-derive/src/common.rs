@@ -1,36 +1,132 @@
-use syn::{Attribute, Data, DeriveInput, Expr, ExprLit, Field, Fields, FieldsNamed, Lit, Meta};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{
+    Abi, Attribute, Data, DeriveInput, Expr, ExprLit, Field, Fields, FieldsNamed, Lit, LitStr,
+    Meta, Path, ReturnType, Token, Type,
+};
 
-pub fn symbol_name(field: &Field) -> String {
-    match find_str_attr_val(field, "dlopen2_name") {
+/// Returns an expression evaluating to the symbol name (`&str`) of the given field.
+pub fn symbol_name(field: &Field) -> Expr {
+    match find_name_attr_val(field, "dlopen2_name") {
         Some(val) => val,
         None => {
             // not found, so use field name
             match field.ident {
-                Some(ref val) => val.to_string(),
+                Some(ref val) => Expr::Lit(ExprLit {
+                    attrs: Vec::new(),
+                    lit: Lit::Str(LitStr::new(&val.to_string(), val.span())),
+                }),
                 None => panic!("All structure fields need to be identifiable"),
             }
         }
     }
 }
 
-pub fn find_str_attr_val(field: &Field, attr_name: &str) -> Option<String> {
-    for attr in field.attrs.iter() {
-        match attr.meta {
-            Meta::NameValue(ref meta) => {
-                if let Some(ident) = meta.path.get_ident() {
-                    if ident == attr_name {
-                        return match &meta.value {
-                            Expr::Lit(ExprLit {
-                                lit: Lit::Str(val), ..
-                            }) => Some(val.value()),
-                            _ => panic!("{attr_name} attribute must be a string"),
-                        };
-                    }
+/// Returns an expression evaluating to the symbol name of the given field as `&CStr`.
+///
+/// Names are null-terminated at compile time, including the ones given as paths to constants,
+/// which need to be `const &str` values. Only mangled names get converted when the symbols
+/// are loaded.
+pub fn symbol_name_cstr(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
+    match (symbol_name(field), mangler) {
+        (Expr::Path(path), Some(mangler)) => quote! {
+            ::std::ffi::CString::new(#mangler({
+                const NAME: &str = #path;
+                NAME
+            }))?.as_c_str()
+        },
+        (name, Some(mangler)) => quote! {
+            ::std::ffi::CString::new(#mangler(#name))?.as_c_str()
+        },
+        (Expr::Path(path), None) => quote! {{
+            const NAME: &str = #path;
+            const LEN: usize = NAME.len() + 1;
+            const BYTES: [u8; LEN] = {
+                let mut bytes = [0; LEN];
+                let mut i = 0;
+                while i < NAME.len() {
+                    bytes[i] = NAME.as_bytes()[i];
+                    i += 1;
                 }
+                bytes
+            };
+            const CSTR: &::std::ffi::CStr = match ::std::ffi::CStr::from_bytes_with_nul(&BYTES) {
+                ::std::result::Result::Ok(cstr) => cstr,
+                ::std::result::Result::Err(_) => panic!("symbol names must not contain null characters"),
+            };
+            CSTR
+        }},
+        (name, None) => quote! {
+            ::std::ffi::CStr::from_bytes_with_nul_unchecked(concat!(#name, "\0").as_bytes())
+        },
+    }
+}
+
+/// Finds the symbol name mangler assigned to the structure using the `dlopen2_mangle` attribute.
+pub fn find_mangler(ast: &DeriveInput) -> Option<Path> {
+    for attr in ast.attrs.iter() {
+        if !attr.path().is_ident("dlopen2_mangle") {
+            continue;
+        }
+        return match attr.meta {
+            Meta::NameValue(ref meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(val), ..
+                }) => match val.parse::<Path>() {
+                    Ok(path) => Some(path),
+                    Err(_) => panic!("dlopen2_mangle attribute must be a path to a function"),
+                },
+                _ => panic!("dlopen2_mangle attribute must be a string"),
+            },
+            _ => panic!("dlopen2_mangle attribute must be in the form dlopen2_mangle = \"path\""),
+        };
+    }
+    None
+}
+
+/// Collects the names listed in the `dlopen2_forbid("name", ...)` attributes of the structure.
+pub fn find_forbidden(ast: &DeriveInput) -> Vec<LitStr> {
+    let mut names = Vec::new();
+    for attr in ast.attrs.iter() {
+        if !attr.path().is_ident("dlopen2_forbid") {
+            continue;
+        }
+        match attr.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated) {
+            Ok(list) => names.extend(list),
+            Err(_) => {
+                panic!("dlopen2_forbid attribute must be in the form dlopen2_forbid(\"name\", ...)")
             }
-            _ => continue,
         }
     }
+    names
+}
+
+/// Finds the value of a name attribute.
+///
+/// Accepted values are string literals and `concat!(...)` invocations (`#[name = "..."]`)
+/// or paths to a `const &str` (`#[name(PATH)]`).
+pub fn find_name_attr_val(field: &Field, attr_name: &str) -> Option<Expr> {
+    for attr in field.attrs.iter() {
+        if !attr.path().is_ident(attr_name) {
+            continue;
+        }
+        return match attr.meta {
+            Meta::NameValue(ref meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(_), ..
+                }) => Some(meta.value.clone()),
+                Expr::Macro(mac) if mac.mac.path.is_ident("concat") => Some(meta.value.clone()),
+                _ => panic!(
+                    "{attr_name} attribute must be a string literal or a concat!(...) invocation"
+                ),
+            },
+            Meta::List(ref list) => match list.parse_args::<Expr>() {
+                Ok(Expr::Path(path)) => Some(Expr::Path(path)),
+                _ => panic!("{attr_name}(...) attribute must contain a path to a const &str"),
+            },
+            Meta::Path(_) => panic!("{attr_name} attribute requires a value"),
+        };
+    }
     None
 }
 
@@ -53,8 +149,8 @@ pub fn has_marker_attr(field: &Field, attr_name: &str) -> bool {
     for attr in field.attrs.iter() {
         match attr.meta {
             Meta::Path(ref val) => {
-                if let Some(ident) = val.get_ident() {
-                    return ident == attr_name;
+                if val.is_ident(attr_name) {
+                    return true;
                 }
             }
             _ => continue,
@@ -77,3 +173,49 @@ pub fn get_fields<'a>(ast: &'a DeriveInput, trait_name: &str) -> &'a FieldsNamed
         }
     }
 }
+
+/// Returns the signature of a function the way it is listed in API manifests,
+/// e.g. `extern "C" fn(c_int) -> c_int`.
+///
+/// Argument names and `unsafe` (which does not affect the ABI) are left out, the ABI is always
+/// spelled out and the whitespace inside of types is normalized, so that `export_api!` and
+/// the `WrapperApi` derive produce the same string for the same signature.
+pub fn fn_signature<'a>(
+    abi: Option<&Abi>,
+    inputs: impl Iterator<Item = &'a Type>,
+    variadic: bool,
+    output: &ReturnType,
+) -> String {
+    let abi = match abi {
+        None => "Rust".to_owned(),
+        Some(abi) => abi
+            .name
+            .as_ref()
+            .map_or_else(|| "C".to_owned(), LitStr::value),
+    };
+    let mut args: Vec<String> = inputs.map(type_signature).collect();
+    if variadic {
+        args.push("...".to_owned());
+    }
+    let output = match output {
+        ReturnType::Type(_, ty) if !matches!(&**ty, Type::Tuple(tuple) if tuple.elems.is_empty()) =>
+        {
+            format!(" -> {}", type_signature(ty))
+        }
+        _ => String::new(),
+    };
+    format!("extern \"{}\" fn({}){}", abi, args.join(", "), output)
+}
+
+// keeps only the spaces that separate words, e.g. `*const u8` or `&'a mut T`
+fn type_signature(ty: &Type) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut signature = String::new();
+    for part in quote!(#ty).to_string().split_whitespace() {
+        if signature.ends_with(is_word_char) && part.starts_with(is_word_char) {
+            signature.push(' ');
+        }
+        signature.push_str(part);
+    }
+    signature
+}
@@ -0,0 +1,59 @@
+use super::common::fn_signature;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{FnArg, ItemFn, LitStr};
+
+pub struct ExportedFns(Vec<ItemFn>);
+
+impl Parse for ExportedFns {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut fns = Vec::new();
+        while !input.is_empty() {
+            fns.push(input.parse()?);
+        }
+        Ok(ExportedFns(fns))
+    }
+}
+
+pub fn impl_export_api(fns: &ExportedFns) -> proc_macro2::TokenStream {
+    let mut entries = Vec::new();
+    for item in fns.0.iter() {
+        let sig = &item.sig;
+        if !sig.generics.params.is_empty() {
+            return syn::Error::new_spanned(&sig.generics, "exported functions can't be generic")
+                .to_compile_error();
+        }
+        let mut inputs = Vec::new();
+        for arg in sig.inputs.iter() {
+            match arg {
+                FnArg::Typed(arg) => inputs.push(&*arg.ty),
+                FnArg::Receiver(receiver) => {
+                    return syn::Error::new_spanned(receiver, "exported functions can't take self")
+                        .to_compile_error();
+                }
+            }
+        }
+        let name = LitStr::new(&sig.ident.to_string(), sig.ident.span());
+        let signature = fn_signature(
+            sig.abi.as_ref(),
+            inputs.into_iter(),
+            sig.variadic.is_some(),
+            &sig.output,
+        );
+        entries.push(quote! {
+            ::dlopen2::wrapper::ManifestEntry::new(#name, #signature)
+        });
+    }
+    let items = fns.0.iter();
+    quote! {
+        #(
+            #[unsafe(no_mangle)]
+            #items
+        )*
+
+        #[unsafe(no_mangle)]
+        #[allow(non_upper_case_globals)]
+        pub static __dlopen2_api_manifest: ::dlopen2::wrapper::ApiManifest =
+            ::dlopen2::wrapper::ApiManifest::new(&[#(#entries),*]);
+    }
+}
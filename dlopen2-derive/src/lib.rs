@@ -6,16 +6,31 @@ extern crate syn;
 
 mod api;
 mod common;
+mod export_api;
 mod multi_api;
 mod wrapper;
 
-use api::impl_library_api;
+use api::{impl_library_api, make_all_optional};
+use export_api::{ExportedFns, impl_export_api};
 use multi_api::impl_wrapper_multi_api;
 use proc_macro::TokenStream;
 use syn::{DeriveInput, parse_macro_input};
 use wrapper::impl_wrapper_api;
 
-#[proc_macro_derive(WrapperApi, attributes(dlopen2_name, dlopen2_allow_null))]
+#[proc_macro_derive(
+    WrapperApi,
+    attributes(
+        dlopen2_name,
+        dlopen2_allow_null,
+        dlopen2_mangle,
+        dlopen2_flatten,
+        dlopen2_sync,
+        dlopen2_forbid,
+        dlopen2_result,
+        dlopen2_catch_unwind,
+        dlopen2_fallback
+    )
+)]
 pub fn wrapper_api(input: TokenStream) -> TokenStream {
     // Parse the string representation
     let ast = parse_macro_input!(input as DeriveInput);
@@ -39,7 +54,7 @@ pub fn wrapper_multi_api(input: TokenStream) -> TokenStream {
     TokenStream::from(wrapper)
 }
 
-#[proc_macro_derive(SymBorApi, attributes(dlopen2_name))]
+#[proc_macro_derive(SymBorApi, attributes(dlopen2_name, dlopen2_mangle))]
 pub fn library_api(input: TokenStream) -> TokenStream {
     // Parse the string representation
     let ast = parse_macro_input!(input as DeriveInput);
@@ -50,3 +65,52 @@ pub fn library_api(input: TokenStream) -> TokenStream {
     // Return the generated impl
     TokenStream::from(library)
 }
+
+/**
+Makes every field of a structure deriving `SymBorApi` optional.
+
+The type of each field that is not an `Option` yet gets wrapped in one, so missing symbols
+are loaded as `None`. The attribute needs to be placed before `#[derive(SymBorApi)]`.
+*/
+#[proc_macro_attribute]
+pub fn dlopen2_all_optional(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "dlopen2_all_optional attribute does not accept any value",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let mut ast = parse_macro_input!(item as DeriveInput);
+    match make_all_optional(&mut ast) {
+        Ok(()) => TokenStream::from(quote::quote!(#ast)),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/**
+Exports functions from a dynamic library together with a manifest of their signatures.
+
+Every function gets marked with `#[unsafe(no_mangle)]` and listed in a manifest
+(the `__dlopen2_api_manifest` symbol, so the macro can be used once per library). A program
+loading the library can compare the manifest with its `WrapperApi` structure using
+`WrapperApi::check_manifest()` before loading any symbol.
+
+```ignore
+dlopen2::export_api! {
+    pub extern "C" fn add_two(arg: c_int) -> c_int {
+        arg + 2
+    }
+
+    pub fn rust_add_one(arg: i32) -> i32 {
+        arg + 1
+    }
+}
+```
+*/
+#[proc_macro]
+pub fn export_api(input: TokenStream) -> TokenStream {
+    let fns = parse_macro_input!(input as ExportedFns);
+    TokenStream::from(impl_export_api(&fns))
+}
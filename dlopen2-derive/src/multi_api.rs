@@ -10,15 +10,86 @@ pub fn impl_wrapper_multi_api(ast: &DeriveInput) -> proc_macro2::TokenStream {
     let fields = get_fields(ast, TRATIT_NAME);
 
     let tok_iter = fields.named.iter().map(field_to_tokens);
+    let verify_iter = fields.named.iter().map(|field| {
+        let field_name = &field.ident;
+        quote! {
+            ::dlopen2::wrapper::WrapperApi::verify(&self.#field_name, lib)?;
+        }
+    });
+    let check_iter = fields.named.iter().map(|field| {
+        let ty = &field.ty;
+        quote! {
+            <#ty as ::dlopen2::wrapper::WrapperApi>::check(lib)?;
+        }
+    });
+    let manifest_check_iter = fields.named.iter().map(|field| {
+        let ty = &field.ty;
+        quote! {
+            <#ty as ::dlopen2::wrapper::WrapperApi>::check_manifest(lib)?;
+        }
+    });
+    let optional_manifest_check_iter = fields.named.iter().map(|field| {
+        let ty = &field.ty;
+        quote! {
+            <#ty as ::dlopen2::wrapper::WrapperApi>::check_manifest_optional(lib)?;
+        }
+    });
+    let field_types: Vec<_> = fields.named.iter().map(|field| &field.ty).collect();
     let q = quote! {
         impl #generics WrapperMultiApi for #name #generics{}
 
         impl #generics ::dlopen2::wrapper::WrapperApi for # name #generics{
-            unsafe fn load(lib: & ::dlopen2::raw::Library) -> ::std::result::Result<Self,::dlopen2::Error> {
+            unsafe fn load(lib: &impl ::dlopen2::raw::SymbolSource) -> ::std::result::Result<Self,::dlopen2::Error> {
                 ::std::result::Result::Ok(#name {
                     #(#tok_iter),*
                 })
             }
+
+            fn check(lib: & ::dlopen2::raw::Library) -> ::std::result::Result<(), ::dlopen2::Error> {
+                #(#check_iter)*
+                ::std::result::Result::Ok(())
+            }
+
+            fn check_manifest(lib: & ::dlopen2::raw::Library) -> ::std::result::Result<(), ::dlopen2::Error> {
+                #(#manifest_check_iter)*
+                ::std::result::Result::Ok(())
+            }
+
+            fn check_manifest_optional(lib: & ::dlopen2::raw::Library) -> ::std::result::Result<(), ::dlopen2::Error> {
+                #(#optional_manifest_check_iter)*
+                ::std::result::Result::Ok(())
+            }
+
+            fn verify(&self, lib: & ::dlopen2::raw::Library) -> ::std::result::Result<(), ::dlopen2::Error> {
+                #(#verify_iter)*
+                ::std::result::Result::Ok(())
+            }
+
+            fn required_symbols() -> ::std::vec::Vec<::std::string::String> {
+                let mut unique: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                #(
+                    for name in <#field_types as ::dlopen2::wrapper::WrapperApi>::required_symbols() {
+                        if !unique.contains(&name) {
+                            unique.push(name);
+                        }
+                    }
+                )*
+                unique
+            }
+
+            fn optional_symbols() -> ::std::vec::Vec<::std::string::String> {
+                // a symbol that is also required by another API can't be missing
+                let mut unique = <Self as ::dlopen2::wrapper::WrapperApi>::required_symbols();
+                let required = unique.len();
+                #(
+                    for name in <#field_types as ::dlopen2::wrapper::WrapperApi>::optional_symbols() {
+                        if !unique.contains(&name) {
+                            unique.push(name);
+                        }
+                    }
+                )*
+                unique.split_off(required)
+            }
         }
     };
 
@@ -29,6 +100,6 @@ fn field_to_tokens(field: &Field) -> proc_macro2::TokenStream {
     let field_name = &field.ident;
 
     quote! {
-        #field_name: ::dlopen2::wrapper::WrapperApi::load(&lib)?
+        #field_name: ::dlopen2::wrapper::WrapperApi::load(lib)?
     }
 }
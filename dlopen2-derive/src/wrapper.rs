@@ -1,8 +1,20 @@
-use super::common::{get_fields, get_non_marker_attrs, has_marker_attr, symbol_name};
+use super::common::{
+    find_forbidden, find_mangler, fn_signature, get_fields, get_non_marker_attrs, has_marker_attr,
+    symbol_name, symbol_name_cstr,
+};
 use quote::quote;
-use syn::{self, BareFnArg, DeriveInput, Field, GenericArgument, Type, TypePtr, Visibility};
+use std::collections::HashMap;
+use syn::{
+    self, BareFnArg, DeriveInput, Expr, ExprLit, Field, FieldsNamed, GenericArgument, Lit, Meta,
+    Path, Type, TypePtr, TypeReference, Visibility,
+};
 
 const ALLOW_NULL: &str = "dlopen2_allow_null";
+const FLATTEN: &str = "dlopen2_flatten";
+const SYNC: &str = "dlopen2_sync";
+const RESULT: &str = "dlopen2_result";
+const CATCH_UNWIND: &str = "dlopen2_catch_unwind";
+const FALLBACK: &str = "dlopen2_fallback";
 const TRAIT_NAME: &str = "WrapperApi";
 
 pub fn impl_wrapper_api(ast: &DeriveInput) -> proc_macro2::TokenStream {
@@ -26,15 +38,145 @@ pub fn impl_wrapper_api(ast: &DeriveInput) -> proc_macro2::TokenStream {
         }
     }
 
-    let field_iter = fields.named.iter().map(field_to_tokens);
-    let wrapper_iter = fields.named.iter().filter_map(field_to_wrapper);
+    let catch_unwind = has_catch_unwind_attr(ast);
+    let wrappers: Vec<(&Field, syn::Ident, proc_macro2::TokenStream)> = fields
+        .named
+        .iter()
+        .flat_map(|field| {
+            field_to_wrapper(field, catch_unwind)
+                .into_iter()
+                .map(move |(method, tokens)| (field, method, tokens))
+        })
+        .collect();
+    if let Err(err) = check_method_collisions(&wrappers) {
+        return err.to_compile_error();
+    }
+    let wrapper_iter = wrappers.iter().map(|(_, _, tokens)| tokens);
+
+    let mangler = find_mangler(ast);
+    let forbidden_iter = find_forbidden(ast).into_iter().map(|name| {
+        quote! {
+            if lib.exports_symbol(#name) {
+                return ::std::result::Result::Err(
+                    ::dlopen2::Error::ForbiddenSymbol(::std::string::String::from(#name))
+                );
+            }
+        }
+    });
+
+    let field_iter = fields
+        .named
+        .iter()
+        .map(|field| field_to_tokens(field, mangler.as_ref()));
+    let verify_iter = fields
+        .named
+        .iter()
+        .map(|field| field_to_verify_tokens(field, mangler.as_ref()));
+    let check_iter = fields
+        .named
+        .iter()
+        .map(|field| field_to_check_tokens(field, mangler.as_ref()));
+    let manifest_check = manifest_check_tokens(fields, mangler.as_ref(), false);
+    let optional_manifest_check = manifest_check_tokens(fields, mangler.as_ref(), true);
+    let (optional, required): (Vec<&Field>, Vec<&Field>) = fields
+        .named
+        .iter()
+        .filter(|field| !has_marker_attr(field, FLATTEN))
+        .partition(|field| is_optional(field) || find_fallback(field).is_some());
+    let flatten_types: Vec<&Type> = fields
+        .named
+        .iter()
+        .filter(|field| has_marker_attr(field, FLATTEN))
+        .map(|field| &field.ty)
+        .collect();
+    let symbol_lists = if mangler.is_none() && flatten_types.is_empty() {
+        // plain names can be listed at compile time
+        let required_names: Vec<Expr> = required.iter().map(|field| symbol_name(field)).collect();
+        let optional_names = optional.iter().map(|field| symbol_name(field));
+        quote! {
+            const REQUIRED_SYMBOLS: &'static [&'static str] = {
+                const NAMES: &[&str] = &[#(#required_names),*];
+                const UNIQUE: [&str; ::dlopen2::wrapper::unique_symbol_count(NAMES, &[])] =
+                    ::dlopen2::wrapper::unique_symbols(NAMES, &[]);
+                &UNIQUE
+            };
+
+            const OPTIONAL_SYMBOLS: &'static [&'static str] = {
+                const NAMES: &[&str] = &[#(#optional_names),*];
+                // a symbol that is also required by another field can't be missing
+                const REQUIRED: &[&str] = &[#(#required_names),*];
+                const UNIQUE: [&str; ::dlopen2::wrapper::unique_symbol_count(NAMES, REQUIRED)] =
+                    ::dlopen2::wrapper::unique_symbols(NAMES, REQUIRED);
+                &UNIQUE
+            };
+        }
+    } else {
+        let required_iter = required
+            .iter()
+            .map(|field| mangled_symbol_name(field, mangler.as_ref()));
+        let optional_iter = optional
+            .iter()
+            .map(|field| mangled_symbol_name(field, mangler.as_ref()));
+        quote! {
+            fn required_symbols() -> ::std::vec::Vec<::std::string::String> {
+                let mut names: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                #(names.push(#required_iter);)*
+                #(names.extend(<#flatten_types as ::dlopen2::wrapper::WrapperApi>::required_symbols());)*
+                let mut unique = ::std::vec::Vec::with_capacity(names.len());
+                for name in names {
+                    if !unique.contains(&name) {
+                        unique.push(name);
+                    }
+                }
+                unique
+            }
+
+            fn optional_symbols() -> ::std::vec::Vec<::std::string::String> {
+                let mut names: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                #(names.push(#optional_iter);)*
+                #(names.extend(<#flatten_types as ::dlopen2::wrapper::WrapperApi>::optional_symbols());)*
+                // a symbol that is also required by another field can't be missing
+                let mut unique = <Self as ::dlopen2::wrapper::WrapperApi>::required_symbols();
+                let required = unique.len();
+                for name in names {
+                    if !unique.contains(&name) {
+                        unique.push(name);
+                    }
+                }
+                unique.split_off(required)
+            }
+        }
+    };
     let q = quote! {
         impl #generics WrapperApi for #struct_name #generics {
-            unsafe fn load(lib: & ::dlopen2::raw::Library ) -> ::std::result::Result<Self, ::dlopen2::Error> {
+            unsafe fn load(lib: &impl ::dlopen2::raw::SymbolSource) -> ::std::result::Result<Self, ::dlopen2::Error> {
+                #(#forbidden_iter)*
                 Ok(Self{
                     #(#field_iter),*
                 })
             }
+
+            fn check(lib: & ::dlopen2::raw::Library) -> ::std::result::Result<(), ::dlopen2::Error> {
+                #(#check_iter)*
+                Ok(())
+            }
+
+            fn check_manifest(lib: & ::dlopen2::raw::Library) -> ::std::result::Result<(), ::dlopen2::Error> {
+                #manifest_check
+                Ok(())
+            }
+
+            fn check_manifest_optional(lib: & ::dlopen2::raw::Library) -> ::std::result::Result<(), ::dlopen2::Error> {
+                #optional_manifest_check
+                Ok(())
+            }
+
+            fn verify(&self, lib: & ::dlopen2::raw::Library) -> ::std::result::Result<(), ::dlopen2::Error> {
+                #(#verify_iter)*
+                Ok(())
+            }
+
+            #symbol_lists
         }
 
         #[allow(dead_code)]
@@ -46,20 +188,65 @@ pub fn impl_wrapper_api(ast: &DeriveInput) -> proc_macro2::TokenStream {
     q
 }
 
-fn field_to_tokens(field: &Field) -> proc_macro2::TokenStream {
+// generated methods with the same name would produce a confusing error in the generated code
+fn check_method_collisions(
+    wrappers: &[(&Field, syn::Ident, proc_macro2::TokenStream)],
+) -> Result<(), syn::Error> {
+    let mut methods: HashMap<String, &Field> = HashMap::new();
+    for (field, method, _) in wrappers {
+        let method = method.to_string();
+        if let Some(other) = methods.get(&method) {
+            return Err(syn::Error::new_spanned(
+                field.ident.as_ref(),
+                format!(
+                    "the method `{}` generated for field `{}` collides with the method generated for field `{}`; \
+                     rename one of the fields and keep its symbol name using `#[dlopen2_name = \"...\"]` \
+                     or move it to a separate sub-API",
+                    method,
+                    field.ident.as_ref().unwrap(),
+                    other.ident.as_ref().unwrap()
+                ),
+            ));
+        }
+        methods.insert(method, field);
+    }
+    Ok(())
+}
+
+fn field_to_tokens(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
     let allow_null = has_marker_attr(field, ALLOW_NULL);
+    if has_marker_attr(field, FLATTEN) {
+        if allow_null {
+            panic!("Only pointers can have the '{ALLOW_NULL}' attribute assigned");
+        }
+        return flatten_field(field);
+    }
+    if has_marker_attr(field, SYNC) {
+        if allow_null {
+            panic!("Only pointers can have the '{ALLOW_NULL}' attribute assigned");
+        }
+        sync_reference(field);
+        return sync_field(field, mangler);
+    }
+    let fallback = find_fallback(field);
+    if fallback.is_some() && !matches!(skip_groups(&field.ty), Type::BareFn(_)) {
+        panic!("Only functions can have the '{FALLBACK}' attribute assigned");
+    }
     match skip_groups(&field.ty) {
         Type::BareFn(_) | Type::Reference(_) => {
             if allow_null {
                 panic!("Only pointers can have the '{ALLOW_NULL}' attribute assigned");
             }
-            normal_field(field)
+            match fallback {
+                Some(fallback) => fallback_field(field, &fallback, mangler),
+                None => normal_field(field, mangler),
+            }
         }
         Type::Ptr(ptr) => {
             if allow_null {
-                allow_null_field(field, ptr)
+                allow_null_field(field, ptr, mangler)
             } else {
-                normal_field(field)
+                normal_field(field, mangler)
             }
         }
         Type::Path(rpath) => {
@@ -76,9 +263,15 @@ fn field_to_tokens(field: &Field) -> proc_macro2::TokenStream {
             match (path.leading_colon.is_some(), segments_str.as_slice()) {
                 (_, ["core" | "std", "option", "Option"])
                 | (false, ["option", "Option"])
-                | (false, ["Option"]) => optional_field(field),
+                | (false, ["Option"]) => optional_field(field, mangler),
+                (false, ["usize"]) => {
+                    if allow_null {
+                        panic!("Only pointers can have the '{ALLOW_NULL}' attribute assigned");
+                    }
+                    address_field(field, mangler)
+                }
                 _ => panic!(
-                    "Only bare functions, optional bare functions, references and pointers are allowed in structures implementing WrapperApi trait"
+                    "Only bare functions, optional bare functions, references, pointers and addresses (usize) are allowed in structures implementing WrapperApi trait"
                 ),
             }
         }
@@ -91,19 +284,243 @@ fn field_to_tokens(field: &Field) -> proc_macro2::TokenStream {
     }
 }
 
-fn normal_field(field: &Field) -> proc_macro2::TokenStream {
+// checks the conditions load() would otherwise panic or fail on, without loading the symbol
+fn field_to_check_tokens(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+    if has_marker_attr(field, FLATTEN) {
+        return quote! {
+            <#ty as ::dlopen2::wrapper::WrapperApi>::check(lib)?;
+        };
+    }
+    let name = symbol_name(field);
+    // optional fields get loaded as the type inside the option, addresses are always pointer-sized
+    let loaded_ty = match skip_groups(ty) {
+        Type::Path(path) if path.path.is_ident("usize") => None,
+        Type::Path(_) if is_optional(field) || has_marker_attr(field, SYNC) => {
+            option_inner_type(ty)
+        }
+        _ => Some(ty),
+    };
+    let size_check = loaded_ty.map(|loaded_ty| {
+        quote! {
+            if ::std::mem::size_of::<#loaded_ty>() != ::std::mem::size_of::<*const ()>() {
+                return ::std::result::Result::Err(
+                    ::dlopen2::Error::SymbolSizeMismatch(::std::string::String::from(#name))
+                );
+            }
+        }
+    });
+    let required = !is_optional(field)
+        && find_fallback(field).is_none()
+        && !has_marker_attr(field, ALLOW_NULL);
+    let presence_check = required.then(|| {
+        let symbol_name = symbol_name_cstr(field, mangler);
+        quote! {
+            if !unsafe { lib.has_symbol_cstr(#symbol_name) } {
+                return ::std::result::Result::Err(::dlopen2::Error::SymbolGettingError(
+                    ::std::io::Error::new(
+                        ::std::io::ErrorKind::NotFound,
+                        ::std::format!("The symbol {} is missing or null", #name),
+                    )
+                ));
+            }
+        }
+    });
+    quote! {
+        #size_check
+        #presence_check
+    }
+}
+
+// compares the signatures of function fields with the manifest exported by `export_api!`,
+// if the whole API is optional its functions (or the manifest) may be missing
+fn manifest_check_tokens(
+    fields: &FieldsNamed,
+    mangler: Option<&Path>,
+    optional_api: bool,
+) -> proc_macro2::TokenStream {
+    let mut signature_checks = Vec::new();
+    let mut flatten_checks = Vec::new();
+    let check_flattened = if optional_api {
+        quote!(check_manifest_optional)
+    } else {
+        quote!(check_manifest)
+    };
+    for field in fields.named.iter() {
+        let ty = &field.ty;
+        if has_marker_attr(field, FLATTEN) {
+            flatten_checks.push(quote! {
+                <#ty as ::dlopen2::wrapper::WrapperApi>::#check_flattened(lib)?;
+            });
+            continue;
+        }
+        let optional = is_optional(field);
+        let fn_ty = match skip_groups(ty) {
+            Type::Path(_) if optional => option_inner_type(ty).map(skip_groups),
+            ty => Some(ty),
+        };
+        if let Some(Type::BareFn(fn_ty)) = fn_ty {
+            //the manifest lists the names that `load()` resolves
+            let name = mangled_symbol_name(field, mangler);
+            let signature = fn_signature(
+                fn_ty.abi.as_ref(),
+                fn_ty.inputs.iter().map(|arg| &arg.ty),
+                fn_ty.variadic.is_some(),
+                &fn_ty.output,
+            );
+            let optional = optional_api || optional || find_fallback(field).is_some();
+            signature_checks.push(quote! {
+                manifest.check_signature(&#name, #signature, #optional)?;
+            });
+        }
+    }
+    let read_manifest = (!signature_checks.is_empty()).then(|| {
+        if optional_api {
+            quote! {
+                let manifest = match ::dlopen2::wrapper::ApiManifest::from_library(lib) {
+                    ::std::result::Result::Ok(manifest) => manifest,
+                    ::std::result::Result::Err(_) => return ::std::result::Result::Ok(()),
+                };
+            }
+        } else {
+            quote! {
+                let manifest = ::dlopen2::wrapper::ApiManifest::from_library(lib)?;
+            }
+        }
+    });
+    quote! {
+        #read_manifest
+        #(#signature_checks)*
+        #(#flatten_checks)*
+    }
+}
+
+// type inside Option<...>
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    match skip_groups(ty) {
+        Type::Path(path) => match &path.path.segments.last()?.arguments {
+            syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// the loaded values of all supported field types are the addresses of the symbols
+// (or null for missing optional symbols)
+fn field_to_verify_tokens(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
+    let field_name = &field.ident;
+    if has_marker_attr(field, FLATTEN) {
+        return quote! {
+            ::dlopen2::wrapper::WrapperApi::verify(&self.#field_name, lib)?;
+        };
+    }
+    let name = symbol_name(field);
+    let symbol_name = symbol_name_cstr(field, mangler);
+    // a missing symbol is expected to be replaced with its fallback
+    let missing = match find_fallback(field) {
+        Some(fallback) => {
+            let ty = &field.ty;
+            quote! {{
+                let fallback: #ty = #fallback;
+                fallback as usize
+            }}
+        }
+        None => quote!(0),
+    };
+    // load() keeps null symbols of allow_null fields even with `strict`
+    let null_allowed = if has_marker_attr(field, ALLOW_NULL) {
+        quote!(true)
+    } else {
+        quote!(!::dlopen2::STRICT)
+    };
+    // the reference of a synchronized field is only reachable through its lock
+    let loaded = if has_marker_attr(field, SYNC) {
+        quote! {
+            let loaded: usize = &**self.#field_name
+                .lock()
+                .unwrap_or_else(::std::sync::PoisonError::into_inner) as *const _ as *const () as usize;
+        }
+    } else {
+        quote! {
+            let loaded: usize = ::std::mem::transmute_copy(&self.#field_name);
+        }
+    };
+    quote! {
+        unsafe {
+            #loaded
+            let current: usize = match lib.symbol_cstr::<*const ()>(#symbol_name) {
+                ::std::result::Result::Ok(ptr) => ptr as usize,
+                ::std::result::Result::Err(::dlopen2::Error::NullSymbol) if #null_allowed => #missing,
+                ::std::result::Result::Err(::dlopen2::Error::SymbolGettingError(_)) => #missing,
+                ::std::result::Result::Err(err) => return ::std::result::Result::Err(err),
+            };
+            if loaded != current {
+                return ::std::result::Result::Err(
+                    ::dlopen2::Error::SymbolAddressMismatch(::std::string::String::from(#name))
+                );
+            }
+        }
+    }
+}
+
+// the name load() resolves the symbol of the field by, as `String`
+fn mangled_symbol_name(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
+    let name = symbol_name(field);
+    match mangler {
+        Some(mangler) => quote!(::std::string::String::from(#mangler(#name))),
+        None => quote!(::std::string::String::from(#name)),
+    }
+}
+
+fn normal_field(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
     let field_name = &field.ident;
-    let symbol_name = symbol_name(field);
+    let symbol_name = symbol_name_cstr(field, mangler);
     quote! {
         #field_name : lib.symbol_cstr(
-            ::std::ffi::CStr::from_bytes_with_nul_unchecked(concat!(#symbol_name, "\0").as_bytes())
+            #symbol_name
         )?
     }
 }
 
-fn allow_null_field(field: &Field, ptr: &TypePtr) -> proc_macro2::TokenStream {
+fn fallback_field(
+    field: &Field,
+    fallback: &Path,
+    mangler: Option<&Path>,
+) -> proc_macro2::TokenStream {
     let field_name = &field.ident;
-    let symbol_name = symbol_name(field);
+    let ty = &field.ty;
+    let symbol_name = symbol_name_cstr(field, mangler);
+    quote! {
+        #field_name : match lib.symbol_cstr(
+            #symbol_name
+        ) {
+            ::std::result::Result::Ok(val) => val,
+            ::std::result::Result::Err(err) => match err {
+                ::dlopen2::Error::NullSymbol if !::dlopen2::STRICT => {
+                    let fallback: #ty = #fallback;
+                    fallback
+                }
+                ::dlopen2::Error::SymbolGettingError(_) => {
+                    let fallback: #ty = #fallback;
+                    fallback
+                }
+                _ => return ::std::result::Result::Err(err)
+            }
+        }
+    }
+}
+
+fn allow_null_field(
+    field: &Field,
+    ptr: &TypePtr,
+    mangler: Option<&Path>,
+) -> proc_macro2::TokenStream {
+    let field_name = &field.ident;
+    let symbol_name = symbol_name_cstr(field, mangler);
     let null_fun = match ptr.mutability {
         Some(_) => quote! {null},
         None => quote! {null_mut},
@@ -111,7 +528,7 @@ fn allow_null_field(field: &Field, ptr: &TypePtr) -> proc_macro2::TokenStream {
 
     quote! {
         #field_name : match lib.symbol_cstr(
-            ::std::ffi::CStr::from_bytes_with_nul_unchecked(concat!(#symbol_name, "\0").as_bytes())
+            #symbol_name
         ) {
             ::std::result::Result::Ok(val) => val,
             ::std::result::Result::Err(err) => match err {
@@ -122,17 +539,58 @@ fn allow_null_field(field: &Field, ptr: &TypePtr) -> proc_macro2::TokenStream {
     }
 }
 
-fn optional_field(field: &Field) -> proc_macro2::TokenStream {
+fn sync_field(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
+    let field_name = &field.ident;
+    let symbol_name = symbol_name_cstr(field, mangler);
+    quote! {
+        #field_name : ::std::sync::Mutex::new(lib.symbol_cstr(
+            #symbol_name
+        )?)
+    }
+}
+
+// the reference inside the Mutex<&mut T> of fields marked with `dlopen2_sync`
+fn sync_reference(field: &Field) -> &TypeReference {
+    let is_mutex = matches!(
+        skip_groups(&field.ty),
+        Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Mutex")
+    );
+    match option_inner_type(&field.ty).map(skip_groups) {
+        Some(Type::Reference(ref_ty)) if is_mutex && ref_ty.mutability.is_some() => ref_ty,
+        _ => panic!(
+            "Only mutable references wrapped in a Mutex (Mutex<&mut T>) can have the '{SYNC}' attribute assigned"
+        ),
+    }
+}
+
+fn flatten_field(field: &Field) -> proc_macro2::TokenStream {
     let field_name = &field.ident;
-    let symbol_name = symbol_name(field);
+    quote! {
+        #field_name : ::dlopen2::wrapper::WrapperApi::load(lib)?
+    }
+}
+
+fn address_field(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
+    let field_name = &field.ident;
+    let symbol_name = symbol_name_cstr(field, mangler);
+    quote! {
+        #field_name : lib.symbol_cstr::<*const ()>(
+            #symbol_name
+        )? as usize
+    }
+}
+
+fn optional_field(field: &Field, mangler: Option<&Path>) -> proc_macro2::TokenStream {
+    let field_name = &field.ident;
+    let symbol_name = symbol_name_cstr(field, mangler);
 
     let tokens = quote! {
         #field_name : match lib.symbol_cstr(
-            ::std::ffi::CStr::from_bytes_with_nul_unchecked(concat!(#symbol_name, "\0").as_bytes())
+            #symbol_name
         ) {
             ::std::result::Result::Ok(val) => Some(val),
             ::std::result::Result::Err(err) => match err {
-                ::dlopen2::Error::NullSymbol => None,
+                ::dlopen2::Error::NullSymbol if !::dlopen2::STRICT => None,
                 ::dlopen2::Error::SymbolGettingError(_) => None,
                 _ => return ::std::result::Result::Err(err)
             }
@@ -141,6 +599,40 @@ fn optional_field(field: &Field) -> proc_macro2::TokenStream {
     tokens
 }
 
+// fields of type Option<...> which are set to None if the symbol is missing
+fn is_optional(field: &Field) -> bool {
+    match skip_groups(&field.ty) {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+// path of the function used in place of a missing symbol (`dlopen2_fallback = "path"`)
+fn find_fallback(field: &Field) -> Option<Path> {
+    for attr in field.attrs.iter() {
+        if !attr.path().is_ident(FALLBACK) {
+            continue;
+        }
+        return match attr.meta {
+            Meta::NameValue(ref meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(val), ..
+                }) => match val.parse::<Path>() {
+                    Ok(path) => Some(path),
+                    Err(_) => panic!("{FALLBACK} attribute must be a path to a function"),
+                },
+                _ => panic!("{FALLBACK} attribute must be a string"),
+            },
+            _ => panic!("{FALLBACK} attribute must be in the form {FALLBACK} = \"path\""),
+        };
+    }
+    None
+}
+
 fn skip_groups(ty: &Type) -> &Type {
     match ty {
         Type::Group(group) => skip_groups(&group.elem),
@@ -148,17 +640,65 @@ fn skip_groups(ty: &Type) -> &Type {
     }
 }
 
-fn field_to_wrapper(field: &Field) -> Option<proc_macro2::TokenStream> {
+fn has_catch_unwind_attr(ast: &DeriveInput) -> bool {
+    ast.attrs.iter().any(|attr| match attr.meta {
+        Meta::Path(ref path) => path.is_ident(CATCH_UNWIND),
+        _ if attr.path().is_ident(CATCH_UNWIND) => {
+            panic!("{CATCH_UNWIND} attribute does not accept any value")
+        }
+        _ => false,
+    })
+}
+
+// returns the generated methods together with their names
+fn field_to_wrapper(
+    field: &Field,
+    catch_unwind: bool,
+) -> Vec<(syn::Ident, proc_macro2::TokenStream)> {
     let ident = field
         .ident
         .as_ref()
         .expect("Fields must have idents (tuple structs are not supported)");
     let attrs = get_non_marker_attrs(field);
 
+    if has_marker_attr(field, FLATTEN) {
+        // For a sub API - hand out a view of it
+        let ty = &field.ty;
+        let as_ident = quote::format_ident!("as_{}", ident);
+        let tokens = quote! {
+            #(#attrs)*
+            pub fn #as_ident (&self) -> & #ty {
+                &self.#ident
+            }
+        };
+        return vec![(as_ident, tokens)];
+    }
+
+    if has_marker_attr(field, SYNC) {
+        let ref_ty = sync_reference(field);
+        let lifetime = &ref_ty.lifetime;
+        let ty = &ref_ty.elem;
+        let tokens = quote! {
+            #(#attrs)*
+            pub fn #ident (&self) -> ::dlopen2::wrapper::SyncGuard<'_, #lifetime, #ty> {
+                ::dlopen2::wrapper::SyncGuard::new(&self.#ident)
+            }
+        };
+        return vec![(ident.clone(), tokens)];
+    }
+
+    if has_marker_attr(field, RESULT) && !is_optional(field) {
+        panic!("Only optional functions can have the '{RESULT}' attribute assigned");
+    }
+    let catch_field = has_marker_attr(field, CATCH_UNWIND);
+    if catch_field && !matches!(skip_groups(&field.ty), Type::BareFn(fun) if fun.abi.is_none()) {
+        panic!("Only Rust-ABI functions can have the '{CATCH_UNWIND}' attribute assigned");
+    }
+
     match skip_groups(&field.ty) {
         Type::BareFn(fun) => {
             if fun.variadic.is_some() {
-                None
+                Vec::new()
             } else {
                 let output = &fun.output;
                 let unsafety = &fun.unsafety;
@@ -170,29 +710,36 @@ fn field_to_wrapper(field: &Field) -> Option<proc_macro2::TokenStream> {
                     ::std::option::Option::Some((ref arg_name, _)) => arg_name,
                     ::std::option::Option::None => unreachable!(),
                 });
-                Some(quote! {
+                // panics can only be caught for Rust-ABI functions,
+                // unwinding out of other ABIs is either undefined or aborts
+                if (catch_field || catch_unwind) && fun.abi.is_none() {
+                    let ret_ty = match output {
+                        syn::ReturnType::Default => quote!(()),
+                        syn::ReturnType::Type(_, ty) => quote!(#ty),
+                    };
+                    let tokens = quote! {
+                        #(#attrs)*
+                        pub #unsafety fn #ident (&self, #(#arg_iter),* ) -> ::std::thread::Result<#ret_ty> {
+                            ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                                (self.#ident)(#(#arg_names),*)
+                            }))
+                        }
+                    };
+                    return vec![(ident.clone(), tokens)];
+                }
+                // the ABI is part of the field type and the wrapper calls through the field,
+                // so unwinding ABIs like "C-unwind" keep their semantics
+                let tokens = quote! {
                     #(#attrs)*
                     pub #unsafety fn #ident (&self, #(#arg_iter),* ) #output {
                         (self.#ident)(#(#arg_names),*)
                     }
-                })
+                };
+                vec![(ident.clone(), tokens)]
             }
         }
         Type::Reference(ref_ty) => {
             let ty = &ref_ty.elem;
-            let mut_acc = match ref_ty.mutability {
-                Some(_token) => {
-                    let mut_ident = &format!("{ident}_mut");
-                    let method_name = syn::Ident::new(mut_ident, ident.span());
-                    Some(quote! {
-                        #(#attrs)*
-                        pub fn #method_name (&mut self) -> &mut #ty {
-                            self.#ident
-                        }
-                    })
-                }
-                None => None,
-            };
             // constant accessor
             let const_acc = quote! {
                 #(#attrs)*
@@ -200,13 +747,31 @@ fn field_to_wrapper(field: &Field) -> Option<proc_macro2::TokenStream> {
                     self.#ident
                 }
             };
-
-            Some(quote! {
-                #const_acc
-                #mut_acc
-            })
+            let mut methods = vec![(ident.clone(), const_acc)];
+            if ref_ty.mutability.is_some() {
+                let mut_ident = &format!("{ident}_mut");
+                let method_name = syn::Ident::new(mut_ident, ident.span());
+                let mut_acc = quote! {
+                    #(#attrs)*
+                    pub fn #method_name (&mut self) -> &mut #ty {
+                        self.#ident
+                    }
+                };
+                methods.push((method_name, mut_acc));
+            }
+            methods
+        }
+        Type::Ptr(_) => Vec::new(),
+        // For `field: usize` holding the address of the symbol
+        Type::Path(path) if path.path.is_ident("usize") => {
+            let tokens = quote! {
+                #(#attrs)*
+                pub fn #ident (&self) -> usize {
+                    self.#ident
+                }
+            };
+            vec![(ident.clone(), tokens)]
         }
-        Type::Ptr(_) => None,
         // For `field: Option<fn(...) -> ...>`
         Type::Path(path) => {
             let path = &path.path;
@@ -221,12 +786,17 @@ fn field_to_wrapper(field: &Field) -> Option<proc_macro2::TokenStream> {
                     GenericArgument::Type(ty) => match skip_groups(ty) {
                         Type::BareFn(fun) => {
                             if fun.variadic.is_some() {
-                                None
+                                Vec::new()
                             } else {
-                                let output = &fun.output;
-                                let output = match output {
-                                    syn::ReturnType::Default => quote!(-> Option<()>),
-                                    syn::ReturnType::Type(_, ty) => quote!( -> Option<#ty>),
+                                let as_result = has_marker_attr(field, RESULT);
+                                let ret_ty = match &fun.output {
+                                    syn::ReturnType::Default => quote!(()),
+                                    syn::ReturnType::Type(_, ty) => quote!(#ty),
+                                };
+                                let output = if as_result {
+                                    quote!(-> ::core::result::Result<#ret_ty, ::dlopen2::Error>)
+                                } else {
+                                    quote!(-> Option<#ret_ty>)
                                 };
                                 let unsafety = &fun.unsafety;
                                 let arg_iter = fun
@@ -238,16 +808,31 @@ fn field_to_wrapper(field: &Field) -> Option<proc_macro2::TokenStream> {
                                     ::std::option::Option::None => unreachable!(),
                                 });
                                 let has_ident = quote::format_ident!("has_{}", ident);
-                                Some(quote! {
+                                // absence of the function is an error, so that it cannot be
+                                // confused with a function returning an Option itself
+                                let body = if as_result {
+                                    quote! {
+                                        match self.#ident {
+                                            Some(f) => Ok((f)(#(#arg_names),*)),
+                                            None => Err(::dlopen2::Error::SymbolNotLoaded),
+                                        }
+                                    }
+                                } else {
+                                    quote!(self.#ident.map(|f| (f)(#(#arg_names),*)))
+                                };
+                                let call = quote! {
                                     #(#attrs)*
                                     pub #unsafety fn #ident (&self, #(#arg_iter),* ) #output {
-                                        self.#ident.map(|f| (f)(#(#arg_names),*))
+                                        #body
                                     }
+                                };
+                                let has = quote! {
                                     #(#attrs)*
                                     pub fn #has_ident (&self) -> bool {
                                         self.#ident.is_some()
                                     }
-                                })
+                                };
+                                vec![(ident.clone(), call), (has_ident, has)]
                             }
                         }
                         Type::Reference(ref_ty) => {
@@ -256,7 +841,7 @@ fn field_to_wrapper(field: &Field) -> Option<proc_macro2::TokenStream> {
                                 Some(_token) => {
                                     let mut_ident = &format!("{ident}");
                                     let method_name = syn::Ident::new(mut_ident, ident.span());
-                                    Some(quote! {
+                                    let tokens = quote! {
                                         #(#attrs)*
                                         pub fn #method_name (&mut self) -> ::core::option::Option<&mut #ty> {
                                             if let Some(&mut ref mut val) = self.#ident {
@@ -265,14 +850,18 @@ fn field_to_wrapper(field: &Field) -> Option<proc_macro2::TokenStream> {
                                                 None
                                             }
                                         }
-                                    })
+                                    };
+                                    vec![(method_name, tokens)]
+                                }
+                                None => {
+                                    let tokens = quote! {
+                                        #(#attrs)*
+                                        pub fn #ident (&self) -> ::core::option::Option<& #ty> {
+                                            self.#ident
+                                        }
+                                    };
+                                    vec![(ident.clone(), tokens)]
                                 }
-                                None => Some(quote! {
-                                    #(#attrs)*
-                                    pub fn #ident (&self) -> ::core::option::Option<& #ty> {
-                                        self.#ident
-                                    }
-                                }),
                             }
                         }
                         _ => panic!("Unsupported field type"),
@@ -0,0 +1,20 @@
+//Records the compiler, target and panic strategy for the default ABI tag (`raw::ABI_TAG`).
+use std::env;
+use std::process::Command;
+
+fn main() {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_owned())
+        .unwrap_or_else(|| "rustc unknown".to_owned());
+    let target = env::var("TARGET").unwrap_or_default();
+    let panic = env::var("CARGO_CFG_PANIC").unwrap_or_default();
+    println!("cargo:rustc-env=DLOPEN2_ABI_TAG={version} {target} panic={panic}");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
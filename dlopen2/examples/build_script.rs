@@ -0,0 +1,17 @@
+mod commons;
+
+use commons::example_lib_path;
+
+//this is what a build.rs file checking the example library would look like
+fn main() {
+    let lib_path = example_lib_path();
+    dlopen2::build::require_symbols(
+        &lib_path,
+        &[
+            "rust_fun_print_something",
+            "rust_fun_add_one",
+            "c_fun_add_two",
+        ],
+    );
+    println!("All required symbols are present");
+}
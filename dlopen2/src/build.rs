@@ -0,0 +1,75 @@
+/*!
+Helpers for checking symbol presence from build scripts.
+
+The functions of this module are intended to be called from a `build.rs` file only: they
+open the library at build time so that a missing symbol fails the build instead of the
+program at run time. They are not meant to be used by the program itself - the library
+found by the build script is not necessarily the one the program is going to load.
+
+```no_run
+// build.rs
+fn main() {
+    dlopen2::build::require_symbols(
+        "/usr/lib/libexample.so",
+        &["example_init", "example_run", "example_shutdown"],
+    );
+}
+```
+
+With `dlopen2` added to `[build-dependencies]`, the build above stops with a message naming
+every symbol the library does not provide.
+*/
+
+use super::err::Error;
+use super::raw::Library;
+use std::ffi::OsStr;
+use std::path::Path;
+
+/**
+Opens the library and returns the names from `symbols` that it does not provide.
+
+An empty vector means that all symbols are present.
+Failing to open the library is reported as an error.
+*/
+pub fn missing_symbols<'a, S>(library: S, symbols: &[&'a str]) -> Result<Vec<&'a str>, Error>
+where
+    S: AsRef<OsStr>,
+{
+    let lib = Library::open(library)?;
+    Ok(symbols
+        .iter()
+        .copied()
+        .filter(|name| !lib.has_symbol(name))
+        .collect())
+}
+
+/**
+Makes the build fail unless the library provides all `symbols`.
+
+The function panics, which aborts the build script, with a message listing all missing
+symbols or describing why the library could not be opened.
+If `library` is a path to an existing file, the build script is additionally asked
+to rerun whenever that file changes.
+*/
+pub fn require_symbols<S>(library: S, symbols: &[&str])
+where
+    S: AsRef<OsStr>,
+{
+    let library = library.as_ref();
+    if Path::new(library).is_file() {
+        println!("cargo:rerun-if-changed={}", Path::new(library).display());
+    }
+    match missing_symbols(library, symbols) {
+        Ok(missing) if missing.is_empty() => (),
+        Ok(missing) => panic!(
+            "Library {} is missing required symbols: {}",
+            library.to_string_lossy(),
+            missing.join(", ")
+        ),
+        Err(err) => panic!(
+            "Could not open library {} to check its symbols: {}",
+            library.to_string_lossy(),
+            err
+        ),
+    }
+}
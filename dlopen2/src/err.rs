@@ -1,23 +1,65 @@
 use std::convert::From;
 use std::error::Error as ErrorTrait;
-use std::ffi::NulError;
+use std::ffi::{NulError, OsString};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::io::Error as IoError;
 
 /// This is a library-specific error that is returned by all calls to all APIs.
+///
+/// Errors reported by the operating system (e.g. the complete `dlerror()` message on Unix)
+/// are preserved in the contained `std::io::Error` and included in the `Display` output.
+///
+/// New variants can be added in any release, so matching on the error needs a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Provided string could not be coverted into `std::ffi::CString` because it contained null
     /// character.
     NullCharacter(NulError),
     /// The library could not be opened.
     OpeningLibraryError(IoError),
+    /// The library could not be opened because it was built for a different architecture
+    /// (e.g. a 32-bit library in a 64-bit process).
+    ArchitectureMismatch(IoError),
     /// The symbol could not be obtained.
     SymbolGettingError(IoError),
     /// Value of the symbol was null.
     NullSymbol,
     /// Address could not be matched to a dynamic link library
     AddrNotMatchingDll(IoError),
+    /// The checksum of the library file did not match the expected one.
+    ChecksumMismatch,
+    /// None of the candidate libraries could be opened.
+    /// Contains the name of every candidate together with the reason it could not be opened.
+    NoCandidateOpened(Vec<(OsString, Error)>),
+    /// The symbol is thread-local, so its address is only valid for the calling thread.
+    ThreadLocalSymbol,
+    /// Information about the library could not be obtained.
+    LibraryInfoError(IoError),
+    /// The symbol no longer resolves to the address it was loaded from.
+    SymbolAddressMismatch(String),
+    /// The library exports a symbol that was marked as forbidden.
+    ForbiddenSymbol(String),
+    /// The type the symbol would be loaded as does not have the size of a pointer.
+    SymbolSizeMismatch(String),
+    /// An optional symbol was not loaded from the library.
+    SymbolNotLoaded,
+    /// The library name is empty, consists of whitespace only or contains a null character.
+    InvalidLibraryName(OsString),
+    /// The protection of the memory at the given address could not be obtained.
+    MemoryQueryError(IoError),
+    /// The file is an executable that the dynamic linker refuses to open as a library.
+    ExecutableNotLoadable(IoError),
+    /// The ABI tag exported by the library does not match the expected one.
+    /// Contains the tag of the library.
+    AbiTagMismatch(String),
+    /// The function is missing from the API manifest of the library or has a different signature.
+    SignatureMismatch(String),
+    /// A library that handed out the given number of raw symbols was closed,
+    /// so the symbols dangle if they are still in use.
+    DanglingSymbols(usize),
+    /// The library could not be closed.
+    ClosingLibraryError(IoError),
 }
 
 impl ErrorTrait for Error {
@@ -25,9 +67,26 @@ impl ErrorTrait for Error {
         use self::Error::*;
         match *self {
             NullCharacter(ref val) => Some(val),
-            OpeningLibraryError(_) | SymbolGettingError(_) | NullSymbol | AddrNotMatchingDll(_) => {
-                None
-            }
+            OpeningLibraryError(_)
+            | ArchitectureMismatch(_)
+            | SymbolGettingError(_)
+            | NullSymbol
+            | AddrNotMatchingDll(_)
+            | ChecksumMismatch
+            | ThreadLocalSymbol
+            | LibraryInfoError(_)
+            | NoCandidateOpened(_)
+            | SymbolAddressMismatch(_)
+            | ForbiddenSymbol(_)
+            | SymbolSizeMismatch(_)
+            | SymbolNotLoaded
+            | InvalidLibraryName(_)
+            | MemoryQueryError(_)
+            | ExecutableNotLoadable(_)
+            | AbiTagMismatch(_)
+            | SignatureMismatch(_)
+            | DanglingSymbols(_)
+            | ClosingLibraryError(_) => None,
         }
     }
 }
@@ -39,11 +98,57 @@ impl Display for Error {
         match self {
             NullCharacter(_) => write!(f, "String had a null character"),
             OpeningLibraryError(msg) => write!(f, "Could not open library: {msg}"),
+            ArchitectureMismatch(msg) => write!(
+                f,
+                "Could not open library built for a different architecture: {msg}"
+            ),
             SymbolGettingError(msg) => {
                 write!(f, "Could not obtain symbol from the library: {msg}")
             }
             NullSymbol => write!(f, "The symbol is NULL"),
             AddrNotMatchingDll(_) => write!(f, "Address does not match any dynamic link library"),
+            ChecksumMismatch => write!(f, "The checksum of the library file does not match"),
+            ThreadLocalSymbol => write!(f, "The symbol is thread-local"),
+            LibraryInfoError(msg) => {
+                write!(f, "Could not obtain information about the library: {msg}")
+            }
+            SymbolAddressMismatch(name) => {
+                write!(
+                    f,
+                    "The symbol {name} no longer resolves to the loaded address"
+                )
+            }
+            ForbiddenSymbol(name) => write!(f, "The library exports the forbidden symbol {name}"),
+            SymbolSizeMismatch(name) => write!(
+                f,
+                "The type of the symbol {name} does not have the size of a pointer"
+            ),
+            SymbolNotLoaded => write!(f, "The optional symbol was not loaded from the library"),
+            InvalidLibraryName(name) => write!(f, "Invalid library name {name:?}"),
+            MemoryQueryError(msg) => write!(f, "Could not query the memory protection: {msg}"),
+            ExecutableNotLoadable(msg) => {
+                write!(f, "Could not open an executable as a library: {msg}")
+            }
+            AbiTagMismatch(tag) => write!(
+                f,
+                "The ABI tag {tag:?} of the library does not match the expected one"
+            ),
+            SignatureMismatch(name) => write!(
+                f,
+                "The function {name} does not match the API manifest of the library"
+            ),
+            DanglingSymbols(count) => write!(
+                f,
+                "The library was closed after handing out {count} raw symbol(s)"
+            ),
+            ClosingLibraryError(msg) => write!(f, "Could not close library: {msg}"),
+            NoCandidateOpened(failures) => {
+                write!(f, "None of the candidate libraries could be opened")?;
+                for (name, err) in failures {
+                    write!(f, "\n{}: {err}", name.to_string_lossy())?;
+                }
+                Ok(())
+            }
         }
     }
 }
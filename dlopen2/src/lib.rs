@@ -81,7 +81,7 @@ Cargo.toml:
 
 ```toml
 [dependencies]
-dlopen2 = "0.8"
+dlopen2 = "0.9"
 ```
 
 # Documentation
@@ -130,6 +130,7 @@ At the moment none seems to have any reasonable advantage over the other.
 )]
 #![cfg_attr(feature = "doc_cfg", feature(doc_cfg))]
 
+pub mod build;
 mod err;
 pub mod raw;
 #[cfg(feature = "symbor")]
@@ -139,4 +140,14 @@ pub mod utils;
 #[cfg(feature = "wrapper")]
 #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "wrapper")))]
 pub mod wrapper;
+#[cfg(all(feature = "derive", feature = "wrapper"))]
+#[cfg_attr(
+    feature = "doc_cfg",
+    doc(cfg(all(feature = "derive", feature = "wrapper")))
+)]
+pub use dlopen2_derive::export_api;
 pub use err::Error;
+
+//lets code generated by the derive macros follow the `strict` feature of this crate
+#[doc(hidden)]
+pub const STRICT: bool = cfg!(feature = "strict");
@@ -0,0 +1,45 @@
+use std::ffi::CStr;
+
+/**
+Default ABI tag, describing the compiler, the target and the panic strategy.
+
+The Rust ABI (`fn` symbols, layouts of `repr(Rust)` types) is not stable, so Rust symbols
+can only be used safely if the library was built by the same compiler for the same target.
+Libraries exporting the tag with `export_abi_tag!()` can be checked against it with
+`Library::check_abi_tag()` before any of their Rust symbols are used.
+*/
+pub const ABI_TAG: &str = env!("DLOPEN2_ABI_TAG");
+
+//name of the symbol emitted by `export_abi_tag!()`
+pub(crate) const ABI_TAG_SYMBOL: &CStr = c"__dlopen2_abi_tag";
+
+/**
+ABI tag exported by a library, see `export_abi_tag!()`.
+
+The layout is fixed (`repr(C)`), so the tag can be read regardless of the compiler
+that built the library.
+*/
+#[repr(C)]
+#[derive(Debug)]
+pub struct AbiTag {
+    ptr: *const u8,
+    len: usize,
+}
+
+//the tag points to immutable static data
+unsafe impl Sync for AbiTag {}
+
+impl AbiTag {
+    /// Creates a tag, used by `export_abi_tag!()`.
+    pub const fn new(tag: &'static str) -> AbiTag {
+        AbiTag {
+            ptr: tag.as_ptr(),
+            len: tag.len(),
+        }
+    }
+
+    /// Returns the bytes of the tag.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::atomic::{
+    AtomicBool, AtomicI8, AtomicI16, AtomicI32, AtomicIsize, AtomicPtr, AtomicU8, AtomicU16,
+    AtomicU32, AtomicUsize,
+};
+#[cfg(target_has_atomic = "64")]
+use std::sync::atomic::{AtomicI64, AtomicU64};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/**
+Atomic types that can be used as views of globals exported by a library.
+
+Implemented for the atomic types of `std::sync::atomic`, see `Library::symbol_atomic_cstr()`.
+Each of them has the same size as the plain type it wraps (e.g. `AtomicI32` and `i32`),
+so the global is declared with the plain type in the library.
+*/
+pub trait AtomicSymbol: private::Sealed + Sync {}
+
+macro_rules! impl_atomic_symbol {
+    ($($ty:ty),*) => {
+        $(
+            impl private::Sealed for $ty {}
+            impl AtomicSymbol for $ty {}
+        )*
+    };
+}
+
+impl_atomic_symbol!(
+    AtomicBool,
+    AtomicI8,
+    AtomicI16,
+    AtomicI32,
+    AtomicIsize,
+    AtomicU8,
+    AtomicU16,
+    AtomicU32,
+    AtomicUsize
+);
+#[cfg(target_has_atomic = "64")]
+impl_atomic_symbol!(AtomicI64, AtomicU64);
+
+impl<T> private::Sealed for AtomicPtr<T> {}
+impl<T> AtomicSymbol for AtomicPtr<T> {}
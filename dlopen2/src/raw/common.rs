@@ -1,22 +1,69 @@
 use super::super::err::Error;
-use std::ffi::{CStr, CString, OsStr};
+use super::super::utils::{PLATFORM_FILE_EXTENSION, PLATFORM_FILE_PREFIX, platform_file_name};
+use std::borrow::Cow;
+use std::ffi::{CStr, CString, OsStr, OsString};
 
 //choose the right platform implementation here
 #[cfg(unix)]
 use super::unix::{
-    addr_info_cleanup, addr_info_init, addr_info_obtain, close_lib, get_sym, open_lib, open_self,
+    NOW_FLAGS, addr_info_cleanup, addr_info_init, addr_info_obtain, close_lib, find_lib,
+    find_lib_in, flags_binding_mode, get_sym, get_sym_case_insensitive, lib_entry_point,
+    lib_soname, open_lib, open_lib_in, open_lib_with_dir, open_self, try_close_lib,
 };
 #[cfg(windows)]
 use super::windows::{
-    addr_info_cleanup, addr_info_init, addr_info_obtain, close_lib, get_sym, open_lib, open_self,
+    NOW_FLAGS, addr_info_cleanup, addr_info_init, addr_info_obtain, close_lib, find_lib,
+    find_lib_in, flags_binding_mode, get_sym, get_sym_case_insensitive, lib_entry_point,
+    lib_soname, open_lib, open_lib_in, open_lib_with_dir, open_self, try_close_lib,
 };
 
 #[cfg(unix)]
 pub use super::unix::Handle;
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use super::unix::{get_local_sym, get_tls_sym};
+#[cfg(windows)]
+use super::version::Version;
 #[cfg(windows)]
 pub use super::windows::Handle;
+#[cfg(windows)]
+use super::windows::{
+    get_local_sym, lib_exported_symbols, lib_has_initializers, lib_undefined_symbols, lib_version,
+    open_lib_from_resource,
+};
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use {
+    super::dl_info::{DlInfoRequest, DlInfoValue},
+    super::unix::{
+        lib_exported_symbols, lib_has_initializers, lib_info, lib_undefined_symbols,
+        promote_lib_to_global,
+    },
+};
+#[cfg(unix)]
+use {
+    super::loader::LibraryLoader,
+    super::unix::{find_lib_fd, open_lib_fd},
+    std::os::fd::{AsRawFd, BorrowedFd},
+};
 
+use super::abi_tag::{ABI_TAG_SYMBOL, AbiTag};
+use super::atomic::AtomicSymbol;
+use super::error_hook::report_error;
+use super::lib_ref::LibRef;
+use super::name_buffer::NameBuffer;
+use super::raw_handle::RawHandle;
+#[cfg(feature = "testing")]
+use super::symbol_override::symbol_override;
+use super::symbol_sig::SymbolSig;
+use super::symbol_source::SymbolSource;
 use std::mem::{size_of, transmute_copy};
+use std::os::raw::c_char;
+use std::path::Path;
+#[cfg(windows)]
+use std::path::PathBuf;
+#[cfg(debug_assertions)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "checksum")]
+use std::{fs::File, io};
 
 /**
 Main interface for opening and working with a dynamic link library.
@@ -33,13 +80,49 @@ C-strings.
 **Note:** The handle to the library gets released when the library object gets dropped.
 Unless your application opened the library multiple times, this is the moment when symbols
 obtained from the library become dangling symbols.
+In builds with debug assertions, calling `set_track_symbols(true)` makes the library count
+the symbols obtained using `symbol()` or `symbol_cstr()` and report `Error::DanglingSymbols`
+to the error hook (see `set_error_hook()`) if it gets closed after handing out any, unless it
+is managed by one of the high-level APIs. The tracking is compiled out in release builds.
 */
 #[derive(Debug)]
 pub struct Library {
     handle: Handle,
+    flags: Option<i32>,
+    newly_loaded: bool,
+    close_on_unwind: bool,
+    //number of raw symbols handed out, used for detecting potentially dangling symbols
+    #[cfg(debug_assertions)]
+    raw_symbols: AtomicUsize,
+    #[cfg(debug_assertions)]
+    track_symbols: bool,
+    #[cfg(debug_assertions)]
+    managed: bool,
+    //file the library was extracted to by open_from_resource(), removed when the library is dropped
+    #[cfg(windows)]
+    temp_file: Option<PathBuf>,
 }
 
 impl Library {
+    pub(crate) fn from_handle(handle: Handle, flags: Option<i32>, newly_loaded: bool) -> Library {
+        #[cfg(feature = "open_count")]
+        super::open_count::register(handle);
+        Library {
+            handle,
+            flags,
+            newly_loaded,
+            close_on_unwind: true,
+            #[cfg(debug_assertions)]
+            raw_symbols: AtomicUsize::new(0),
+            #[cfg(debug_assertions)]
+            track_symbols: false,
+            #[cfg(debug_assertions)]
+            managed: false,
+            #[cfg(windows)]
+            temp_file: None,
+        }
+    }
+
     /**
     Open a dynamic library.
 
@@ -50,6 +133,13 @@ impl Library {
     Please refer to your operating system guide for precise information about the directories
     where the operating system searches for dynamic link libraries.
 
+    **Note:** Executables, including position-independent ones (PIE), generally can't be opened
+    as libraries. glibc 2.30 and newer refuses to open them, which is reported as
+    `Error::ExecutableNotLoadable`. Other dynamic linkers may open a PIE, but only the symbols
+    it was linked to export (e.g. using `-rdynamic`) can be obtained. To use symbols of the
+    current program use `open_self()`, otherwise move the shared code of the program into
+    a library.
+
     # Example
 
     ```no_run
@@ -67,9 +157,7 @@ impl Library {
     where
         S: AsRef<OsStr>,
     {
-        Ok(Self {
-            handle: unsafe { open_lib(name.as_ref(), None) }?,
-        })
+        Self::open_with_flags(name, None)
     }
 
     /**
@@ -101,11 +189,392 @@ impl Library {
     where
         S: AsRef<OsStr>,
     {
-        Ok(Self {
-            handle: unsafe { open_lib(name.as_ref(), flags) }?,
+        let name = name.as_ref();
+        Self::open_checked(name, || {
+            let newly_loaded = unsafe { find_lib(name) }.is_none();
+            Ok(Self::from_handle(
+                unsafe { open_lib(name, flags) }?,
+                flags,
+                newly_loaded,
+            ))
+        })
+    }
+
+    //validates the name and reports failures of the opening to the error hook
+    pub(crate) fn open_checked<F>(name: &OsStr, open: F) -> Result<Library, Error>
+    where
+        F: FnOnce() -> Result<Library, Error>,
+    {
+        check_name(name)
+            .and_then(|()| open())
+            .inspect_err(|err| report_error(|| name.to_string_lossy(), err))
+    }
+
+    /**
+    Open a dynamic library, looking for it and its dependencies in the given directory too.
+
+    This allows loading libraries that expect their dependencies to be found relative to the
+    working directory without changing it, which would affect the whole process.
+    On Windows the directory is added using `AddDllDirectory()` for the duration of the load only
+    and the library is loaded with `LOAD_LIBRARY_SEARCH_DEFAULT_DIRS`, so the directory is
+    searched for both the library and its dependencies. Loads using this function are serialized
+    to keep the directory from affecting other ones.
+    `dlopen()` on Unix systems has no per-load search path, so a relative `name` is only looked up
+    in the directory first. Dependencies are found if the library references them using
+    `$ORIGIN` in its `RPATH`/`RUNPATH`, the search path of the process cannot be changed
+    for a single load.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        let lib = Library::open_with_cwd("libplugin.so", "plugins/example", None).unwrap();
+    }
+    ```
+    */
+    pub fn open_with_cwd<S, P>(name: S, cwd: P, flags: Option<i32>) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+        P: AsRef<Path>,
+    {
+        let name = name.as_ref();
+        Self::open_checked(name, || {
+            let newly_loaded = unsafe { find_lib(name) }.is_none();
+            Ok(Self::from_handle(
+                unsafe { open_lib_with_dir(name, cwd.as_ref(), flags) }?,
+                flags,
+                newly_loaded,
+            ))
+        })
+    }
+
+    /**
+    Open a dynamic library that is embedded as an `RT_RCDATA` resource of another module.
+
+    Windows can only load libraries from files, so the resource gets copied to a newly created
+    file in the temporary directory which is then loaded. The file is removed when the returned
+    `Library` is dropped, or right away if loading fails. Removing it fails silently if the library
+    is still loaded by another handle, e.g. one obtained with `Library::open()` using its path.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        let exe = Library::open_self().unwrap();
+        let lib = Library::open_from_resource(&exe, 101, None).unwrap();
+    }
+    ```
+    */
+    #[cfg(windows)]
+    pub fn open_from_resource(
+        module: &Library,
+        resource_id: u16,
+        flags: Option<i32>,
+    ) -> Result<Library, Error> {
+        let (handle, path) = unsafe { open_lib_from_resource(module.handle, resource_id, flags) }
+            .inspect_err(|err| {
+            report_error(|| format!("resource {resource_id}").into(), err)
+        })?;
+        //the file was just created, so nobody could have loaded it before
+        let mut lib = Self::from_handle(handle, flags, true);
+        lib.temp_file = Some(path);
+        Ok(lib)
+    }
+
+    /**
+    Checks that a library can be opened and provides all `required` symbols, then closes it.
+
+    Returns the first failure: the error of opening the library or of obtaining
+    one of the symbols. This is intended for validating plugins without keeping them loaded.
+
+    **Note:** Opening the library runs its initialization code (e.g. constructors or `DllMain`),
+    so only libraries that are trusted to be loaded should be validated.
+    The library is not unloaded if it was already loaded, e.g. by another `Library`.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        match Library::validate("plugins/libplugin.so", &[c"plugin_init", c"plugin_run"]) {
+            Ok(()) => println!("The plugin is valid"),
+            Err(err) => println!("The plugin is invalid: {err}"),
+        }
+    }
+    ```
+    */
+    pub fn validate<S>(path: S, required: &[&CStr]) -> Result<(), Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        let lib = Self::open(path)?;
+        for name in required {
+            let _: *const () = unsafe { lib.untracked_symbol_cstr(name) }?;
+        }
+        Ok(())
+    }
+
+    /**
+    Open a dynamic library resolving all its relocations immediately.
+
+    On unix-like platforms libraries are opened with `RTLD_NOW` instead of the default `RTLD_LAZY`,
+    so a missing dependency or symbol referenced by the library makes opening fail
+    instead of crashing the program once the affected function gets called.
+    Windows always resolves imports at load time, so there this is the same as `open()`.
+    */
+    pub fn open_now<S>(name: S) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        Self::open_with_flags(name, NOW_FLAGS)
+    }
+
+    /**
+    Open the first dynamic library from the list of candidates that can be opened.
+
+    The candidates are tried in order, which is useful when a library is known under
+    different names or paths across platforms and distributions.
+    If none of them can be opened, `Error::NoCandidateOpened` listing all the failures is returned.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        let lib = Library::open_first(&["libssl.so.3", "libssl.so.1.1", "libssl.so"], None)
+            .unwrap();
+    }
+    ```
+    */
+    pub fn open_first<S>(candidates: &[S], flags: Option<i32>) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        let mut failures = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            match Self::open_with_flags(candidate, flags) {
+                Ok(lib) => return Ok(lib),
+                Err(err) => failures.push((candidate.as_ref().to_os_string(), err)),
+            }
+        }
+        Err(Error::NoCandidateOpened(failures))
+    }
+
+    /**
+    Open a dynamic library, trying platform-specific variants of the name if needed.
+
+    For the name `foo` (which may include a directory) the following file names are tried,
+    in this order:
+
+    1. the name as given (`foo`),
+    1. the platform-specific file name (`libfoo.so`, `libfoo.dylib` or `foo.dll`,
+       see `utils::platform_file_name()`),
+    1. the platform-specific file name with the versions 0 to 9 (`libfoo.so.0`, `libfoo.0.dylib`
+       or `foo-0.dll` and so on).
+
+    The first one that can be opened is returned. If none of them can be opened,
+    `Error::NoCandidateOpened` listing all the failures is returned.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        //opens e.g. libz.so.1 if there is no libz.so
+        let lib = Library::open_smart("z", None).unwrap();
+    }
+    ```
+    */
+    pub fn open_smart<S>(name: S, flags: Option<i32>) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        Self::open_first(&smart_candidates(name.as_ref()), flags)
+    }
+
+    /**
+    Equivalent of the `open` method but converts the name using a caller-provided buffer.
+
+    Reusing the same `NameBuffer` across calls avoids allocating a new null-terminated copy
+    of the name every time a library is opened.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::{Library, NameBuffer};
+
+    fn main() {
+        let mut buffer = NameBuffer::new();
+        let libm = Library::open_in("libm.so.6", &mut buffer).unwrap();
+        let libc = Library::open_in("libc.so.6", &mut buffer).unwrap();
+    }
+    ```
+    */
+    pub fn open_in<S>(name: S, buffer: &mut NameBuffer) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        Self::open_with_flags_in(name, buffer, None)
+    }
+
+    /// Equivalent of the `open_with_flags` method but converts the name using a
+    /// caller-provided buffer.
+    pub fn open_with_flags_in<S>(
+        name: S,
+        buffer: &mut NameBuffer,
+        flags: Option<i32>,
+    ) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        let name = name.as_ref();
+        Self::open_checked(name, || {
+            let newly_loaded = unsafe { find_lib_in(name, &mut buffer.library) }.is_none();
+            Ok(Self::from_handle(
+                unsafe { open_lib_in(name, &mut buffer.library, flags) }?,
+                flags,
+                newly_loaded,
+            ))
         })
     }
 
+    /**
+    Open a dynamic library with flags without blocking the async runtime.
+
+    Opening a library may take a noticeable amount of time (disk access, relocations and
+    initialization code of the library). This function performs `open_with_flags()` on the
+    blocking thread pool of the current tokio runtime.
+
+    **Note:** This function needs to be called from within a tokio runtime.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    async fn load_plugin() {
+        let lib = Library::open_async("libyourplugin.so", None).await.unwrap();
+    }
+    ```
+    */
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
+    pub fn open_async<S>(
+        name: S,
+        flags: Option<i32>,
+    ) -> impl Future<Output = Result<Library, Error>> + Send + 'static
+    where
+        S: AsRef<OsStr>,
+    {
+        let name = name.as_ref().to_os_string();
+        async move {
+            match tokio::task::spawn_blocking(move || Self::open_with_flags(name, flags)).await {
+                Ok(result) => result,
+                Err(err) => match err.try_into_panic() {
+                    Ok(panic) => std::panic::resume_unwind(panic),
+                    Err(err) => Err(Error::OpeningLibraryError(std::io::Error::other(err))),
+                },
+            }
+        }
+    }
+
+    /**
+    Open a dynamic library after verifying the SHA-256 hash of its file.
+
+    Opening a library runs its initialization code, so the file is read and hashed first.
+    `Error::ChecksumMismatch` is returned if the hash differs from `expected_hash`, without
+    opening the library. The hash needs to be computed from the file the platform loader
+    maps as well, so `name` should be a path, not just a file name.
+
+    On platforms supported by `open_from_fd()` the verified file is opened through
+    its descriptor, which prevents replacing the file between verification and loading.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        let expected_hash: [u8; 32] = [0; 32]; //obtained from a trusted source
+        let lib = Library::open_verified("/opt/plugins/libplugin.so", &expected_hash, None).unwrap();
+    }
+    ```
+    */
+    #[cfg(feature = "checksum")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "checksum")))]
+    pub fn open_verified<S>(
+        name: S,
+        expected_hash: &[u8; 32],
+        flags: Option<i32>,
+    ) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        use sha2::{Digest, Sha256};
+
+        let mut file = File::open(name.as_ref()).map_err(Error::OpeningLibraryError)?;
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher).map_err(Error::OpeningLibraryError)?;
+        let hash: [u8; 32] = hasher.finalize().into();
+        if &hash != expected_hash {
+            return Err(Error::ChecksumMismatch);
+        }
+        #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+        {
+            use std::os::fd::AsFd;
+            Self::open_from_fd(file.as_fd(), flags)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+        {
+            drop(file);
+            Self::open_with_flags(name, flags)
+        }
+    }
+
+    /**
+    Open a dynamic library from an already opened file descriptor.
+
+    This allows loading a file that was previously verified without reopening it by path,
+    which would allow the file to be replaced in the meantime.
+    The descriptor only needs to stay valid for the duration of this call.
+
+    **Note:** Support depends on the platform:
+
+    * Linux and Android - the library is opened through its `/proc/self/fd/N` path,
+      so procfs needs to be mounted.
+    * FreeBSD - the library is opened using `fdlopen()`.
+    * Other platforms - `Error::OpeningLibraryError` with `ErrorKind::Unsupported` is returned.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+    use std::fs::File;
+    use std::os::fd::AsFd;
+
+    fn main() {
+        let file = File::open("/lib/i386-linux-gnu/libm.so.6").unwrap();
+        //verify the contents of the file here
+        let lib = Library::open_from_fd(file.as_fd(), None).unwrap();
+    }
+    ```
+    */
+    #[cfg(unix)]
+    pub fn open_from_fd(fd: BorrowedFd<'_>, flags: Option<i32>) -> Result<Library, Error> {
+        let newly_loaded = unsafe { find_lib_fd(fd) }.is_none();
+        let handle = unsafe { open_lib_fd(fd, flags) }.inspect_err(|err| {
+            report_error(|| format!("file descriptor {}", fd.as_raw_fd()).into(), err)
+        })?;
+        Ok(Self::from_handle(handle, flags, newly_loaded))
+    }
+
     /**
     Open the main program itself as a library.
 
@@ -113,9 +582,9 @@ impl Library {
     into.
     */
     pub fn open_self() -> Result<Library, Error> {
-        Ok(Self {
-            handle: unsafe { open_self() }?,
-        })
+        let handle =
+            unsafe { open_self() }.inspect_err(|err| report_error(|| "self".into(), err))?;
+        Ok(Self::from_handle(handle, None, false))
     }
 
     /**
@@ -130,6 +599,18 @@ impl Library {
     Therefore allowing it here would bring many problems, especially if user obtains references
     or functions.
     This method checks the address value and returns `Error::NullSymbol` error if the value is null.
+
+    **Note:** For thread-local symbols the OS returns the address of the instance belonging
+    to the calling thread. Such an address must not be shared with other threads, which is
+    easy to do by accident since `Library` is `Send` and `Sync`. On Linux (glibc) thread-local
+    symbols (including ones defined by dependencies of the library) are detected and
+    `Error::ThreadLocalSymbol` is returned.
+
+    **Note:** On Linux (glibc) a specific version of a symbol can be requested by appending it
+    to the name, as in `memcpy@GLIBC_2.14` (`dlvsym()` is used then). Other Unix systems
+    return `Error::SymbolGettingError` for such names. On Windows `@` is part of decorated
+    names (e.g. `_func@8`), which are looked up as they are.
+
     If your code does require obtaining symbols with null value, please do something like this:
 
     # Example
@@ -158,81 +639,971 @@ impl Library {
         }
     }
 
+    /// Equivalent of the `symbol` method but converts the name using a caller-provided buffer.
+    pub unsafe fn symbol_in<T>(&self, name: &str, buffer: &mut NameBuffer) -> Result<T, Error> {
+        unsafe { self.symbol_cstr(buffer.symbol_cstr(name)?) }
+    }
+
     /// Equivalent of the `symbol` method but takes `CStr` as a argument.
     pub unsafe fn symbol_cstr<T>(&self, name: &CStr) -> Result<T, Error> {
-        unsafe {
-            //TODO: convert it to some kind of static assertion (not yet supported in Rust)
-            //this comparison should be calculated by compiler at compilation time - zero cost
-            if size_of::<T>() != size_of::<*mut ()>() {
-                panic!(
-                    "The type passed to dlopen2::Library::symbol() function has a different size than a \
-                 pointer - cannot transmute"
-                );
-            }
-            let raw = get_sym(self.handle, name)?;
-            if raw.is_null() {
-                Err(Error::NullSymbol)
-            } else {
-                Ok(transmute_copy(&raw))
-            }
-        }
+        let symbol = unsafe { self.untracked_symbol_cstr(name) }?;
+        self.count_raw_symbol();
+        Ok(symbol)
     }
 
     /**
-    Returns the raw OS handle for the opened library.
+    Obtains a function symbol as the function pointer type named by the `S` marker.
 
-    This is `HMODULE` on Windows and `*mut c_void` on Unix systems. Don't use unless absolutely necessary.
+    This is `symbol_cstr()` with the target type fixed by a `SymbolSig` implementation
+    instead of inferred from the context, so a mistake in the signature only needs
+    to be fixed in a single place and a differently typed variable fails to compile.
+
+    # Safety
+
+    The signature named by `S` needs to match the signature of the function in the library.
     */
-    pub unsafe fn into_raw(&self) -> Handle {
-        self.handle
+    pub unsafe fn symbol_typed_cstr<S: SymbolSig>(&self, name: &CStr) -> Result<S::Sig, Error> {
+        unsafe { self.symbol_cstr(name) }
     }
-}
 
-impl Drop for Library {
-    fn drop(&mut self) {
-        self.handle = close_lib(self.handle);
+    /// Equivalent of the `symbol_typed_cstr` method but takes `&str` as a argument.
+    pub unsafe fn symbol_typed<S: SymbolSig>(&self, name: &str) -> Result<S::Sig, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.symbol_typed_cstr::<S>(cname.as_ref())
+        }
     }
-}
 
-unsafe impl Sync for Library {}
-unsafe impl Send for Library {}
+    /**
+    Checks that the library exports the expected ABI tag (see `export_abi_tag!()`).
 
-/// Container for information about overlapping symbol from dynamic load library.
-#[derive(Debug)]
-pub struct OverlappingSymbol {
-    /// Overlapping symbol name
-    pub name: String,
-    /// Overlapping symbol address
-    pub addr: *const (),
-}
+    This is meant to be called before using Rust symbols (e.g. `fn` pointers or references
+    to `repr(Rust)` types) of the library, whose ABI may differ between compilers.
+    Usually `expected` is `raw::ABI_TAG`, which is the tag of the current compiler.
+    `Error::AbiTagMismatch` is returned if the tags differ and `Error::SymbolGettingError`
+    if the library does not export a tag.
+    */
+    pub fn check_abi_tag(&self, expected: &str) -> Result<(), Error> {
+        let tag: &AbiTag = unsafe { self.untracked_symbol_cstr(ABI_TAG_SYMBOL) }?;
+        if tag.as_bytes() == expected.as_bytes() {
+            Ok(())
+        } else {
+            Err(Error::AbiTagMismatch(
+                String::from_utf8_lossy(tag.as_bytes()).into_owned(),
+            ))
+        }
+    }
 
-/// Container for information about an address obtained from dynamic load library.
-#[derive(Debug)]
-pub struct AddressInfo {
-    /// Path to the library that is the source of this symbol.
-    pub dll_path: String,
-    /// Base address of the library that is the source of this symbol.
-    pub dll_base_addr: *const (),
-    /// Information about the overlapping symbol from the dynamic load library.
-    ///
-    /// The information is optional since the given address may not overlap with any symbol.
-    pub overlapping_symbol: Option<OverlappingSymbol>,
-}
+    /**
+    Obtains a symbol and intentionally keeps the library loaded forever.
 
-/// Obtains information about an address previously loaded from a dynamic load library.
-pub struct AddressInfoObtainer {}
+    The library is leaked, so the returned symbol can never dangle. This makes it possible
+    to store function pointers obtained from the library in statics:
 
-impl Default for AddressInfoObtainer {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+    ```no_run
+    use dlopen2::raw::Library;
+    use std::sync::LazyLock;
 
-impl AddressInfoObtainer {
-    pub fn new() -> AddressInfoObtainer {
-        unsafe { addr_info_init() };
-        AddressInfoObtainer {}
-    }
+    static ADD_ONE: LazyLock<fn(i32) -> i32> = LazyLock::new(|| {
+        let lib = Library::open("libexample.so").expect("Could not open library");
+        unsafe { lib.leak_and_symbol_cstr(c"add_one") }.expect("Could not load symbol")
+    });
+
+    fn main() {
+        println!("{}", ADD_ONE(1));
+    }
+    ```
+
+    If the symbol cannot be obtained the library is closed and the error is returned.
+    */
+    pub unsafe fn leak_and_symbol_cstr<T: 'static>(self, name: &CStr) -> Result<T, Error> {
+        let symbol = unsafe { self.untracked_symbol_cstr(name) }?;
+        std::mem::forget(self);
+        Ok(symbol)
+    }
+
+    /// Equivalent of the `leak_and_symbol_cstr` method but takes `&str` as a argument.
+    pub unsafe fn leak_and_symbol<T: 'static>(self, name: &str) -> Result<T, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.leak_and_symbol_cstr(cname.as_ref())
+        }
+    }
+
+    /**
+    Obtains a symbol that is defined by the library itself.
+
+    `symbol()` may return a symbol defined by one of the dependencies of the library
+    (on Linux `dlsym()` searches the whole dependency tree of the handle, which also includes
+    libraries opened with `RTLD_GLOBAL`). This method works the same way but returns
+    `Error::SymbolGettingError` if the symbol is not defined by this very library.
+    On Windows it rejects exports forwarded to other modules.
+    */
+    #[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+    pub unsafe fn symbol_local<T>(&self, name: &str) -> Result<T, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.symbol_local_cstr(cname.as_ref())
+        }
+    }
+
+    /// Equivalent of the `symbol_local` method but takes `CStr` as a argument.
+    #[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+    pub unsafe fn symbol_local_cstr<T>(&self, name: &CStr) -> Result<T, Error> {
+        let symbol = unsafe { self.untracked_symbol_with(name, get_local_sym) }?;
+        self.count_raw_symbol();
+        Ok(symbol)
+    }
+
+    /**
+    Obtains a symbol, ignoring the case of its name.
+
+    On Windows `GetProcAddress()` is case-sensitive, so if there is no export with the exact name
+    the export table is searched for one matching it ignoring (ASCII) case. If several exports
+    match, the first one in the export table is used. This helps with legacy code relying
+    on loose matching, but is slower than `symbol()`.
+    On other platforms symbol names are always case-sensitive and this is the same as `symbol()`.
+    */
+    pub unsafe fn symbol_case_insensitive<T>(&self, name: &str) -> Result<T, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.symbol_case_insensitive_cstr(cname.as_ref())
+        }
+    }
+
+    /// Equivalent of the `symbol_case_insensitive` method but takes `CStr` as a argument.
+    pub unsafe fn symbol_case_insensitive_cstr<T>(&self, name: &CStr) -> Result<T, Error> {
+        let symbol = unsafe { self.untracked_symbol_with(name, get_sym_case_insensitive) }?;
+        self.count_raw_symbol();
+        Ok(symbol)
+    }
+
+    unsafe fn untracked_symbol_cstr<T>(&self, name: &CStr) -> Result<T, Error> {
+        unsafe { self.untracked_symbol_with(name, get_sym) }
+    }
+
+    unsafe fn untracked_symbol_with<T>(
+        &self,
+        name: &CStr,
+        get: unsafe fn(Handle, &CStr) -> Result<*mut (), Error>,
+    ) -> Result<T, Error> {
+        unsafe { self.unreported_symbol_with(name, get) }
+            .inspect_err(|err| report_error(|| name.to_string_lossy(), err))
+    }
+
+    //same as symbol_cstr(), but failures are left to the caller to report
+    pub(crate) unsafe fn probe_symbol_cstr<T>(&self, name: &CStr) -> Result<T, Error> {
+        let symbol = unsafe { self.unreported_symbol_with(name, get_sym) }?;
+        self.count_raw_symbol();
+        Ok(symbol)
+    }
+
+    unsafe fn unreported_symbol_with<T>(
+        &self,
+        name: &CStr,
+        get: unsafe fn(Handle, &CStr) -> Result<*mut (), Error>,
+    ) -> Result<T, Error> {
+        #[cfg(feature = "testing")]
+        if let Some(raw) = symbol_override(name) {
+            return unsafe { cast_symbol(raw) };
+        }
+        unsafe { get(self.handle, name).and_then(|raw| cast_symbol(raw)) }
+    }
+
+    /**
+    Obtains a reference to statically allocated data in the library.
+
+    The returned `LibRef` borrows the library, so unlike references obtained with `symbol()`
+    it cannot outlive it.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::{LibRef, Library};
+
+    struct Plugin<'a> {
+        version: LibRef<'a, u32>,
+    }
+
+    fn main() {
+        let lib = Library::open("libyourlib.so").unwrap();
+        let plugin = Plugin {
+            version: unsafe { lib.reference("version") }.unwrap(),
+        };
+        println!("Plugin version: {}", *plugin.version);
+    }
+    ```
+    */
+    pub unsafe fn reference<T>(&self, name: &str) -> Result<LibRef<'_, T>, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.reference_cstr(cname.as_ref())
+        }
+    }
+
+    /// Equivalent of the `reference` method but takes `CStr` as a argument.
+    pub unsafe fn reference_cstr<T>(&self, name: &CStr) -> Result<LibRef<'_, T>, Error> {
+        unsafe { Ok(LibRef::new(self.untracked_symbol_cstr(name)?)) }
+    }
+
+    /**
+    Obtains an atomic view of a mutable global exported by the library.
+
+    This allows globals that are shared between threads to be read and modified with atomic
+    operations, e.g. an `int` global of a C library can be obtained as `AtomicI32` (see
+    `AtomicSymbol` for the supported types). The returned reference borrows the library.
+    `Error::SymbolGettingError` is returned if the global is not aligned as required
+    by the atomic type, which can happen for 64-bit globals on 32-bit platforms.
+
+    # Safety
+
+    Besides the type of the global matching the atomic type, every access to the global needs
+    to be atomic for as long as the view is used - including the accesses made by the library
+    itself and through other references obtained with e.g. `symbol()`. Mixing atomic and
+    non-atomic accesses from different threads is a data race and undefined behavior.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    fn main() {
+        let lib = Library::open("libyourlib.so").unwrap();
+        let counter: &AtomicI32 = unsafe { lib.symbol_atomic_cstr(c"counter") }.unwrap();
+        counter.fetch_add(1, Ordering::SeqCst);
+    }
+    ```
+    */
+    pub unsafe fn symbol_atomic_cstr<A: AtomicSymbol>(&self, name: &CStr) -> Result<&A, Error> {
+        let ptr: *const A = unsafe { self.untracked_symbol_cstr(name) }?;
+        if !ptr.is_aligned() {
+            return Err(Error::SymbolGettingError(std::io::Error::other(format!(
+                "The symbol is not aligned to {} bytes as required by the atomic type",
+                align_of::<A>()
+            ))));
+        }
+        Ok(unsafe { &*ptr })
+    }
+
+    /// Equivalent of the `symbol_atomic_cstr` method but takes `&str` as a argument.
+    pub unsafe fn symbol_atomic<A: AtomicSymbol>(&self, name: &str) -> Result<&A, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.symbol_atomic_cstr(cname.as_ref())
+        }
+    }
+
+    /**
+    Obtains the instance of a thread-local variable (`__thread` or `thread_local`)
+    belonging to the calling thread.
+
+    `symbol()` rejects such symbols with `Error::ThreadLocalSymbol`, because the address
+    is easily shared with other threads by accident. This method is meant for code that does
+    want the instance of the current thread: the instance gets resolved (and allocated if the
+    thread did not use it yet) the way `__tls_get_addr()` does it for the library itself.
+    `Error::SymbolGettingError` is returned for symbols that are not thread-local variables
+    of the library or of its dependencies.
+
+    # Safety
+
+    Besides the type matching the variable, the returned reference is valid only on the calling
+    thread and only until the thread exits - it must not be sent to or used by other threads,
+    even though nothing in its type prevents that. Each thread needs to obtain its own instance.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+    use std::os::raw::c_int;
+
+    fn main() {
+        let libc = Library::open("libc.so.6").unwrap();
+        let errno: &c_int = unsafe { libc.symbol_tls_cstr(c"errno") }.unwrap();
+        println!("errno of this thread: {}", errno);
+    }
+    ```
+    */
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    pub unsafe fn symbol_tls_cstr<T>(&self, name: &CStr) -> Result<&T, Error> {
+        let ptr: *const T = unsafe { self.untracked_symbol_with(name, get_tls_sym) }?;
+        Ok(unsafe { &*ptr })
+    }
+
+    /// Equivalent of the `symbol_tls_cstr` method but takes `&str` as a argument.
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    pub unsafe fn symbol_tls<T>(&self, name: &str) -> Result<&T, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.symbol_tls_cstr(cname.as_ref())
+        }
+    }
+
+    /**
+    Obtains a slice over data exported by the library whose length is exported as another symbol.
+
+    This covers self-describing tables, e.g. `const entry_t table[]` together with
+    `const size_t table_len`. The length symbol is read as `L` (usually `usize` or `u32`, matching
+    its type in the library) and gives the number of elements, not bytes. The returned slice
+    borrows the library. `Error::SymbolGettingError` is returned if the length can't be converted
+    to `usize`, if the slice would be larger than `isize::MAX` bytes or if the data is not aligned
+    as required by `T`.
+
+    # Safety
+
+    Both symbols must have the given types and the data must contain at least `len` valid values
+    of `T` for as long as the slice is used.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        let lib = Library::open("libyourlib.so").unwrap();
+        let table: &[u16] =
+            unsafe { lib.symbol_sized_slice_cstr::<u16, u32>(c"table", c"table_len") }.unwrap();
+        println!("{} entries", table.len());
+    }
+    ```
+    */
+    pub unsafe fn symbol_sized_slice_cstr<T, L>(
+        &self,
+        data_name: &CStr,
+        len_name: &CStr,
+    ) -> Result<&[T], Error>
+    where
+        L: Copy + TryInto<usize>,
+    {
+        let len: *const L = unsafe { self.untracked_symbol_cstr(len_name) }?;
+        //the length is copied out, so it doesn't need to be aligned like the data
+        let len: usize = unsafe { len.read_unaligned() }.try_into().map_err(|_| {
+            Error::SymbolGettingError(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "The length of the slice does not fit into usize",
+            ))
+        })?;
+        if size_of::<T>().saturating_mul(len) > isize::MAX as usize {
+            return Err(Error::SymbolGettingError(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "The slice would be larger than isize::MAX bytes",
+            )));
+        }
+        let data: *const T = unsafe { self.untracked_symbol_cstr(data_name) }?;
+        if !data.is_aligned() {
+            return Err(Error::SymbolGettingError(std::io::Error::other(format!(
+                "The symbol is not aligned to {} bytes as required by the element type",
+                align_of::<T>()
+            ))));
+        }
+        Ok(unsafe { std::slice::from_raw_parts(data, len) })
+    }
+
+    /// Equivalent of the `symbol_sized_slice_cstr` method but takes `&str` as arguments.
+    pub unsafe fn symbol_sized_slice<T, L>(
+        &self,
+        data_name: &str,
+        len_name: &str,
+    ) -> Result<&[T], Error>
+    where
+        L: Copy + TryInto<usize>,
+    {
+        unsafe {
+            let data_cname = CString::new(data_name)?;
+            let len_cname = CString::new(len_name)?;
+            self.symbol_sized_slice_cstr::<T, L>(data_cname.as_ref(), len_cname.as_ref())
+        }
+    }
+
+    /**
+    Copies `len` bytes starting at the address of the symbol.
+
+    This is intended for extracting data embedded in libraries (e.g. configuration blobs)
+    and for tools dumping exported data. The returned bytes are a copy, so they stay valid
+    after the library is closed.
+
+    # Safety
+
+    At least `len` bytes starting at the symbol need to be readable, i.e. belong to the symbol
+    or to memory of the library following it.
+    */
+    pub unsafe fn symbol_bytes_cstr(&self, name: &CStr, len: usize) -> Result<Vec<u8>, Error> {
+        let data: *const u8 = unsafe { self.untracked_symbol_cstr(name) }?;
+        Ok(unsafe { std::slice::from_raw_parts(data, len) }.to_vec())
+    }
+
+    /// Equivalent of the `symbol_bytes_cstr` method but takes `&str` as a argument.
+    pub unsafe fn symbol_bytes(&self, name: &str, len: usize) -> Result<Vec<u8>, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.symbol_bytes_cstr(cname.as_ref(), len)
+        }
+    }
+
+    /**
+    Obtains a table of strings exported by the library.
+
+    The symbol needs to be a list of null-terminated strings terminated by an empty string
+    (`"first\0second\0\0"`), the format of e.g. Windows environment blocks. The returned strings
+    point directly into the library and borrow it. An empty table consists of a single null byte.
+
+    # Safety
+
+    The table must be terminated by an empty string. A missing terminator is undefined behavior:
+    the memory after the table gets read until two consecutive null bytes are found.
+    */
+    pub unsafe fn symbol_string_table_cstr(&self, name: &CStr) -> Result<Vec<&CStr>, Error> {
+        let mut ptr: *const c_char = unsafe { self.untracked_symbol_cstr(name) }?;
+        let mut strings = Vec::new();
+        unsafe {
+            while *ptr != 0 {
+                let string = CStr::from_ptr(ptr);
+                ptr = ptr.add(string.count_bytes() + 1);
+                strings.push(string);
+            }
+        }
+        Ok(strings)
+    }
+
+    /// Equivalent of the `symbol_string_table_cstr` method but takes `&str` as a argument.
+    pub unsafe fn symbol_string_table(&self, name: &str) -> Result<Vec<&CStr>, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.symbol_string_table_cstr(cname.as_ref())
+        }
+    }
+
+    /**
+    Checks if the library exports a symbol with the given name.
+
+    Unlike `symbol()` this only performs the lookup and does not require choosing a type.
+    Symbols with a null value and symbols that could not be obtained are both reported as absent.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        let lib = Library::open("libyourlib.so").unwrap();
+        if lib.has_symbol("symbolname") {
+            println!("The symbol is present");
+        }
+    }
+    ```
+    */
+    pub fn has_symbol(&self, name: &str) -> bool {
+        match CString::new(name) {
+            Ok(cname) => self.has_symbol_cstr(cname.as_ref()),
+            Err(_) => false,
+        }
+    }
+
+    /// Equivalent of the `has_symbol` method but takes `CStr` as a argument.
+    pub fn has_symbol_cstr(&self, name: &CStr) -> bool {
+        match unsafe { get_sym(self.handle, name) } {
+            Ok(raw) => !raw.is_null(),
+            //present, only not usable through symbol()
+            Err(Error::ThreadLocalSymbol) => true,
+            Err(_) => false,
+        }
+    }
+
+    /**
+    Makes symbols of the already opened library available for symbol resolution of
+    libraries that are loaded later, as if it was opened with `RTLD_GLOBAL`.
+
+    This reopens the library with `RTLD_GLOBAL | RTLD_NOLOAD`, which changes the visibility
+    of the existing mapping. The change is permanent and outlives this `Library` object.
+
+    **Note:** This is only available on Linux with glibc. Other platforms either do not
+    support changing the visibility of a loaded library or do not distinguish between local
+    and global visibility at all (Windows).
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::Library;
+
+    fn main() {
+        let lib = Library::open("libyourplugin.so").unwrap();
+        //initialize the plugin, then expose its symbols to plugins loaded afterwards
+        lib.promote_to_global().unwrap();
+    }
+    ```
+    */
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    pub fn promote_to_global(&self) -> Result<(), Error> {
+        unsafe { promote_lib_to_global(self.handle) }
+    }
+
+    /**
+    Obtains information about the opened library using `dlinfo()`.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::{DlInfoRequest, DlInfoValue, Library};
+
+    fn main() {
+        let lib = Library::open("libyourplugin.so").unwrap();
+        if let DlInfoValue::Origin(dir) = lib.info(DlInfoRequest::Origin).unwrap() {
+            println!("Loaded from {}", dir.display());
+        }
+    }
+    ```
+    */
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    pub fn info(&self, request: DlInfoRequest) -> Result<DlInfoValue, Error> {
+        unsafe { lib_info(self.handle, request) }
+    }
+
+    /**
+    Returns a loader opening further libraries through the `dlopen()` exported by this library.
+
+    The library needs to export `dlopen()` and `dlerror()`, which is the case for libdl
+    and libc on most systems. Like `symbol()` the lookup includes the dependencies of the library,
+    e.g. the functions of libc are returned for a library that doesn't define its own.
+    See `LibraryLoader` for when this is useful.
+    */
+    #[cfg(unix)]
+    pub fn as_loader(&self) -> Result<LibraryLoader<'_>, Error> {
+        unsafe {
+            Ok(LibraryLoader::new(
+                self.untracked_symbol_cstr(c"dlopen")?,
+                self.untracked_symbol_cstr(c"dlerror")?,
+            ))
+        }
+    }
+
+    /**
+    Returns the SONAME of the library.
+
+    On Linux the SONAME is read from the `DT_SONAME` entry of the dynamic section of the library.
+    It is the canonical, usually versioned, name of the library (e.g. `libc.so.6`), which may
+    differ from the name of the file that got opened. `None` is returned if the library
+    has no SONAME and on platforms whose libraries don't have one (Windows, macOS).
+    Other Unix systems return `Error::LibraryInfoError`.
+    */
+    pub fn soname(&self) -> Result<Option<String>, Error> {
+        unsafe { lib_soname(self.handle) }
+    }
+
+    /**
+    Returns the address of the entry point of the library.
+
+    On Linux (glibc) this is `e_entry` of the ELF header adjusted by the load address,
+    on Windows `AddressOfEntryPoint` of the PE header (usually `DllMain`). `None` is returned
+    if the library has no entry point, which is common for ELF libraries, and always on macOS.
+    Other Unix systems return `Error::LibraryInfoError`. The address is only meant for
+    introspection, entry points are called by the loader.
+    */
+    pub fn entry_point(&self) -> Result<Option<*const ()>, Error> {
+        unsafe { lib_entry_point(self.handle) }
+    }
+
+    /**
+    Checks whether the library declares functions that run when it gets loaded.
+
+    On Linux (glibc) this looks for `DT_INIT` and a non-empty `DT_INIT_ARRAY` in the dynamic
+    section of the library, which is where `__attribute__((constructor))` functions end up.
+    On Windows a module entry point (`DllMain`) or TLS callbacks count as initializers.
+
+    This is purely introspective: by the time it can be called the initializers have
+    already run. It allows hosts to log or reject such libraries, e.g. before loading them
+    into another, more restricted process.
+    */
+    #[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+    pub fn has_initializers(&self) -> Result<bool, Error> {
+        unsafe { lib_has_initializers(self.handle) }
+    }
+
+    /**
+    Lists all symbols exported by the library together with their addresses.
+
+    This is more efficient than obtaining every symbol by its name.
+    On Linux (glibc) the dynamic symbol table of the library is read, thread-local symbols
+    are not included because they have no address shared by all threads.
+    On Windows the export table of the module is read, exports forwarded to other
+    modules are not included.
+    */
+    #[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+    pub fn exported_symbols(&self) -> Result<Vec<ExportedSymbol>, Error> {
+        unsafe { lib_exported_symbols(self.handle) }
+    }
+
+    /**
+    Lists the names of the symbols the library imports from other libraries.
+
+    This helps diagnosing which import could not be satisfied when loading a library fails
+    because of a missing symbol (of a library that could be loaded lazily or a different build).
+    On Linux (glibc) these are the undefined entries of the dynamic symbol table of the library,
+    which does not tell which library should provide them.
+    On Windows the import table of the module is read, functions imported by ordinal
+    are listed as `#<ordinal>` and delay-loaded imports are not included.
+    */
+    #[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+    pub fn undefined_symbols(&self) -> Result<Vec<String>, Error> {
+        unsafe { lib_undefined_symbols(self.handle) }
+    }
+
+    /**
+    Reads the file version from the version resource (`VERSIONINFO`) of the library.
+
+    This allows logging or checking the versions of plugins without them exporting
+    a version symbol. `None` is returned if the library has no version resource.
+    The resource is read from the file the library was loaded from.
+    */
+    #[cfg(windows)]
+    pub fn file_version(&self) -> Result<Option<Version>, Error> {
+        unsafe { lib_version(self.handle, false) }
+    }
+
+    /// Same as `file_version()`, but reads the version of the product the library belongs to.
+    #[cfg(windows)]
+    pub fn product_version(&self) -> Result<Option<Version>, Error> {
+        unsafe { lib_version(self.handle, true) }
+    }
+
+    /**
+    Returns whether opening the library actually loaded it into the process.
+
+    `false` means that the library was already loaded (e.g. opened before or loaded as
+    a dependency of another library) and opening it only incremented its reference count.
+    This helps deciding who is responsible for initializing the library.
+
+    **Note:** The check is performed right before the library gets opened, so if another thread
+    loads the same library at the same time, the result may be inaccurate.
+    If it can't be checked (`open_from_fd()` on platforms without procfs), `true` is reported.
+    The main program opened with `open_self()` is never newly loaded.
+    */
+    pub fn was_newly_loaded(&self) -> bool {
+        self.newly_loaded
+    }
+
+    /**
+    Sets whether the library gets closed when it is dropped while the thread is panicking.
+
+    Normally a failure to close the library causes a panic. During unwinding that would abort
+    the process and hide the original panic, so the failure only gets reported to the hook set
+    by `set_error_hook()` instead. Passing `false` keeps the library loaded for the rest of the life of
+    the process when it is dropped during unwinding, which also protects destructors that still
+    use its symbols. The default is `true`.
+    */
+    pub fn set_close_on_unwind(&mut self, close: bool) {
+        self.close_on_unwind = close;
+    }
+
+    /**
+    Returns the flags the library was opened with.
+
+    `None` means that the platform default flags were used.
+    Together with the library name this allows reopening the library the same way,
+    for example when reloading it.
+    */
+    pub fn flags(&self) -> Option<i32> {
+        self.flags
+    }
+
+    /**
+    Returns when the symbols the library imports get resolved, derived from its flags.
+
+    This tells whether a missing dependency symbol fails the loading of the library or only
+    the first call of a function using it. On Unix systems this is `BindingMode::Now`
+    if the library was opened with `RTLD_NOW` (e.g. by `open_now()`) and `BindingMode::Lazy`
+    otherwise. Setting the `LD_BIND_NOW` environment variable or reopening a library that was
+    already loaded with different flags changes the actual behavior without being reflected here.
+    On Windows imports are always resolved when the library gets loaded.
+    */
+    pub fn binding_mode(&self) -> BindingMode {
+        flags_binding_mode(self.flags)
+    }
+
+    /**
+    Returns the raw OS handle for the opened library.
+
+    This is `HMODULE` on Windows and `*mut c_void` on Unix systems. Don't use unless absolutely necessary.
+    The handle can be converted to a `RawHandle` to store it in a platform independent way.
+    */
+    pub unsafe fn into_raw(&self) -> Handle {
+        self.handle
+    }
+
+    /**
+    Creates a library from a raw OS handle.
+
+    The library takes over one reference to the handle and releases it when dropped.
+    The flags the library was opened with are not known, so `flags()` returns `None`.
+
+    # Safety
+
+    The handle must be a valid handle of an opened library (e.g. obtained from `into_raw()`
+    of a library that was then forgotten using `std::mem::forget()`) and the reference
+    it represents must not be released by anybody else.
+    */
+    pub unsafe fn from_raw(handle: RawHandle) -> Library {
+        Self::from_handle(handle.into(), None, false)
+    }
+
+    /**
+    Enables or disables counting the symbols handed out by `symbol()` and `symbol_cstr()`.
+
+    While enabled, closing the library after it handed out any such symbol reports
+    `Error::DanglingSymbols` to the error hook, because the symbols dangle
+    if they are still in use. Libraries managed by one of the high-level APIs
+    never report it, their symbols borrow the library. Tracking is disabled by default.
+
+    **Note:** This is a debugging aid, in builds without debug assertions it does nothing.
+    */
+    pub fn set_track_symbols(&mut self, track: bool) {
+        #[cfg(debug_assertions)]
+        {
+            self.track_symbols = track;
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = track;
+    }
+
+    #[inline]
+    fn count_raw_symbol(&self) {
+        #[cfg(debug_assertions)]
+        if self.track_symbols {
+            self.raw_symbols.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Marks the library as owned by one of the high-level APIs, which protect obtained
+    /// symbols from dangling.
+    #[cfg(any(feature = "wrapper", feature = "symbor"))]
+    pub(crate) fn set_managed(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            self.managed = true;
+        }
+    }
+
+    /// Reverts `set_managed()` when one of the high-level APIs gives up the library.
+    #[cfg(feature = "symbor")]
+    pub(crate) fn set_unmanaged(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            self.managed = false;
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    pub(crate) fn dangling_symbols(&self) -> Option<Error> {
+        let raw_symbols = self.raw_symbols.load(Ordering::Relaxed);
+        if self.managed || raw_symbols == 0 {
+            None
+        } else {
+            Some(Error::DanglingSymbols(raw_symbols))
+        }
+    }
+}
+
+pub(crate) unsafe fn cast_symbol<T>(raw: *mut ()) -> Result<T, Error> {
+    unsafe {
+        //TODO: convert it to some kind of static assertion (not yet supported in Rust)
+        //this comparison should be calculated by compiler at compilation time - zero cost
+        if size_of::<T>() != size_of::<*mut ()>() {
+            panic!(
+                "The type passed to dlopen2::Library::symbol() function has a different size than a \
+             pointer - cannot transmute"
+            );
+        }
+        if raw.is_null() {
+            Err(Error::NullSymbol)
+        } else {
+            Ok(transmute_copy(&raw))
+        }
+    }
+}
+
+//Platform loaders treat empty names specially (dlopen() returns the handle of the program)
+//and names containing a null character would get truncated.
+fn check_name(name: &OsStr) -> Result<(), Error> {
+    let bytes = name.as_encoded_bytes();
+    if bytes.iter().all(u8::is_ascii_whitespace) || bytes.contains(&0) {
+        Err(Error::InvalidLibraryName(name.to_os_string()))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+impl Library {
+    fn remove_temp_file(&mut self) {
+        if let Some(path) = self.temp_file.take() {
+            //fails if the library is still loaded by another handle, nothing can be done then
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+impl Library {
+    //name of the library for errors reported to the hook when it gets dropped
+    fn reported_name(&self) -> Cow<'static, str> {
+        match self.soname() {
+            Ok(Some(soname)) => soname.into(),
+            _ => "library".into(),
+        }
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        if let Some(err) = self.dangling_symbols() {
+            report_error(|| self.reported_name(), &err);
+        }
+        if std::thread::panicking() {
+            //panicking again would abort the process and hide the original panic
+            if self.close_on_unwind {
+                #[cfg(feature = "open_count")]
+                super::open_count::unregister(self.handle);
+                if let Err(err) = try_close_lib(self.handle) {
+                    report_error(|| self.reported_name(), &err);
+                }
+                #[cfg(windows)]
+                self.remove_temp_file();
+            }
+            return;
+        }
+        #[cfg(feature = "open_count")]
+        super::open_count::unregister(self.handle);
+        self.handle = close_lib(self.handle);
+        #[cfg(windows)]
+        self.remove_temp_file();
+    }
+}
+
+unsafe impl Sync for Library {}
+unsafe impl Send for Library {}
+
+impl SymbolSource for Library {
+    fn get_symbol(&self, name: &CStr) -> Result<*const (), Error> {
+        unsafe { self.untracked_symbol_cstr(name) }
+    }
+
+    unsafe fn symbol_cstr<T>(&self, name: &CStr) -> Result<T, Error> {
+        unsafe { Library::symbol_cstr(self, name) }
+    }
+
+    fn has_symbol(&self, name: &str) -> bool {
+        Library::has_symbol(self, name)
+    }
+
+    //dlsym() also finds the symbols of dependencies and treats null symbols as missing,
+    //so the export table of the library is used where it can be read
+    fn exports_symbol(&self, name: &str) -> bool {
+        #[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+        if let Ok(symbols) = self.exported_symbols() {
+            return symbols.iter().any(|symbol| symbol.name == name);
+        }
+        Library::has_symbol(self, name)
+    }
+}
+
+/// Time at which the symbols imported by a library get resolved, see `Library::binding_mode()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingMode {
+    /// Functions get resolved when they are called for the first time.
+    Lazy,
+    /// All symbols get resolved when the library is loaded.
+    Now,
+}
+
+/// Container for information about overlapping symbol from dynamic load library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlappingSymbol {
+    /// Overlapping symbol name
+    pub name: String,
+    /// Overlapping symbol address
+    pub addr: *const (),
+}
+
+impl OverlappingSymbol {
+    /// Returns the human-readable form of the symbol name.
+    ///
+    /// This is `None` if the name is not a mangled Rust or C++ name.
+    #[cfg(feature = "demangle")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "demangle")))]
+    pub fn demangled_name(&self) -> Option<String> {
+        demangle_name(&self.name)
+    }
+}
+
+#[cfg(feature = "demangle")]
+pub(crate) fn demangle_name(name: &str) -> Option<String> {
+    if let Ok(demangled) = rustc_demangle::try_demangle(name) {
+        return Some(format!("{demangled:#}"));
+    }
+    cpp_demangle::Symbol::new(name)
+        .ok()
+        .and_then(|symbol| symbol.demangle(&Default::default()).ok())
+}
+
+/// Symbol exported by a dynamic link library, see `Library::exported_symbols()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedSymbol {
+    /// Name of the symbol
+    pub name: String,
+    /// Address of the symbol
+    pub addr: *const (),
+}
+
+/// Container for information about an address obtained from dynamic load library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressInfo {
+    /// Path to the library that is the source of this symbol.
+    pub dll_path: String,
+    /// Base address of the library that is the source of this symbol.
+    pub dll_base_addr: *const (),
+    /// Information about the overlapping symbol from the dynamic load library.
+    ///
+    /// The information is optional since the given address may not overlap with any symbol.
+    pub overlapping_symbol: Option<OverlappingSymbol>,
+    /// Offset of the queried address from the start of the overlapping symbol.
+    ///
+    /// This is 0 if the address points exactly at the symbol or if there is no overlapping symbol.
+    pub offset_from_symbol: usize,
+}
+
+/// Obtains information about an address previously loaded from a dynamic load library.
+pub struct AddressInfoObtainer {
+    demangle: Option<bool>,
+}
+
+impl Default for AddressInfoObtainer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddressInfoObtainer {
+    pub fn new() -> AddressInfoObtainer {
+        Self::builder().build()
+    }
+
+    /**
+    Creates a builder that allows configuring how the information gets obtained.
+
+    # Example
+
+    ```no_run
+    use dlopen2::raw::AddressInfoObtainer;
+    fn main() {
+        let aio = AddressInfoObtainer::builder()
+            .symbol_search_path("C:\\symbols")
+            .demangle(true)
+            .build();
+    }
+    ```
+    */
+    pub fn builder() -> AddressInfoObtainerBuilder {
+        AddressInfoObtainerBuilder::default()
+    }
 
     /**
     Obtains information about an address previously loaded from a dynamic load library.
@@ -253,13 +1624,60 @@ impl AddressInfoObtainer {
         if let Some(os) = addr_info.overlapping_symbol{
             println!("Overlapping symbol name: {}", &os.name);
             println!("Overlapping symbol address: {:?}", os.addr);
+            println!("Offset from the symbol: {}", addr_info.offset_from_symbol);
         }
 
     }
     ```
     */
     pub unsafe fn obtain(&self, addr: *const ()) -> Result<AddressInfo, Error> {
-        unsafe { addr_info_obtain(addr) }
+        unsafe { addr_info_obtain(addr, self.demangle) }
+    }
+}
+
+/// Builder of a configured `AddressInfoObtainer`.
+#[derive(Debug, Clone, Default)]
+pub struct AddressInfoObtainerBuilder {
+    symbol_search_path: Option<OsString>,
+    demangle: Option<bool>,
+}
+
+impl AddressInfoObtainerBuilder {
+    /**
+    Sets the paths where the symbol handler looks for debug symbols (`.pdb` files).
+
+    Multiple paths are separated by semicolons. The symbol handler is shared by the whole
+    process, so this changes the search path of all obtainers.
+    Currently this only has an effect on Windows.
+    */
+    pub fn symbol_search_path<S>(mut self, path: S) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        self.symbol_search_path = Some(path.as_ref().to_os_string());
+        self
+    }
+
+    /**
+    Sets whether names of overlapping symbols get demangled.
+
+    On Windows this toggles the `SYMOPT_UNDNAME` option of the symbol handler, which is
+    enabled by default. On Unix systems names are returned mangled by default and demangling
+    Rust and C++ symbols requires the `demangle` feature, without it this option is ignored.
+    Regardless of this option the `demangle` feature also provides
+    `OverlappingSymbol::demangled_name()`.
+    */
+    pub fn demangle(mut self, demangle: bool) -> Self {
+        self.demangle = Some(demangle);
+        self
+    }
+
+    /// Creates the configured `AddressInfoObtainer`.
+    pub fn build(self) -> AddressInfoObtainer {
+        unsafe { addr_info_init(self.symbol_search_path.as_deref()) };
+        AddressInfoObtainer {
+            demangle: self.demangle,
+        }
     }
 }
 
@@ -268,3 +1686,28 @@ impl Drop for AddressInfoObtainer {
         unsafe { addr_info_cleanup() }
     }
 }
+
+//names tried by `Library::open_smart()`, in order
+fn smart_candidates(name: &OsStr) -> Vec<OsString> {
+    let mut candidates = vec![name.to_os_string()];
+    let path = Path::new(name);
+    let Some(core_name) = path.file_name() else {
+        return candidates;
+    };
+    let dir = path.parent().unwrap_or(Path::new(""));
+    candidates.push(dir.join(platform_file_name(core_name)).into_os_string());
+    for version in 0..10 {
+        let mut file_name = OsString::from(PLATFORM_FILE_PREFIX);
+        file_name.push(core_name);
+        if cfg!(any(target_os = "macos", target_os = "ios")) {
+            file_name.push(format!(".{version}.{PLATFORM_FILE_EXTENSION}"));
+        } else if cfg!(windows) {
+            file_name.push(format!("-{version}.{PLATFORM_FILE_EXTENSION}"));
+        } else {
+            file_name.push(format!(".{PLATFORM_FILE_EXTENSION}.{version}"));
+        }
+        candidates.push(dir.join(file_name).into_os_string());
+    }
+    candidates.dedup();
+    candidates
+}
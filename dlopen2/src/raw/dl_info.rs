@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+/// Kind of information about an opened library that can be obtained with `Library::info()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DlInfoRequest {
+    /// Directory the library was loaded from (`RTLD_DI_ORIGIN`).
+    Origin,
+    /// Entry of the library in the list of loaded objects (`RTLD_DI_LINKMAP`).
+    LinkMap,
+}
+
+/// Information about an opened library returned by `Library::info()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DlInfoValue {
+    /// Directory the library was loaded from.
+    Origin(PathBuf),
+    /// Entry of the library in the list of loaded objects.
+    LinkMap {
+        /// Address at which the library got loaded
+        /// (the difference between addresses in the file and in memory).
+        base_addr: *const (),
+        /// Path of the library. It is empty for the main program.
+        path: PathBuf,
+    },
+}
@@ -0,0 +1,68 @@
+use super::super::err::Error;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+type ErrorHook = Arc<dyn Fn(&str, &Error) + Send + Sync>;
+
+static HOOK: RwLock<Option<ErrorHook>> = RwLock::new(None);
+//checked first, so that failures cost nothing more than an atomic load while no hook is set
+static HOOK_SET: AtomicBool = AtomicBool::new(false);
+
+/**
+Sets a function that gets called whenever opening a library or obtaining a symbol fails.
+
+The hook receives the name of the library or symbol together with the error before the error
+gets returned, which allows logging all failures in one place. It is global for the whole process
+and replaces any previously set hook. Failures of all APIs of the crate are reported,
+including symbols of optional fields that are missing in the library.
+
+The hook is called without holding any lock, so it may open libraries or replace the hook.
+
+# Example
+
+```no_run
+use dlopen2::raw::{Library, set_error_hook};
+
+fn main() {
+    set_error_hook(|name, err| eprintln!("dlopen2 failed for {name}: {err}"));
+    let _ = Library::open("libmissing.so");
+}
+```
+*/
+pub fn set_error_hook<F>(hook: F)
+where
+    F: Fn(&str, &Error) + Send + Sync + 'static,
+{
+    let mut guard = HOOK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *guard = Some(Arc::new(hook));
+    HOOK_SET.store(true, Ordering::Release);
+}
+
+/// Removes the hook set by `set_error_hook()`.
+pub fn clear_error_hook() {
+    let mut guard = HOOK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    HOOK_SET.store(false, Ordering::Release);
+    *guard = None;
+}
+
+pub(crate) fn report_error<'a, F>(name: F, err: &Error)
+where
+    F: FnOnce() -> Cow<'a, str>,
+{
+    if !HOOK_SET.load(Ordering::Acquire) {
+        return;
+    }
+    //the lock is released before calling the hook, which may set a hook or fail again itself
+    let hook = HOOK
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if let Some(hook) = hook {
+        hook(&name(), err);
+    }
+}
@@ -0,0 +1,59 @@
+use super::super::err::Error;
+#[cfg(unix)]
+use super::unix::lib_exports;
+#[cfg(windows)]
+use super::windows::lib_exports;
+use std::ffi::OsStr;
+
+/**
+Exports of a dynamic link library that was inspected without loading it.
+
+This is intended for static analysis of libraries whose dependencies may not be installed:
+the dependencies of an inspected library are never resolved and none of its code runs - which
+also means that its symbols can only be listed, not obtained or called.
+
+On Windows the library gets mapped as an image resource (`LOAD_LIBRARY_AS_IMAGE_RESOURCE`)
+and its export table is read. `dlopen()` has no equivalent mode, so on Unix systems
+the file gets parsed instead. This works for ELF files only, other formats
+(e.g. Mach-O on macOS) are reported as unsupported. Since the file is not searched for in the
+library paths, a path to the file should be provided.
+
+# Example
+
+```no_run
+use dlopen2::raw::InspectedLibrary;
+
+fn main() {
+    let lib = InspectedLibrary::open("plugins/libplugin.so").unwrap();
+    for name in lib.exports() {
+        println!("{name}");
+    }
+}
+```
+*/
+#[derive(Debug, Clone)]
+pub struct InspectedLibrary {
+    exports: Vec<String>,
+}
+
+impl InspectedLibrary {
+    /// Inspects the library using provided file name or path.
+    pub fn open<S>(name: S) -> Result<InspectedLibrary, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        Ok(InspectedLibrary {
+            exports: lib_exports(name.as_ref())?,
+        })
+    }
+
+    /// Returns names of all symbols exported by the library.
+    pub fn exports(&self) -> impl Iterator<Item = &str> {
+        self.exports.iter().map(String::as_str)
+    }
+
+    /// Checks if the library exports a symbol with the given name.
+    pub fn has_export(&self, name: &str) -> bool {
+        self.exports.iter().any(|export| export == name)
+    }
+}
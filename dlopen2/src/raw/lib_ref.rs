@@ -0,0 +1,24 @@
+use std::ops::Deref;
+
+/// Safe wrapper around a const reference to statically allocated data in a `Library`.
+///
+/// Unlike a plain reference obtained through `Library::symbol()`, the lifetime of `LibRef` is
+/// tied to the library it was obtained from. This allows storing it in structures while the
+/// compiler makes sure that it never outlives the library.
+#[derive(Debug, Clone, Copy)]
+pub struct LibRef<'lib, T: 'lib> {
+    reference: &'lib T,
+}
+
+impl<'lib, T> LibRef<'lib, T> {
+    pub fn new(reference: &'lib T) -> LibRef<'lib, T> {
+        LibRef { reference }
+    }
+}
+
+impl<'lib, T> Deref for LibRef<'lib, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.reference
+    }
+}
@@ -0,0 +1,120 @@
+use super::super::err::Error;
+use super::common::Library;
+use super::error_hook::report_error;
+use std::ffi::{CStr, CString, OsStr};
+use std::io::{Error as IoError, ErrorKind};
+
+/**
+Set of libraries whose symbols are searched together.
+
+This models a combined namespace for functionality split across several libraries
+without making their symbols global (`RTLD_GLOBAL`). Symbols are searched for in the libraries
+in the order they were added and the first library providing the symbol wins.
+All libraries are kept loaded for as long as the set exists.
+
+**Note:** When the set is dropped, the libraries are closed in the reverse order of adding them
+(the library added last is closed first). Libraries that depend on libraries added before them
+are therefore closed before their dependencies, as long as they were added in dependency order.
+
+# Example
+
+```no_run
+use dlopen2::raw::LibrarySet;
+
+fn main() {
+    let mut set = LibrarySet::new();
+    set.open("libplugin_core.so").unwrap();
+    set.open("libplugin_extras.so").unwrap();
+    let run: unsafe extern "C" fn() = unsafe { set.symbol("plugin_run") }.unwrap();
+    unsafe { run() };
+}
+```
+*/
+#[derive(Debug, Default)]
+pub struct LibrarySet {
+    libraries: Vec<Library>,
+}
+
+impl LibrarySet {
+    /// Creates an empty set.
+    pub fn new() -> LibrarySet {
+        LibrarySet::default()
+    }
+
+    /// Adds an already opened library, which is searched after all libraries added before.
+    pub fn push(&mut self, lib: Library) {
+        self.libraries.push(lib);
+    }
+
+    /// Opens the library using provided file name or path and adds it to the set.
+    pub fn open<S>(&mut self, name: S) -> Result<(), Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        self.push(Library::open(name)?);
+        Ok(())
+    }
+
+    /// Removes the library at the given position and returns it, keeping the order
+    /// of the remaining libraries. Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Library {
+        self.libraries.remove(index)
+    }
+
+    /// Returns the libraries of the set in the order they are searched.
+    pub fn libraries(&self) -> &[Library] {
+        &self.libraries
+    }
+
+    /**
+    Obtains the symbol from the first library of the set that provides it.
+
+    Only libraries that don't provide the symbol at all are skipped: if the first library
+    providing it reports an error (like `Error::NullSymbol`), that error is returned.
+    If no library provides the symbol, the error of the last library is returned.
+    See `Library::symbol()` for the requirements on `T`.
+    */
+    pub unsafe fn symbol_cstr<T>(&self, name: &CStr) -> Result<T, Error> {
+        //libraries skipped in favor of later ones must not be reported to the error hook
+        let mut last_error = None;
+        for lib in &self.libraries {
+            match unsafe { lib.probe_symbol_cstr(name) } {
+                Err(err @ Error::SymbolGettingError(_)) => last_error = Some(err),
+                result => {
+                    return result.inspect_err(|err| report_error(|| name.to_string_lossy(), err));
+                }
+            }
+        }
+        let err = last_error.unwrap_or_else(|| {
+            Error::SymbolGettingError(IoError::new(
+                ErrorKind::NotFound,
+                "The library set is empty",
+            ))
+        });
+        report_error(|| name.to_string_lossy(), &err);
+        Err(err)
+    }
+
+    /// Equivalent of the `symbol_cstr` method but takes `&str` as a argument.
+    pub unsafe fn symbol<T>(&self, name: &str) -> Result<T, Error> {
+        unsafe {
+            let cname = CString::new(name)?;
+            self.symbol_cstr(cname.as_ref())
+        }
+    }
+}
+
+impl From<Vec<Library>> for LibrarySet {
+    fn from(libraries: Vec<Library>) -> LibrarySet {
+        LibrarySet { libraries }
+    }
+}
+
+impl Drop for LibrarySet {
+    fn drop(&mut self) {
+        //a Vec drops its elements in insertion order
+        while let Some(lib) = self.libraries.pop() {
+            drop(lib);
+        }
+    }
+}
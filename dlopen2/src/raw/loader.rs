@@ -0,0 +1,61 @@
+use super::super::err::Error;
+use super::common::Library;
+use super::unix::{DlerrorFn, DlopenFn, find_lib, open_lib_through};
+use std::ffi::OsStr;
+use std::marker::PhantomData;
+
+/**
+Opens libraries through the `dlopen()` exported by a specific library.
+
+Created by `Library::as_loader()`. On Android the dynamic linker decides in which linker
+namespace a library gets loaded based on the caller of `dlopen()`, so calling the function of
+a particular libdl (or a library bundling its own loader) can change which libraries are
+visible. The loader borrows the library it was obtained from, which keeps its functions valid.
+
+The opened libraries are ordinary `Library` objects: their handles are only valid within
+the dynamic linker of the process, so they get closed with the regular `dlclose()`.
+
+# Example
+
+```no_run
+use dlopen2::raw::Library;
+
+fn main() {
+    let libdl = Library::open("libdl.so").unwrap();
+    let loader = libdl.as_loader().unwrap();
+    let lib = loader.open("libexample.so", None).unwrap();
+}
+```
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct LibraryLoader<'lib> {
+    dlopen: DlopenFn,
+    dlerror: DlerrorFn,
+    pd: PhantomData<&'lib Library>,
+}
+
+impl<'lib> LibraryLoader<'lib> {
+    pub(crate) fn new(dlopen: DlopenFn, dlerror: DlerrorFn) -> LibraryLoader<'lib> {
+        LibraryLoader {
+            dlopen,
+            dlerror,
+            pd: PhantomData,
+        }
+    }
+
+    /// Opens a library using the `dlopen()` of the loader, see `Library::open_with_flags()`.
+    pub fn open<S>(&self, name: S, flags: Option<i32>) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        let name = name.as_ref();
+        Library::open_checked(name, || {
+            let newly_loaded = unsafe { find_lib(name) }.is_none();
+            Ok(Library::from_handle(
+                unsafe { open_lib_through(self.dlopen, self.dlerror, name, flags) }?,
+                flags,
+                newly_loaded,
+            ))
+        })
+    }
+}
@@ -0,0 +1,131 @@
+/**
+Obtains a symbol from a `raw::Library`, pairing its name with the expected type.
+
+The name gets null-terminated at compile time, so the macro expands to a plain
+`Library::symbol_cstr()` call without any conversion at runtime. Names containing a null
+character are rejected during compilation. Because the type has to be given right next to
+the name, it is harder to accidentally load a symbol with a wrong signature than with the
+turbofish form.
+
+The macro expands to an unsafe call and has to be used inside of an `unsafe` block.
+If the type is omitted, it gets inferred like with `symbol_cstr()`.
+
+# Example
+
+```no_run
+use dlopen2::raw::Library;
+use dlopen2::symbol;
+use std::os::raw::c_int;
+
+fn main() {
+    let lib = Library::open("libexample.so").unwrap();
+    let add_two = unsafe { symbol!(lib, "c_fun_add_two": unsafe extern "C" fn(c_int) -> c_int) }
+        .unwrap();
+    let counter: &i32 = unsafe { symbol!(lib, "counter") }.unwrap();
+    println!("{} {}", unsafe { add_two(2) }, counter);
+}
+```
+*/
+#[macro_export]
+macro_rules! symbol {
+    ($lib:expr, $name:literal : $ty:ty) => {
+        $lib.symbol_cstr::<$ty>($crate::symbol!(@cstr $name))
+    };
+    ($lib:expr, $name:literal) => {
+        $lib.symbol_cstr($crate::symbol!(@cstr $name))
+    };
+    (@cstr $name:literal) => {
+        const {
+            match ::std::ffi::CStr::from_bytes_with_nul(::std::concat!($name, "\0").as_bytes()) {
+                ::std::result::Result::Ok(name) => name,
+                ::std::result::Result::Err(_) => {
+                    ::std::panic!("symbol names must not contain null characters")
+                }
+            }
+        }
+    };
+}
+
+/**
+Exports the ABI tag of a library, to be used in the library crate.
+
+Without arguments the default tag `raw::ABI_TAG` is exported, which describes the compiler
+the library was built with. A custom tag (any `&'static str` constant expression, e.g. including
+the version of the plugin interface) can be given instead. The loading side verifies the tag
+with `Library::check_abi_tag()`.
+
+# Example
+
+```no_run
+// in the library crate
+dlopen2::export_abi_tag!();
+
+#[unsafe(no_mangle)]
+pub fn plugin_name() -> String {
+    "example".to_owned()
+}
+```
+
+```no_run
+// in the program loading the library
+use dlopen2::raw::{ABI_TAG, Library};
+
+fn main() {
+    let lib = Library::open("libplugin.so").unwrap();
+    lib.check_abi_tag(ABI_TAG).expect("The plugin was built by a different compiler");
+    let plugin_name: fn() -> String = unsafe { lib.symbol("plugin_name") }.unwrap();
+    println!("{}", plugin_name());
+}
+```
+*/
+#[macro_export]
+macro_rules! export_abi_tag {
+    () => {
+        $crate::export_abi_tag!($crate::raw::ABI_TAG);
+    };
+    ($tag:expr) => {
+        #[unsafe(no_mangle)]
+        #[allow(non_upper_case_globals)]
+        pub static __dlopen2_abi_tag: $crate::raw::AbiTag = $crate::raw::AbiTag::new($tag);
+    };
+}
+
+/**
+Obtains several symbols of different types from a `raw::Library` at once.
+
+The symbols are obtained in order and the first failure is returned, otherwise the result
+is a tuple of all symbols. This is meant for small ad-hoc sets of symbols that don't warrant
+a structure deriving `WrapperApi`. Like with `symbol!`, the names get null-terminated at compile
+time and the macro has to be used inside of an `unsafe` block.
+
+# Example
+
+```no_run
+use dlopen2::raw::Library;
+use dlopen2::symbols_tuple;
+use std::os::raw::c_int;
+
+fn main() {
+    let lib = Library::open("libexample.so").unwrap();
+    let (add_two, counter) = unsafe {
+        symbols_tuple!(lib, (
+            "c_fun_add_two": unsafe extern "C" fn(c_int) -> c_int,
+            "counter": &i32,
+        ))
+    }
+    .unwrap();
+    println!("{} {}", unsafe { add_two(2) }, counter);
+}
+```
+*/
+#[macro_export]
+macro_rules! symbols_tuple {
+    ($lib:expr, ($($name:literal : $ty:ty),+ $(,)?)) => {{
+        let lib = &$lib;
+        (|| -> ::std::result::Result<($($ty,)+), $crate::Error> {
+            ::std::result::Result::Ok((
+                $(lib.symbol_cstr::<$ty>($crate::symbol!(@cstr $name))?,)+
+            ))
+        })()
+    }};
+}
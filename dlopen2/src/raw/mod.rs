@@ -23,12 +23,72 @@ fn main(){
 
 //!
 
+mod abi_tag;
+mod atomic;
 mod common;
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+mod dl_info;
+mod error_hook;
+mod inspect;
+mod lib_ref;
+mod library_set;
+#[cfg(unix)]
+mod loader;
+mod macros;
+mod name_buffer;
+#[cfg(feature = "open_count")]
+mod open_count;
+mod open_dir;
+mod protection;
+mod raw_handle;
+mod scope;
+mod search_paths;
+mod send_symbol;
+#[cfg(feature = "testing")]
+mod symbol_override;
+mod symbol_sig;
+mod symbol_source;
 #[cfg(test)]
 mod tests;
+mod thunk;
 #[cfg(unix)]
 mod unix;
 #[cfg(windows)]
+mod version;
+#[cfg(windows)]
 mod windows;
 
-pub use self::common::{AddressInfo, AddressInfoObtainer, Handle, Library, OverlappingSymbol};
+pub use self::abi_tag::{ABI_TAG, AbiTag};
+pub use self::atomic::AtomicSymbol;
+pub use self::common::{
+    AddressInfo, AddressInfoObtainer, AddressInfoObtainerBuilder, BindingMode, ExportedSymbol,
+    Handle, Library, OverlappingSymbol,
+};
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub use self::dl_info::{DlInfoRequest, DlInfoValue};
+pub use self::error_hook::{clear_error_hook, set_error_hook};
+pub use self::inspect::InspectedLibrary;
+pub use self::lib_ref::LibRef;
+pub use self::library_set::LibrarySet;
+#[cfg(unix)]
+pub use self::loader::LibraryLoader;
+pub use self::name_buffer::NameBuffer;
+#[cfg(feature = "open_count")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "open_count")))]
+pub use self::open_count::open_count;
+pub use self::open_dir::open_dir;
+pub use self::protection::{MemoryProtection, symbol_protection};
+pub use self::raw_handle::RawHandle;
+pub use self::scope::{symbol_default, symbol_default_cstr, symbol_next, symbol_next_cstr};
+pub use self::search_paths::default_search_paths;
+pub use self::send_symbol::SendSymbol;
+#[cfg(feature = "testing")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "testing")))]
+pub use self::symbol_override::{
+    clear_symbol_overrides, remove_symbol_override, set_symbol_override,
+};
+pub use self::symbol_sig::{FnSig, SymbolSig};
+pub use self::symbol_source::SymbolSource;
+pub use self::thunk::resolve_thunk;
+#[cfg(windows)]
+pub use self::version::Version;
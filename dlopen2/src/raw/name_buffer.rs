@@ -0,0 +1,55 @@
+use super::super::err::Error;
+#[cfg(unix)]
+use super::unix::NameChar;
+#[cfg(windows)]
+use super::windows::NameChar;
+use std::ffi::{CStr, CString};
+
+/// Reusable scratch space for converting names into the form expected by the operating system.
+///
+/// Opening a library or obtaining a symbol requires a null-terminated copy of the name
+/// (UTF-16 for library names on Windows). The `*_in` methods of `Library` write that copy into
+/// a `NameBuffer` instead of allocating a new one on every call, so a single buffer can be
+/// reused when opening many libraries or loading many symbols.
+#[derive(Debug, Default)]
+pub struct NameBuffer {
+    pub(crate) library: Vec<NameChar>,
+    symbol: Vec<u8>,
+}
+
+impl NameBuffer {
+    /// Creates an empty buffer. No memory is allocated until it is first used.
+    pub fn new() -> NameBuffer {
+        Self::default()
+    }
+
+    /// Creates a buffer that can hold names of up to `capacity` characters without reallocating.
+    pub fn with_capacity(capacity: usize) -> NameBuffer {
+        NameBuffer {
+            library: Vec::with_capacity(capacity + 1),
+            symbol: Vec::with_capacity(capacity + 1),
+        }
+    }
+
+    /// Number of characters of the longest name the buffer can hold without reallocating.
+    ///
+    /// Library and symbol names are converted separately, so this is the smaller of both
+    /// capacities, without the space for the null terminator.
+    pub fn capacity(&self) -> usize {
+        self.library
+            .capacity()
+            .min(self.symbol.capacity())
+            .saturating_sub(1)
+    }
+
+    pub(crate) fn symbol_cstr(&mut self, name: &str) -> Result<&CStr, Error> {
+        if name.as_bytes().contains(&0) {
+            //let CString produce the proper error
+            return Err(CString::new(name).unwrap_err().into());
+        }
+        self.symbol.clear();
+        self.symbol.extend_from_slice(name.as_bytes());
+        self.symbol.push(0);
+        Ok(unsafe { CStr::from_bytes_with_nul_unchecked(&self.symbol) })
+    }
+}
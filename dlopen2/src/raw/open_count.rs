@@ -0,0 +1,64 @@
+use super::common::Handle;
+#[cfg(unix)]
+use super::unix::find_lib;
+#[cfg(windows)]
+use super::windows::find_lib;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::sync::Mutex;
+
+//the same library opened multiple times has the same handle
+static OPEN_COUNTS: Lazy<Mutex<HashMap<usize, usize>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn register(handle: Handle) {
+    //poisoning should never happen
+    let mut counts = OPEN_COUNTS.lock().expect("Mutex got poisoned");
+    *counts.entry(handle as usize).or_insert(0) += 1;
+}
+
+pub(crate) fn unregister(handle: Handle) {
+    //poisoning should never happen
+    let mut counts = OPEN_COUNTS.lock().expect("Mutex got poisoned");
+    if let Some(count) = counts.get_mut(&(handle as usize)) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(&(handle as usize));
+        }
+    }
+}
+
+/**
+Returns the number of `Library` objects this crate currently has open for the given library.
+
+The name is resolved by the platform loader the same way as in `Library::open()`, but
+without loading the library. Only libraries opened through this crate are counted,
+so the returned value is not the reference count of the operating system.
+If the library is not loaded, 0 is returned.
+
+# Example
+
+```no_run
+use dlopen2::raw::{Library, open_count};
+
+fn main() {
+    let lib = Library::open("libm.so.6").unwrap();
+    let another = Library::open("libm.so.6").unwrap();
+    assert_eq!(open_count("libm.so.6"), 2);
+    drop(another);
+    assert_eq!(open_count("libm.so.6"), 1);
+}
+```
+*/
+pub fn open_count<S>(name: S) -> usize
+where
+    S: AsRef<OsStr>,
+{
+    let handle = match unsafe { find_lib(name.as_ref()) } {
+        Some(handle) => handle,
+        None => return 0,
+    };
+    //poisoning should never happen
+    let counts = OPEN_COUNTS.lock().expect("Mutex got poisoned");
+    counts.get(&(handle as usize)).copied().unwrap_or(0)
+}
@@ -0,0 +1,71 @@
+use super::super::err::Error;
+use super::super::utils::{PLATFORM_FILE_EXTENSION, PLATFORM_FILE_PREFIX};
+use super::common::Library;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+type OpenedLibrary = (PathBuf, Result<Library, Error>);
+
+/**
+Opens all dynamic link libraries in the given directory.
+
+Files are considered libraries if their names follow the platform naming convention
+(see `utils::platform_file_name()`) and their core name matches `pattern`.
+The pattern supports the `*` (any sequence of characters) and `?` (any single character)
+wildcards, so `"*"` matches all libraries. Subdirectories are not searched.
+
+Each library is opened with the given flags and the results are returned together with the path
+of the file, sorted by the path. A library that can't be opened doesn't stop the others from being
+opened, only failing to read the directory is reported as an error.
+
+# Example
+
+```no_run
+use dlopen2::raw::open_dir;
+
+fn main() {
+    for (path, result) in open_dir("plugins", "plugin_*", None).unwrap() {
+        match result {
+            Ok(_lib) => println!("Loaded {}", path.display()),
+            Err(err) => println!("Could not load {}: {err}", path.display()),
+        }
+    }
+}
+```
+*/
+pub fn open_dir<P>(dir: P, pattern: &str, flags: Option<i32>) -> Result<Vec<OpenedLibrary>, Error>
+where
+    P: AsRef<Path>,
+{
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(Error::OpeningLibraryError)? {
+        let path = entry.map_err(Error::OpeningLibraryError)?.path();
+        let matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(PLATFORM_FILE_PREFIX))
+            .and_then(|name| name.strip_suffix(PLATFORM_FILE_EXTENSION))
+            .and_then(|name| name.strip_suffix('.'))
+            .is_some_and(|core_name| glob_match(pattern.as_bytes(), core_name.as_bytes()));
+        if matches && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let result = Library::open_with_flags(&path, flags);
+            (path, result)
+        })
+        .collect())
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|skip| glob_match(rest, &text[skip..])),
+        Some((b'?', rest)) => !text.is_empty() && glob_match(rest, &text[1..]),
+        Some((&c, rest)) => text.first() == Some(&c) && glob_match(rest, &text[1..]),
+    }
+}
@@ -0,0 +1,41 @@
+use super::super::err::Error;
+#[cfg(unix)]
+use super::unix::addr_protection;
+#[cfg(windows)]
+use super::windows::addr_protection;
+
+/// Access rights of the memory page containing an address, see `symbol_protection()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryProtection {
+    /// The memory can be read.
+    pub readable: bool,
+    /// The memory can be written.
+    pub writable: bool,
+    /// The memory can be executed.
+    pub executable: bool,
+}
+
+/**
+Queries the protection of the memory page that contains the given address.
+
+This allows checking whether a symbol lives in an executable, writable or read-only region,
+e.g. before patching instructions of a loaded function.
+On Linux the mappings of the process are read from `/proc/self/maps`, on Windows
+`VirtualQuery()` is used. Other platforms return `Error::MemoryQueryError`.
+
+# Example
+
+```no_run
+use dlopen2::raw::{Library, symbol_protection};
+
+fn main() {
+    let lib = Library::open("libexample.so").unwrap();
+    let fun: unsafe extern "C" fn() = unsafe { lib.symbol("example_fun") }.unwrap();
+    let protection = symbol_protection(fun as *const ()).unwrap();
+    assert!(protection.executable);
+}
+```
+*/
+pub fn symbol_protection(addr: *const ()) -> Result<MemoryProtection, Error> {
+    addr_protection(addr)
+}
@@ -0,0 +1,39 @@
+use super::common::Handle;
+
+/**
+Platform independent wrapper around the raw OS handle of a library.
+
+On Windows the wrapped value is the `HMODULE` returned by `LoadLibraryW()`, which is the base
+address the library is mapped at. On Unix systems it is the opaque pointer returned by `dlopen()`.
+In both cases it is just an identifier of the library: it does not own a reference to it,
+so it can be freely copied and moved between threads, but the library it identifies may
+get unloaded in the meantime.
+
+Raw handles can be obtained using `Library::into_raw()` and turned back to a library
+with `Library::from_raw()`.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawHandle(Handle);
+
+impl RawHandle {
+    /// Returns the wrapped platform handle.
+    pub fn get(self) -> Handle {
+        self.0
+    }
+}
+
+impl From<Handle> for RawHandle {
+    fn from(handle: Handle) -> RawHandle {
+        RawHandle(handle)
+    }
+}
+
+impl From<RawHandle> for Handle {
+    fn from(handle: RawHandle) -> Handle {
+        handle.0
+    }
+}
+
+//the handle is only an identifier of the library, all operations on it are thread safe
+unsafe impl Send for RawHandle {}
+unsafe impl Sync for RawHandle {}
@@ -0,0 +1,54 @@
+use super::super::err::Error;
+use super::common::cast_symbol;
+#[cfg(unix)]
+use super::unix::get_scoped_sym;
+#[cfg(windows)]
+use super::windows::get_scoped_sym;
+use std::ffi::{CStr, CString};
+
+/**
+Obtains a symbol from the global scope of the program (`dlsym(RTLD_DEFAULT, ...)`).
+
+The symbol is searched for in the program and all libraries loaded with `RTLD_GLOBAL`,
+in the order they were loaded - the same way the dynamic linker resolves references.
+On Windows `Error::SymbolGettingError` is returned because there is no such scope.
+*/
+pub unsafe fn symbol_default_cstr<T>(name: &CStr) -> Result<T, Error> {
+    unsafe { cast_symbol(get_scoped_sym(false, name)?) }
+}
+
+/// Equivalent of the `symbol_default_cstr` function but takes `&str` as a argument.
+pub unsafe fn symbol_default<T>(name: &str) -> Result<T, Error> {
+    let cname = CString::new(name)?;
+    unsafe { symbol_default_cstr(cname.as_ref()) }
+}
+
+/**
+Obtains the next definition of a symbol in the load order (`dlsym(RTLD_NEXT, ...)`).
+
+The search starts after the object that contains `dlopen2`, which is usually the binary or
+library calling this function. This allows interposers to find the definition they replace,
+e.g. a `malloc` wrapper can obtain the `malloc` of the C library.
+On Windows `Error::SymbolGettingError` is returned because there is no such scope.
+
+# Example
+
+```no_run
+use dlopen2::raw::symbol_next_cstr;
+use std::os::raw::c_void;
+
+fn main() {
+    let malloc: unsafe extern "C" fn(usize) -> *mut c_void =
+        unsafe { symbol_next_cstr(c"malloc") }.unwrap();
+}
+```
+*/
+pub unsafe fn symbol_next_cstr<T>(name: &CStr) -> Result<T, Error> {
+    unsafe { cast_symbol(get_scoped_sym(true, name)?) }
+}
+
+/// Equivalent of the `symbol_next_cstr` function but takes `&str` as a argument.
+pub unsafe fn symbol_next<T>(name: &str) -> Result<T, Error> {
+    let cname = CString::new(name)?;
+    unsafe { symbol_next_cstr(cname.as_ref()) }
+}
@@ -0,0 +1,35 @@
+use super::super::err::Error;
+#[cfg(unix)]
+use super::unix::search_paths;
+#[cfg(windows)]
+use super::windows::search_paths;
+use std::path::PathBuf;
+
+/**
+Returns the directories searched for libraries that are opened by name, in the order they are searched.
+
+This is intended for diagnosing why a library could not be found.
+On Linux with glibc the list is obtained from the dynamic linker (`dlinfo()` with
+`RTLD_DI_SERINFO`) for the program itself, so it contains the directories of `LD_LIBRARY_PATH`,
+the `RUNPATH` of the program and the system directories. Libraries listed only in the
+`ld.so.cache` are found without searching, so the cache is not part of the list.
+On Windows the list follows the documented search order of `LoadLibrary()`: the directory of the
+program, the directory set by `SetDllDirectory()`, the system directories, the current directory
+(unless `SetDllDirectory()` was used) and the directories of `PATH`.
+Other platforms report `Error::LibraryInfoError` with `ErrorKind::Unsupported`.
+
+# Example
+
+```no_run
+use dlopen2::raw::default_search_paths;
+
+fn main() {
+    for dir in default_search_paths().unwrap() {
+        println!("{}", dir.display());
+    }
+}
+```
+*/
+pub fn default_search_paths() -> Result<Vec<PathBuf>, Error> {
+    search_paths()
+}
@@ -0,0 +1,49 @@
+use std::ops::Deref;
+
+/// Wrapper around a symbol that allows moving it to other threads.
+///
+/// Raw pointers obtained through `Library::symbol()` are neither `Send` nor `Sync`, even though
+/// the `Library` itself is. `SendSymbol` asserts that the wrapped symbol is usable from any thread,
+/// which allows offloading calls of library functions to worker threads.
+///
+/// The wrapped type must be a plain pointer or function, which is checked at compile time.
+/// Unlike `LibRef` a `SendSymbol` does not borrow the library: the library must outlive
+/// all `SendSymbol`s obtained from it.
+#[derive(Debug, Clone, Copy)]
+pub struct SendSymbol<T: Copy> {
+    symbol: T,
+}
+
+impl<T: Copy> SendSymbol<T> {
+    /// Wraps the symbol.
+    ///
+    /// # Safety
+    ///
+    /// The symbol must be valid to use from any thread (e.g. not point to thread-local data)
+    /// and the library it was obtained from must stay loaded while the wrapper or any copy
+    /// of the symbol is in use.
+    pub unsafe fn new(symbol: T) -> SendSymbol<T> {
+        const {
+            assert!(
+                size_of::<T>() == size_of::<*const ()>(),
+                "SendSymbol can only wrap pointers and functions"
+            )
+        };
+        SendSymbol { symbol }
+    }
+
+    /// Returns the wrapped symbol.
+    pub fn get(&self) -> T {
+        self.symbol
+    }
+}
+
+impl<T: Copy> Deref for SendSymbol<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.symbol
+    }
+}
+
+unsafe impl<T: Copy> Send for SendSymbol<T> {}
+unsafe impl<T: Copy> Sync for SendSymbol<T> {}
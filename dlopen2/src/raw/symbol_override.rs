@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+//addresses are stored as integers because raw pointers can't be shared between threads
+static OVERRIDES: RwLock<Option<HashMap<String, Option<usize>>>> = RwLock::new(None);
+//checked first, so that lookups cost nothing more than an atomic load while no override is set
+static OVERRIDES_SET: AtomicBool = AtomicBool::new(false);
+
+/**
+Makes symbols with the given name resolve to `addr` instead of their real address.
+
+This is intended for tests only: it allows exercising the handling of missing symbols and of
+unexpected addresses deterministically, without building special libraries. While an override
+is set, obtaining the symbol from any library (e.g. with `Library::symbol_cstr()` or through the
+`WrapperApi` and `SymBorApi` derives) returns `addr` without calling the system loader,
+and `None` makes it fail with `Error::NullSymbol`. Overrides are global for the whole process,
+so tests using them should not run in parallel with tests using the same symbol names.
+
+# Example
+
+```no_run
+use dlopen2::raw::{Library, clear_symbol_overrides, set_symbol_override};
+
+fn main() {
+    set_symbol_override("plugin_init", None);
+    let lib = Library::open("libplugin.so").unwrap();
+    let init: Result<unsafe extern "C" fn(), _> = unsafe { lib.symbol("plugin_init") };
+    assert!(init.is_err());
+    clear_symbol_overrides();
+}
+```
+*/
+pub fn set_symbol_override(name: &str, addr: Option<*const ()>) {
+    let mut guard = OVERRIDES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard
+        .get_or_insert_with(HashMap::new)
+        .insert(name.to_owned(), addr.map(|addr| addr as usize));
+    OVERRIDES_SET.store(true, Ordering::Release);
+}
+
+/// Removes the override set for the given name by `set_symbol_override()`.
+pub fn remove_symbol_override(name: &str) {
+    let mut guard = OVERRIDES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(overrides) = guard.as_mut() {
+        overrides.remove(name);
+        OVERRIDES_SET.store(!overrides.is_empty(), Ordering::Release);
+    }
+}
+
+/// Removes all overrides set by `set_symbol_override()`.
+pub fn clear_symbol_overrides() {
+    let mut guard = OVERRIDES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    OVERRIDES_SET.store(false, Ordering::Release);
+    *guard = None;
+}
+
+//Returns the overridden address of the symbol (null meaning `None`), if there is an override.
+pub(crate) fn symbol_override(name: &CStr) -> Option<*mut ()> {
+    if !OVERRIDES_SET.load(Ordering::Acquire) {
+        return None;
+    }
+    let guard = OVERRIDES
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let addr = guard.as_ref()?.get(name.to_str().ok()?)?;
+    Some(addr.unwrap_or(0) as *mut ())
+}
@@ -0,0 +1,88 @@
+mod private {
+    pub trait Sealed {}
+}
+
+/**
+Function pointer types that can be obtained with `Library::symbol_typed_cstr()`.
+
+Implemented for `fn`, `extern "C" fn` and `extern "system" fn` pointers (both safe and `unsafe`)
+taking up to 12 arguments. The calling convention and the number of arguments are available
+as associated constants, e.g. for diagnostics.
+Function pointers with higher-ranked lifetimes (e.g. `fn(&u8)`) are not covered.
+*/
+pub trait FnSig: private::Sealed + Copy {
+    /// Calling convention of the function, as written in `extern "..."`.
+    const ABI: &'static str;
+    /// Number of arguments the function takes.
+    const ARITY: usize;
+}
+
+/**
+Marker for the signature of a symbol, used by `Library::symbol_typed_cstr()`.
+
+Instead of spelling out the function pointer type at every call site (and risking a different
+type being inferred), the signature gets named once:
+
+```no_run
+use dlopen2::raw::{Library, SymbolSig};
+use std::os::raw::c_int;
+
+struct Add;
+
+impl SymbolSig for Add {
+    type Sig = unsafe extern "C" fn(c_int, c_int) -> c_int;
+}
+
+fn main() {
+    let lib = Library::open("libyourlib.so").unwrap();
+    let add = unsafe { lib.symbol_typed::<Add>("add") }.unwrap();
+    println!("{}", unsafe { add(1, 2) });
+}
+```
+
+Every function pointer type implementing `FnSig` is also its own marker.
+*/
+pub trait SymbolSig {
+    /// Type of the function pointer the symbol is obtained as.
+    type Sig: FnSig;
+}
+
+impl<F: FnSig> SymbolSig for F {
+    type Sig = F;
+}
+
+macro_rules! impl_fn_sig {
+    ($abi:literal; $($arg:ident),*) => {
+        impl<R, $($arg),*> private::Sealed for extern $abi fn($($arg),*) -> R {}
+        impl<R, $($arg),*> FnSig for extern $abi fn($($arg),*) -> R {
+            const ABI: &'static str = $abi;
+            const ARITY: usize = impl_fn_sig!(@count $($arg)*);
+        }
+        impl<R, $($arg),*> private::Sealed for unsafe extern $abi fn($($arg),*) -> R {}
+        impl<R, $($arg),*> FnSig for unsafe extern $abi fn($($arg),*) -> R {
+            const ABI: &'static str = $abi;
+            const ARITY: usize = impl_fn_sig!(@count $($arg)*);
+        }
+    };
+    (@count) => { 0 };
+    (@count $head:ident $($tail:ident)*) => { 1 + impl_fn_sig!(@count $($tail)*) };
+    (@all $($arg:ident),*) => {
+        impl_fn_sig!("Rust"; $($arg),*);
+        impl_fn_sig!("C"; $($arg),*);
+        impl_fn_sig!("system"; $($arg),*);
+    };
+}
+
+impl_fn_sig!(@all);
+impl_fn_sig!(@all A);
+impl_fn_sig!(@all A, B);
+impl_fn_sig!(@all A, B, C);
+impl_fn_sig!(@all A, B, C, D);
+impl_fn_sig!(@all A, B, C, D, E);
+impl_fn_sig!(@all A, B, C, D, E, F);
+impl_fn_sig!(@all A, B, C, D, E, F, G);
+impl_fn_sig!(@all A, B, C, D, E, F, G, H);
+impl_fn_sig!(@all A, B, C, D, E, F, G, H, I);
+impl_fn_sig!(@all A, B, C, D, E, F, G, H, I, J);
+impl_fn_sig!(@all A, B, C, D, E, F, G, H, I, J, K);
+impl_fn_sig!(@all A, B, C, D, E, F, G, H, I, J, K, L);
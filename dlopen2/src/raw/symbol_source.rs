@@ -0,0 +1,81 @@
+use super::super::err::Error;
+use super::common::cast_symbol;
+use std::ffi::{CStr, CString};
+
+/**
+Source of symbols that `WrapperApi::load()` obtains the addresses of the symbols from.
+
+Implemented by `Library`, which is what containers load from. Implementing it for a different
+type allows loading structures deriving `WrapperApi` without a dynamic link library,
+e.g. from a table of mock functions in unit tests:
+
+```
+use dlopen2::Error;
+use dlopen2::raw::SymbolSource;
+use dlopen2::wrapper::WrapperApi;
+use std::ffi::CStr;
+use std::io::ErrorKind;
+
+#[derive(WrapperApi)]
+struct Api {
+    add_two: extern "C" fn(arg: i32) -> i32,
+}
+
+extern "C" fn mock_add_two(arg: i32) -> i32 {
+    arg + 2
+}
+
+struct MockSource;
+
+impl SymbolSource for MockSource {
+    fn get_symbol(&self, name: &CStr) -> Result<*const (), Error> {
+        match name.to_bytes() {
+            b"add_two" => Ok(mock_add_two as *const ()),
+            _ => Err(Error::SymbolGettingError(ErrorKind::NotFound.into())),
+        }
+    }
+}
+
+fn main() {
+    let api = unsafe { Api::load(&MockSource) }.unwrap();
+    assert_eq!(api.add_two(1), 3);
+}
+```
+*/
+pub trait SymbolSource {
+    /// Obtains the address of the symbol.
+    ///
+    /// Missing symbols need to be reported as `Error::SymbolGettingError` and null addresses
+    /// as `Error::NullSymbol`, so that optional fields and fallbacks work as they do
+    /// for libraries.
+    fn get_symbol(&self, name: &CStr) -> Result<*const (), Error>;
+
+    /**
+    Obtains the symbol as `T`, which is what the derive of `WrapperApi` uses.
+
+    The default implementation converts the address returned by `get_symbol()` like
+    `Library::symbol_cstr()` does, which panics if `T` does not have the size of a pointer.
+
+    # Safety
+
+    `T` needs to match the type of the symbol.
+    */
+    unsafe fn symbol_cstr<T>(&self, name: &CStr) -> Result<T, Error> {
+        unsafe { cast_symbol(self.get_symbol(name)?.cast_mut()) }
+    }
+
+    /// Checks if the symbol can be obtained and is not null.
+    fn has_symbol(&self, name: &str) -> bool {
+        match CString::new(name) {
+            Ok(cname) => self.get_symbol(cname.as_ref()).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Checks if the source itself exports the symbol, which is what "dlopen2_forbid" checks.
+    ///
+    /// The default implementation is `has_symbol()`.
+    fn exports_symbol(&self, name: &str) -> bool {
+        self.has_symbol(name)
+    }
+}
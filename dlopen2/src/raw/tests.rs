@@ -1,4 +1,6 @@
 use super::super::err::Error;
+#[cfg(debug_assertions)]
+use super::Library;
 #[cfg(unix)]
 use super::unix::{close_lib, get_sym, open_lib};
 #[cfg(windows)]
@@ -57,3 +59,28 @@ fn get_err() {
         assert!(close_lib(handle).is_null());
     }
 }
+
+#[cfg(debug_assertions)]
+#[test]
+fn dangling_symbols() {
+    let mut lib = Library::open(EXISTING_LIB).expect("Could not open library");
+    let _sym: *const () = unsafe { lib.symbol_cstr(EXISTING_SYM) }.expect("Could not get symbol");
+    assert!(lib.dangling_symbols().is_none());
+    lib.set_track_symbols(true);
+    let _sym: *const () = unsafe { lib.symbol_cstr(EXISTING_SYM) }.expect("Could not get symbol");
+    assert!(matches!(
+        lib.dangling_symbols(),
+        Some(Error::DanglingSymbols(1))
+    ));
+}
+
+#[cfg(all(debug_assertions, any(feature = "wrapper", feature = "symbor")))]
+#[test]
+fn no_dangling_symbols_when_managed() {
+    let mut managed = Library::open(EXISTING_LIB).expect("Could not open library");
+    managed.set_track_symbols(true);
+    managed.set_managed();
+    let _sym: *const () =
+        unsafe { managed.symbol_cstr(EXISTING_SYM) }.expect("Could not get symbol");
+    assert!(managed.dangling_symbols().is_none());
+}
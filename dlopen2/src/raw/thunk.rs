@@ -0,0 +1,103 @@
+use super::super::err::Error;
+#[cfg(not(target_arch = "x86_64"))]
+use std::io::{Error as IoError, ErrorKind};
+
+//chains of thunks longer than this are unlikely to be thunks at all
+#[cfg(target_arch = "x86_64")]
+const MAX_JUMPS: usize = 8;
+
+/**
+Follows jump thunks starting at the given address to the function they jump to.
+
+Exported functions and function pointers often point to a thunk instead of the function itself:
+a PLT entry on Unix systems, an import or incremental linking thunk on Windows. This resolves
+such an address to the address of the real implementation, which is useful e.g. when
+disassembling or patching a loaded function. Addresses that don't point to a thunk are
+returned unchanged.
+
+This is best-effort only. Just the x86-64 architecture is supported, other architectures
+return `Error::MemoryQueryError`. Only thunks consisting of a single jump are recognized:
+`jmp rel8`, `jmp rel32` and `jmp [rip + disp32]`, optionally preceded by `endbr64`
+and the `bnd` prefix. Note that:
+
+* A function starting with a jump (e.g. a tail call) is followed as if it was a thunk.
+* With lazy binding an unresolved PLT entry jumps back to the PLT resolver stub. Call
+  the function once or open the library with `RTLD_NOW` before resolving.
+
+# Safety
+
+The address must point to readable memory containing code, usually a function obtained from
+a library that is still loaded.
+
+# Example
+
+```no_run
+use dlopen2::raw::{Library, resolve_thunk};
+
+fn main() {
+    let lib = Library::open("libexample.so").unwrap();
+    let fun: unsafe extern "C" fn() = unsafe { lib.symbol("example_fun") }.unwrap();
+    let implementation = unsafe { resolve_thunk(fun as *const ()) }.unwrap();
+    println!("example_fun is implemented at {implementation:?}");
+}
+```
+*/
+pub unsafe fn resolve_thunk(addr: *const ()) -> Result<*const (), Error> {
+    if addr.is_null() {
+        return Err(Error::NullSymbol);
+    }
+    #[cfg(target_arch = "x86_64")]
+    {
+        let mut addr = addr;
+        for _ in 0..MAX_JUMPS {
+            match unsafe { jump_target(addr as *const u8) } {
+                Some(target) => addr = target,
+                None => break,
+            }
+        }
+        Ok(addr)
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        Err(Error::MemoryQueryError(IoError::new(
+            ErrorKind::Unsupported,
+            "Resolving thunks is supported on x86-64 only",
+        )))
+    }
+}
+
+// target of the jump at the given address or None if there is no jump
+#[cfg(target_arch = "x86_64")]
+unsafe fn jump_target(code: *const u8) -> Option<*const ()> {
+    const ENDBR64: [u8; 4] = [0xF3, 0x0F, 0x1E, 0xFA];
+    const BND: u8 = 0xF2;
+    unsafe {
+        let mut code = code;
+        if (code as *const [u8; 4]).read_unaligned() == ENDBR64 {
+            code = code.add(4);
+        }
+        if *code == BND {
+            code = code.add(1);
+        }
+        match (*code, *code.add(1)) {
+            //jmp rel8
+            (0xEB, _) => {
+                let rel = *code.add(1) as i8;
+                Some(code.add(2).offset(rel as isize) as *const ())
+            }
+            //jmp rel32
+            (0xE9, _) => {
+                let rel = (code.add(1) as *const i32).read_unaligned();
+                Some(code.add(5).offset(rel as isize) as *const ())
+            }
+            //jmp [rip + disp32]
+            (0xFF, 0x25) => {
+                let disp = (code.add(2) as *const i32).read_unaligned();
+                let slot = code.add(6).offset(disp as isize) as *const *const ();
+                let target = slot.read_unaligned();
+                if target.is_null() { None } else { Some(target) }
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,15 +1,44 @@
 #![allow(clippy::let_unit_value)]
 
 use super::super::err::Error;
-use super::common::{AddressInfo, OverlappingSymbol};
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use super::common::ExportedSymbol;
+#[cfg(feature = "demangle")]
+use super::common::demangle_name;
+use super::common::{AddressInfo, BindingMode, OverlappingSymbol};
+use super::protection::MemoryProtection;
 use libc::{Dl_info, RTLD_LAZY, RTLD_LOCAL, dladdr, dlclose, dlerror, dlopen, dlsym};
-use std::ffi::{CStr, OsStr};
+use std::ffi::{CStr, CString, NulError, OsStr};
 use std::io::{Error as IoError, ErrorKind};
-use std::os::raw::{c_int, c_void};
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+use std::os::fd::AsRawFd;
+use std::os::fd::BorrowedFd;
+use std::os::raw::{c_char, c_int, c_void};
 use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
 use std::ptr::{null, null_mut};
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use {
+    super::dl_info::{DlInfoRequest, DlInfoValue},
+    libc::{
+        PATH_MAX, RTLD_DI_LINKMAP, RTLD_DI_ORIGIN, RTLD_DI_SERINFO, RTLD_DI_SERINFOSIZE,
+        RTLD_GLOBAL, RTLD_NOLOAD, c_uint, dladdr1, dlinfo,
+    },
+    std::ffi::OsString,
+    std::os::unix::ffi::OsStringExt,
+};
 
 const DEFAULT_FLAGS: c_int = RTLD_LOCAL | RTLD_LAZY;
+//resolves all relocations when the library gets loaded
+pub const NOW_FLAGS: Option<i32> = Some(RTLD_LOCAL | libc::RTLD_NOW);
+
+pub fn flags_binding_mode(flags: Option<i32>) -> BindingMode {
+    if flags.unwrap_or(DEFAULT_FLAGS) & libc::RTLD_NOW != 0 {
+        BindingMode::Now
+    } else {
+        BindingMode::Lazy
+    }
+}
 
 // calls to dlerror are not thread-safe on some platforms,
 // so we guard them with a mutex if required
@@ -29,14 +58,147 @@ fn lock_dlerror_mutex() -> LockResult<MutexGuard<'static, ()>> {
 fn lock_dlerror_mutex() {}
 
 pub type Handle = *mut c_void;
+pub type NameChar = u8;
 
 #[inline]
 pub unsafe fn get_sym(handle: Handle, name: &CStr) -> Result<*mut (), Error> {
     unsafe {
+        let symbol = get_any_sym(handle, name)?;
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        if !symbol.is_null() && is_thread_local_address(symbol) {
+            return Err(Error::ThreadLocalSymbol);
+        }
+        Ok(symbol)
+    }
+}
+
+//Looks the symbol up in the global scope (RTLD_DEFAULT) or in the objects loaded
+//after the object containing this code (RTLD_NEXT).
+#[inline]
+pub unsafe fn get_scoped_sym(next: bool, name: &CStr) -> Result<*mut (), Error> {
+    unsafe {
+        let handle = if next {
+            libc::RTLD_NEXT
+        } else {
+            libc::RTLD_DEFAULT
+        };
+        let symbol = get_any_sym(handle, name)?;
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        if !symbol.is_null() && is_thread_local_address(symbol) {
+            return Err(Error::ThreadLocalSymbol);
+        }
+        Ok(symbol)
+    }
+}
+
+//Looks up a thread-local symbol. For these dlsym() resolves the instance of the calling
+//thread through __tls_get_addr(), allocating it if needed.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub unsafe fn get_tls_sym(handle: Handle, name: &CStr) -> Result<*mut (), Error> {
+    unsafe {
+        let symbol = get_any_sym(handle, name)?;
+        if symbol.is_null() || !is_thread_local_address(symbol) {
+            return Err(Error::SymbolGettingError(IoError::new(
+                ErrorKind::InvalidInput,
+                "The symbol is not a thread-local symbol",
+            )));
+        }
+        Ok(symbol)
+    }
+}
+
+//Addresses of regular symbols point into the mapping of a loaded object, which allows skipping
+//the search of the thread-local storage blocks for them.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+unsafe fn is_thread_local_address(addr: *mut ()) -> bool {
+    unsafe {
+        let mut info = MaybeUninit::<Dl_info>::uninit();
+        if dladdr(addr as *const c_void, info.as_mut_ptr()) != 0 {
+            return false;
+        }
+        //dlsym() returns addresses inside the block of the calling thread of the object
+        //defining the symbol, which may be any dependency of the handle
+        unsafe extern "C" fn callback(
+            info: *mut libc::dl_phdr_info,
+            size: libc::size_t,
+            data: *mut c_void,
+        ) -> c_int {
+            unsafe {
+                if size < size_of::<libc::dl_phdr_info>() {
+                    return 0;
+                }
+                let info = &*info;
+                let block = info.dlpi_tls_data as usize;
+                if info.dlpi_tls_modid == 0 || block == 0 {
+                    return 0;
+                }
+                let addr = *(data as *const usize);
+                (0..info.dlpi_phnum as usize)
+                    .map(|i| &*info.dlpi_phdr.add(i))
+                    .any(|phdr| {
+                        phdr.p_type == libc::PT_TLS
+                            && addr >= block
+                            && addr < block + phdr.p_memsz as usize
+                    }) as c_int
+            }
+        }
+        let mut addr = addr as usize;
+        libc::dl_iterate_phdr(Some(callback), &mut addr as *mut usize as *mut c_void) != 0
+    }
+}
+
+//the complete message of the dynamic linker, it often names the exact missing dependency
+//or symbol version
+unsafe fn dlerror_message() -> String {
+    unsafe {
+        let msg = dlerror();
+        if msg.is_null() {
+            String::from("Unknown error of the dynamic linker")
+        } else {
+            CStr::from_ptr(msg).to_string_lossy().into_owned()
+        }
+    }
+}
+
+//dlsym() has no case-insensitive mode and ELF symbol names are case-sensitive
+#[inline]
+pub unsafe fn get_sym_case_insensitive(handle: Handle, name: &CStr) -> Result<*mut (), Error> {
+    unsafe { get_sym(handle, name) }
+}
+
+//Splits names like `memcpy@GLIBC_2.14` into the name and the requested version, `@@` (marking
+//the default version in `nm` output) is accepted too. ELF symbol names never contain `@`.
+fn split_version(name: &CStr) -> Result<Option<(CString, CString)>, Error> {
+    let bytes = name.to_bytes();
+    let Some(at) = bytes.iter().position(|&c| c == b'@') else {
+        return Ok(None);
+    };
+    if cfg!(not(all(target_os = "linux", target_env = "gnu"))) {
+        return Err(Error::SymbolGettingError(IoError::new(
+            ErrorKind::Unsupported,
+            "Version-qualified symbol names are only supported on Linux with glibc",
+        )));
+    }
+    let version = bytes[at + 1..]
+        .strip_prefix(b"@")
+        .unwrap_or(&bytes[at + 1..]);
+    Ok(Some((CString::new(&bytes[..at])?, CString::new(version)?)))
+}
+
+#[inline]
+unsafe fn get_any_sym(handle: Handle, name: &CStr) -> Result<*mut (), Error> {
+    unsafe {
+        let versioned = split_version(name)?;
         let _lock = lock_dlerror_mutex();
         //clear the dlerror in order to be able to distinguish between NULL pointer and error
         let _ = dlerror();
-        let symbol = dlsym(handle, name.as_ptr());
+        let symbol = match &versioned {
+            #[cfg(all(target_os = "linux", target_env = "gnu"))]
+            Some((name, version)) => libc::dlvsym(handle, name.as_ptr(), version.as_ptr()),
+            #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+            Some(_) => unreachable!(),
+            None => dlsym(handle, name.as_ptr()),
+        };
         //This can be either error or just the library has a NULL pointer - legal
         if symbol.is_null() {
             let msg = dlerror();
@@ -56,9 +218,9 @@ pub unsafe fn open_self() -> Result<Handle, Error> {
         let _lock = lock_dlerror_mutex();
         let handle = dlopen(null(), DEFAULT_FLAGS);
         if handle.is_null() {
-            Err(Error::OpeningLibraryError(IoError::other(
-                CStr::from_ptr(dlerror()).to_string_lossy().to_string(),
-            )))
+            Err(Error::OpeningLibraryError(
+                IoError::other(dlerror_message()),
+            ))
         } else {
             Ok(handle)
         }
@@ -67,38 +229,928 @@ pub unsafe fn open_self() -> Result<Handle, Error> {
 
 #[inline]
 pub unsafe fn open_lib(name: &OsStr, flags: Option<i32>) -> Result<Handle, Error> {
+    unsafe { open_lib_in(name, &mut Vec::new(), flags) }
+}
+
+#[inline]
+pub unsafe fn open_lib_in(
+    name: &OsStr,
+    buffer: &mut Vec<NameChar>,
+    flags: Option<i32>,
+) -> Result<Handle, Error> {
     unsafe {
-        let mut v: Vec<u8> = Vec::new();
-        //as_bytes i a unix-specific extension
-        let cstr = if !name.is_empty() && name.as_bytes()[name.len() - 1] == 0 {
-            //don't need to convert
-            CStr::from_bytes_with_nul_unchecked(name.as_bytes())
+        buffer.clear();
+        let cstr = to_cstr(name, buffer)?;
+        let _lock = lock_dlerror_mutex();
+        let handle = dlopen(cstr.as_ptr(), flags.unwrap_or(DEFAULT_FLAGS));
+        if handle.is_null() {
+            Err(open_error(dlerror_message(), Some(name)))
         } else {
-            //need to convert
-            v.extend_from_slice(name.as_bytes());
-            v.push(0);
-            CStr::from_bytes_with_nul_unchecked(v.as_slice())
+            Ok(handle)
+        }
+    }
+}
+
+pub type DlopenFn = unsafe extern "C" fn(*const c_char, c_int) -> *mut c_void;
+pub type DlerrorFn = unsafe extern "C" fn() -> *mut c_char;
+
+//same as open_lib(), but through dlopen() and dlerror() obtained from a specific library
+pub unsafe fn open_lib_through(
+    dlopen_fn: DlopenFn,
+    dlerror_fn: DlerrorFn,
+    name: &OsStr,
+    flags: Option<i32>,
+) -> Result<Handle, Error> {
+    unsafe {
+        let mut buffer = Vec::new();
+        let cstr = to_cstr(name, &mut buffer)?;
+        let _lock = lock_dlerror_mutex();
+        let handle = dlopen_fn(cstr.as_ptr(), flags.unwrap_or(DEFAULT_FLAGS));
+        if handle.is_null() {
+            let msg = dlerror_fn();
+            let msg = if msg.is_null() {
+                String::from("Unknown error of the dynamic linker")
+            } else {
+                CStr::from_ptr(msg).to_string_lossy().into_owned()
+            };
+            Err(open_error(msg, Some(name)))
+        } else {
+            Ok(handle)
+        }
+    }
+}
+
+//dlopen() has no per-call search path, so only the library itself gets looked up in the directory
+#[inline]
+pub unsafe fn open_lib_with_dir(
+    name: &OsStr,
+    dir: &Path,
+    flags: Option<i32>,
+) -> Result<Handle, Error> {
+    let in_dir = dir.join(name);
+    unsafe {
+        if Path::new(name).is_relative() && in_dir.is_file() {
+            open_lib(in_dir.as_os_str(), flags)
+        } else {
+            open_lib(name, flags)
+        }
+    }
+}
+
+//messages of the dynamic linkers (glibc, musl, macOS) reporting a library built for a different
+//architecture, compared in lowercase
+const ARCHITECTURE_MISMATCH_MESSAGES: [&str; 4] = [
+    "wrong elf class",
+    "exec format error",
+    "incompatible architecture",
+    "wrong architecture",
+];
+
+//glibc 2.30 and newer refuses to open executables, including position-independent ones
+const EXECUTABLE_MESSAGES: [&str; 2] = [
+    "cannot dynamically load executable",
+    "cannot dynamically load position-independent executable",
+];
+
+fn open_error(msg: String, name: Option<&OsStr>) -> Error {
+    let lowercase = msg.to_lowercase();
+    if EXECUTABLE_MESSAGES
+        .iter()
+        .any(|pattern| lowercase.contains(pattern))
+    {
+        Error::ExecutableNotLoadable(IoError::other(msg))
+    } else if ARCHITECTURE_MISMATCH_MESSAGES
+        .iter()
+        .any(|pattern| lowercase.contains(pattern))
+        || name.is_some_and(is_foreign_elf)
+    {
+        Error::ArchitectureMismatch(IoError::other(msg))
+    } else {
+        Error::OpeningLibraryError(IoError::other(msg))
+    }
+}
+
+//e_machine value of the current architecture
+#[cfg(target_arch = "x86")]
+const ELF_MACHINE: Option<u16> = Some(3);
+#[cfg(target_arch = "x86_64")]
+const ELF_MACHINE: Option<u16> = Some(62);
+#[cfg(target_arch = "arm")]
+const ELF_MACHINE: Option<u16> = Some(40);
+#[cfg(target_arch = "aarch64")]
+const ELF_MACHINE: Option<u16> = Some(183);
+#[cfg(target_arch = "riscv64")]
+const ELF_MACHINE: Option<u16> = Some(243);
+#[cfg(target_arch = "powerpc64")]
+const ELF_MACHINE: Option<u16> = Some(21);
+#[cfg(target_arch = "s390x")]
+const ELF_MACHINE: Option<u16> = Some(22);
+#[cfg(target_arch = "loongarch64")]
+const ELF_MACHINE: Option<u16> = Some(258);
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    target_arch = "arm",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    target_arch = "powerpc64",
+    target_arch = "s390x",
+    target_arch = "loongarch64"
+)))]
+const ELF_MACHINE: Option<u16> = None;
+
+//Some dynamic linkers (e.g. glibc) report libraries built for another CPU as not found,
+//so check the header of the file if it is an ELF file of a different class or machine.
+fn is_foreign_elf(name: &OsStr) -> bool {
+    use std::io::Read;
+
+    //names without a slash are searched for in the library paths, not opened directly
+    if !name.as_bytes().contains(&b'/') {
+        return false;
+    }
+    let mut header = [0u8; 20];
+    let read = std::fs::File::open(name).and_then(|mut file| file.read_exact(&mut header));
+    let elf = match ElfFile::new(&header) {
+        Some(elf) if read.is_ok() => elf,
+        _ => return false,
+    };
+    let class = if cfg!(target_pointer_width = "64") {
+        2
+    } else {
+        1
+    };
+    let machine = match elf.u16(0x12) {
+        Some(machine) => machine,
+        None => return false,
+    };
+    header[4] != class || ELF_MACHINE.is_some_and(|expected| expected != machine)
+}
+
+//Reads the names of all symbols defined by an ELF file from its dynamic symbol table.
+//There is no dlopen() mode that maps a library without loading its dependencies,
+//so the file gets parsed instead.
+pub fn lib_exports(name: &OsStr) -> Result<Vec<String>, Error> {
+    let data = std::fs::read(name).map_err(Error::OpeningLibraryError)?;
+    let elf = ElfFile::new(&data).ok_or_else(|| {
+        Error::OpeningLibraryError(IoError::new(
+            ErrorKind::Unsupported,
+            "Only ELF files can be inspected on this platform",
+        ))
+    })?;
+    elf.exports().ok_or_else(|| {
+        Error::OpeningLibraryError(IoError::new(ErrorKind::InvalidData, "Malformed ELF file"))
+    })
+}
+
+struct ElfFile<'a> {
+    data: &'a [u8],
+    is_64: bool,
+    is_le: bool,
+}
+
+impl<'a> ElfFile<'a> {
+    const SHT_DYNSYM: u32 = 11;
+    const STB_GLOBAL: u8 = 1;
+    const STB_WEAK: u8 = 2;
+
+    //checks the identification bytes that every other read depends on
+    fn new(data: &'a [u8]) -> Option<ElfFile<'a>> {
+        if data.len() < 6 || data[..4] != *b"\x7fELF" {
+            return None;
+        }
+        let is_le = match data[5] {
+            1 => true,
+            2 => false,
+            _ => return None,
         };
+        Some(ElfFile {
+            data,
+            is_64: data[4] == 2,
+            is_le,
+        })
+    }
+
+    fn bytes<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        self.data
+            .get(offset..offset.checked_add(N)?)?
+            .try_into()
+            .ok()
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes = self.bytes(offset)?;
+        Some(if self.is_le {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let bytes = self.bytes(offset)?;
+        Some(if self.is_le {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    //address and offset sized fields
+    fn word(&self, offset: usize) -> Option<usize> {
+        if !self.is_64 {
+            return self.u32(offset).map(|val| val as usize);
+        }
+        let bytes = self.bytes(offset)?;
+        let val = if self.is_le {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        };
+        usize::try_from(val).ok()
+    }
+
+    fn c_str(&self, offset: usize) -> Option<&str> {
+        let tail = self.data.get(offset..)?;
+        let len = tail.iter().position(|&c| c == 0)?;
+        std::str::from_utf8(&tail[..len]).ok()
+    }
+
+    //returns the offset, size, linked section and entry size of a section
+    fn section(&self, index: usize) -> Option<(u32, usize, usize, usize, usize)> {
+        let (shoff, shentsize) = if self.is_64 {
+            (self.word(0x28)?, self.u16(0x3a)?)
+        } else {
+            (self.word(0x20)?, self.u16(0x2e)?)
+        };
+        let header = shoff.checked_add(index.checked_mul(shentsize as usize)?)?;
+        let sh_type = self.u32(header + 4)?;
+        Some(if self.is_64 {
+            (
+                sh_type,
+                self.word(header + 0x18)?,
+                self.word(header + 0x20)?,
+                self.u32(header + 0x28)? as usize,
+                self.word(header + 0x38)?,
+            )
+        } else {
+            (
+                sh_type,
+                self.word(header + 0x10)?,
+                self.word(header + 0x14)?,
+                self.u32(header + 0x18)? as usize,
+                self.word(header + 0x24)?,
+            )
+        })
+    }
+
+    fn exports(&self) -> Option<Vec<String>> {
+        let shnum = self.u16(if self.is_64 { 0x3c } else { 0x30 })? as usize;
+        let mut exports = Vec::new();
+        for index in 0..shnum {
+            let (sh_type, offset, size, link, entsize) = self.section(index)?;
+            if sh_type != Self::SHT_DYNSYM || entsize == 0 {
+                continue;
+            }
+            let (_, strtab, _, _, _) = self.section(link)?;
+            //the first symbol is always the undefined one
+            for sym in (offset..offset.checked_add(size)?).step_by(entsize).skip(1) {
+                let (info, shndx) = if self.is_64 {
+                    (*self.data.get(sym + 4)?, self.u16(sym + 6)?)
+                } else {
+                    (*self.data.get(sym + 12)?, self.u16(sym + 14)?)
+                };
+                let binding = info >> 4;
+                if shndx == 0 || (binding != Self::STB_GLOBAL && binding != Self::STB_WEAK) {
+                    continue;
+                }
+                let name = self.c_str(strtab.checked_add(self.u32(sym)? as usize)?)?;
+                if !name.is_empty() {
+                    exports.push(name.to_string());
+                }
+            }
+        }
+        Some(exports)
+    }
+}
+
+#[inline]
+pub unsafe fn find_lib(name: &OsStr) -> Option<Handle> {
+    unsafe { find_lib_in(name, &mut Vec::new()) }
+}
+
+#[inline]
+pub unsafe fn find_lib_in(name: &OsStr, buffer: &mut Vec<NameChar>) -> Option<Handle> {
+    unsafe {
+        buffer.clear();
+        let cstr = to_cstr(name, buffer).ok()?;
         let _lock = lock_dlerror_mutex();
-        let handle = dlopen(cstr.as_ptr(), flags.unwrap_or(DEFAULT_FLAGS));
+        //RTLD_NOLOAD only returns the handle of an already loaded library
+        let handle = dlopen(cstr.as_ptr(), libc::RTLD_NOLOAD | RTLD_LAZY);
         if handle.is_null() {
-            Err(Error::OpeningLibraryError(IoError::other(
-                CStr::from_ptr(dlerror()).to_string_lossy().to_string(),
-            )))
+            None
+        } else {
+            //release the additional reference, the handle stays valid while the library is loaded
+            dlclose(handle);
+            Some(handle)
+        }
+    }
+}
+
+//works on raw bytes, so names that are not valid UTF-8 get passed to the OS unchanged
+fn to_cstr<'a>(name: &'a OsStr, buffer: &'a mut Vec<u8>) -> Result<&'a CStr, NulError> {
+    //as_bytes i a unix-specific extension
+    let bytes = name.as_bytes();
+    match bytes.iter().position(|&c| c == 0) {
+        //already null-terminated, don't need to convert
+        Some(pos) if pos == bytes.len() - 1 => {
+            Ok(unsafe { CStr::from_bytes_with_nul_unchecked(bytes) })
+        }
+        //a null character inside the name would silently truncate it
+        Some(_) => Err(CString::new(bytes).unwrap_err()),
+        None => {
+            buffer.extend_from_slice(bytes);
+            buffer.push(0);
+            Ok(unsafe { CStr::from_bytes_with_nul_unchecked(buffer.as_slice()) })
+        }
+    }
+}
+
+#[cfg(target_os = "freebsd")]
+unsafe extern "C" {
+    fn fdlopen(fd: c_int, mode: c_int) -> *mut c_void;
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[inline]
+pub unsafe fn open_lib_fd(fd: BorrowedFd, flags: Option<i32>) -> Result<Handle, Error> {
+    unsafe { open_lib(OsStr::new(&fd_path(fd)), flags) }
+}
+
+//the dynamic linker has no fd based interface, but procfs exposes the descriptor as a path
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn fd_path(fd: BorrowedFd) -> String {
+    format!("/proc/self/fd/{}", fd.as_raw_fd())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[inline]
+pub unsafe fn find_lib_fd(fd: BorrowedFd) -> Option<Handle> {
+    unsafe { find_lib(OsStr::new(&fd_path(fd))) }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+#[inline]
+pub unsafe fn find_lib_fd(_fd: BorrowedFd) -> Option<Handle> {
+    None
+}
+
+#[cfg(target_os = "freebsd")]
+#[inline]
+pub unsafe fn open_lib_fd(fd: BorrowedFd, flags: Option<i32>) -> Result<Handle, Error> {
+    unsafe {
+        let _lock = lock_dlerror_mutex();
+        let handle = fdlopen(fd.as_raw_fd(), flags.unwrap_or(DEFAULT_FLAGS));
+        if handle.is_null() {
+            Err(open_error(dlerror_message(), None))
         } else {
             Ok(handle)
         }
     }
 }
 
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+#[inline]
+pub unsafe fn open_lib_fd(_fd: BorrowedFd, _flags: Option<i32>) -> Result<Handle, Error> {
+    Err(Error::OpeningLibraryError(IoError::new(
+        ErrorKind::Unsupported,
+        "Opening a library from a file descriptor is not supported on this platform",
+    )))
+}
+
+//only the leading fields of the glibc link_map structure are needed
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[repr(C)]
+struct LinkMap {
+    l_addr: usize,
+    l_name: *const c_char,
+    l_ld: *const ElfDyn,
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[repr(C)]
+struct ElfDyn {
+    d_tag: isize,
+    d_val: usize,
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu", target_pointer_width = "64"))]
+#[repr(C)]
+struct ElfSym {
+    st_name: u32,
+    st_info: u8,
+    st_other: u8,
+    st_shndx: u16,
+    st_value: u64,
+    st_size: u64,
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu", target_pointer_width = "32"))]
+#[repr(C)]
+struct ElfSym {
+    st_name: u32,
+    st_value: u32,
+    st_size: u32,
+    st_info: u8,
+    st_other: u8,
+    st_shndx: u16,
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+mod elf {
+    pub const DT_NULL: isize = 0;
+    pub const DT_HASH: isize = 4;
+    pub const DT_STRTAB: isize = 5;
+    pub const DT_SYMTAB: isize = 6;
+    pub const DT_INIT: isize = 12;
+    pub const DT_SONAME: isize = 14;
+    pub const DT_INIT_ARRAYSZ: isize = 27;
+    pub const DT_GNU_HASH: isize = 0x6ffffef5;
+    pub const STT_TLS: u8 = 6;
+    pub const STB_GLOBAL: u8 = 1;
+    pub const STB_WEAK: u8 = 2;
+    pub const SHN_UNDEF: u16 = 0;
+}
+
+//The dynamic section of a loaded library.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+struct DynamicSection {
+    base: usize,
+    entries: *const ElfDyn,
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+impl DynamicSection {
+    //libraries without a dynamic section give None
+    unsafe fn of(handle: Handle) -> Result<Option<DynamicSection>, IoError> {
+        unsafe {
+            let link_map = link_map(handle)?;
+            let entries = (*link_map).l_ld;
+            Ok((!entries.is_null()).then(|| DynamicSection {
+                base: (*link_map).l_addr,
+                entries,
+            }))
+        }
+    }
+
+    //the tags and values of the entries before DT_NULL
+    unsafe fn entries(&self) -> impl Iterator<Item = (isize, usize)> + '_ {
+        let mut entry = self.entries;
+        std::iter::from_fn(move || unsafe {
+            if (*entry).d_tag == elf::DT_NULL {
+                return None;
+            }
+            let item = ((*entry).d_tag, (*entry).d_val);
+            entry = entry.add(1);
+            Some(item)
+        })
+    }
+
+    //glibc relocates the addresses of the dynamic section in place on most,
+    //but not all architectures
+    fn address(&self, value: usize) -> usize {
+        if value < self.base {
+            value + self.base
+        } else {
+            value
+        }
+    }
+}
+
+//The dynamic symbol table of a loaded library.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+struct DynamicSymbols {
+    base: usize,
+    symtab: *const ElfSym,
+    strtab: *const c_char,
+    count: usize,
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+impl DynamicSymbols {
+    //the first symbol is always the undefined one
+    unsafe fn iter(&self) -> impl Iterator<Item = (&CStr, &ElfSym)> {
+        unsafe {
+            (1..self.count).map(|index| {
+                let sym = &*self.symtab.add(index);
+                (CStr::from_ptr(self.strtab.add(sym.st_name as usize)), sym)
+            })
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+unsafe fn dynamic_symbols(handle: Handle) -> Option<DynamicSymbols> {
+    unsafe {
+        let dynamic = DynamicSection::of(handle).ok()??;
+        let mut symtab: *const ElfSym = null();
+        let mut strtab: *const c_char = null();
+        let mut hash: *const u32 = null();
+        let mut gnu_hash: *const u32 = null();
+        for (tag, value) in dynamic.entries() {
+            let ptr = dynamic.address(value);
+            match tag {
+                elf::DT_SYMTAB => symtab = ptr as *const ElfSym,
+                elf::DT_STRTAB => strtab = ptr as *const c_char,
+                elf::DT_HASH => hash = ptr as *const u32,
+                elf::DT_GNU_HASH => gnu_hash = ptr as *const u32,
+                _ => (),
+            }
+        }
+        if symtab.is_null() || strtab.is_null() {
+            return None;
+        }
+        let count = if !hash.is_null() {
+            //nchain is equal to the number of symbols
+            *hash.add(1) as usize
+        } else if !gnu_hash.is_null() {
+            gnu_hash_symbol_count(gnu_hash)
+        } else {
+            return None;
+        };
+        Some(DynamicSymbols {
+            base: dynamic.base,
+            symtab,
+            strtab,
+            count,
+        })
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub unsafe fn lib_exported_symbols(handle: Handle) -> Result<Vec<ExportedSymbol>, Error> {
+    unsafe {
+        let symbols = dynamic_symbols(handle).ok_or_else(|| {
+            Error::LibraryInfoError(IoError::other(
+                "The dynamic symbol table of the library could not be found",
+            ))
+        })?;
+        Ok(symbols
+            .iter()
+            .filter(|(name, sym)| {
+                let binding = sym.st_info >> 4;
+                sym.st_shndx != elf::SHN_UNDEF
+                    //the value of thread-local symbols is an offset, not an address
+                    && sym.st_info & 0xf != elf::STT_TLS
+                    && (binding == elf::STB_GLOBAL || binding == elf::STB_WEAK)
+                    && !name.is_empty()
+            })
+            .map(|(name, sym)| ExportedSymbol {
+                name: name.to_string_lossy().into_owned(),
+                addr: symbols.base.wrapping_add(sym.st_value as usize) as *const (),
+            })
+            .collect())
+    }
+}
+
+//Lists the symbols the library imports, which are the undefined entries of its dynamic symbol table.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub unsafe fn lib_undefined_symbols(handle: Handle) -> Result<Vec<String>, Error> {
+    unsafe {
+        let symbols = dynamic_symbols(handle).ok_or_else(|| {
+            Error::LibraryInfoError(IoError::other(
+                "The dynamic symbol table of the library could not be found",
+            ))
+        })?;
+        Ok(symbols
+            .iter()
+            .filter(|(name, sym)| sym.st_shndx == elf::SHN_UNDEF && !name.is_empty())
+            .map(|(name, _)| name.to_string_lossy().into_owned())
+            .collect())
+    }
+}
+
+//the GNU hash table does not store the number of symbols, it needs to be found
+//by walking the chain of the last bucket
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+unsafe fn gnu_hash_symbol_count(table: *const u32) -> usize {
+    unsafe {
+        let nbuckets = *table as usize;
+        let symoffset = *table.add(1) as usize;
+        let bloom_size = *table.add(2) as usize;
+        let buckets = table.add(4 + bloom_size * size_of::<usize>() / size_of::<u32>());
+        let chain = buckets.add(nbuckets);
+        let last = (0..nbuckets)
+            .map(|i| *buckets.add(i) as usize)
+            .max()
+            .unwrap_or(0);
+        if last < symoffset {
+            return symoffset;
+        }
+        let mut index = last;
+        while *chain.add(index - symoffset) & 1 == 0 {
+            index += 1;
+        }
+        index + 1
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[inline]
+unsafe fn link_map(handle: Handle) -> Result<*const LinkMap, IoError> {
+    unsafe {
+        let _lock = lock_dlerror_mutex();
+        let mut link_map: *const LinkMap = null();
+        if dlinfo(
+            handle,
+            RTLD_DI_LINKMAP,
+            &mut link_map as *mut *const LinkMap as *mut c_void,
+        ) != 0
+        {
+            return Err(IoError::other(dlerror_message()));
+        }
+        Ok(link_map)
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[inline]
+pub unsafe fn promote_lib_to_global(handle: Handle) -> Result<(), Error> {
+    unsafe {
+        let link_map = link_map(handle).map_err(Error::OpeningLibraryError)?;
+        let _lock = lock_dlerror_mutex();
+        let name = (*link_map).l_name;
+        //the main program has an empty name and is always globally visible
+        if name.is_null() || *name == 0 {
+            return Ok(());
+        }
+        //reopening an already loaded library with RTLD_GLOBAL changes its visibility
+        let promoted = dlopen(name, RTLD_GLOBAL | RTLD_NOLOAD | RTLD_LAZY);
+        if promoted.is_null() {
+            Err(Error::OpeningLibraryError(
+                IoError::other(dlerror_message()),
+            ))
+        } else {
+            //the promotion persists, release the additional reference
+            close_lib(promoted);
+            Ok(())
+        }
+    }
+}
+
+//Checks if the dynamic section declares functions that run when the library gets loaded.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub unsafe fn lib_has_initializers(handle: Handle) -> Result<bool, Error> {
+    unsafe {
+        let dynamic = match DynamicSection::of(handle).map_err(Error::LibraryInfoError)? {
+            Some(dynamic) => dynamic,
+            None => return Ok(false),
+        };
+        Ok(dynamic
+            .entries()
+            .any(|(tag, value)| tag == elf::DT_INIT || (tag == elf::DT_INIT_ARRAYSZ && value != 0)))
+    }
+}
+
+//Reads the DT_SONAME entry of the dynamic section, which is an offset into the string table.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub unsafe fn lib_soname(handle: Handle) -> Result<Option<String>, Error> {
+    unsafe {
+        let dynamic = match DynamicSection::of(handle).map_err(Error::LibraryInfoError)? {
+            Some(dynamic) => dynamic,
+            None => return Ok(None),
+        };
+        let mut strtab: Option<usize> = None;
+        let mut soname: Option<usize> = None;
+        for (tag, value) in dynamic.entries() {
+            match tag {
+                elf::DT_STRTAB => strtab = Some(dynamic.address(value)),
+                elf::DT_SONAME => soname = Some(value),
+                _ => (),
+            }
+        }
+        Ok(match (strtab, soname) {
+            (Some(strtab), Some(offset)) => Some(
+                CStr::from_ptr((strtab + offset) as *const c_char)
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+            _ => None,
+        })
+    }
+}
+
+//Mach-O libraries have an install name instead, which is not a SONAME
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub unsafe fn lib_soname(_handle: Handle) -> Result<Option<String>, Error> {
+    Ok(None)
+}
+
+#[cfg(not(any(
+    all(target_os = "linux", target_env = "gnu"),
+    target_os = "macos",
+    target_os = "ios"
+)))]
+pub unsafe fn lib_soname(_handle: Handle) -> Result<Option<String>, Error> {
+    Err(Error::LibraryInfoError(IoError::new(
+        ErrorKind::Unsupported,
+        "Reading the SONAME is not supported on this platform",
+    )))
+}
+
+//Reads e_entry from the ELF header, which is mapped at the start of the library.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub unsafe fn lib_entry_point(handle: Handle) -> Result<Option<*const ()>, Error> {
+    unsafe {
+        let link_map = link_map(handle).map_err(Error::LibraryInfoError)?;
+        let mut info = MaybeUninit::<Dl_info>::uninit();
+        if dladdr((*link_map).l_ld as *const c_void, info.as_mut_ptr()) == 0 {
+            return Err(Error::LibraryInfoError(IoError::other(
+                "The ELF header of the library could not be found",
+            )));
+        }
+        let header = info.assume_init().dli_fbase as *const u8;
+        if header.is_null() || *(header as *const [u8; 4]) != *b"\x7fELF" {
+            return Err(Error::LibraryInfoError(IoError::new(
+                ErrorKind::InvalidData,
+                "The library is not mapped with a valid ELF header",
+            )));
+        }
+        //e_entry follows e_ident, e_type, e_machine and e_version in both ELF classes
+        //and has the size of an address
+        let entry = (header.add(24) as *const usize).read_unaligned();
+        if entry == 0 {
+            return Ok(None);
+        }
+        Ok(Some((*link_map).l_addr.wrapping_add(entry) as *const ()))
+    }
+}
+
+//dynamic libraries have no entry point in Mach-O
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub unsafe fn lib_entry_point(_handle: Handle) -> Result<Option<*const ()>, Error> {
+    Ok(None)
+}
+
+#[cfg(not(any(
+    all(target_os = "linux", target_env = "gnu"),
+    target_os = "macos",
+    target_os = "ios"
+)))]
+pub unsafe fn lib_entry_point(_handle: Handle) -> Result<Option<*const ()>, Error> {
+    Err(Error::LibraryInfoError(IoError::new(
+        ErrorKind::Unsupported,
+        "Reading the entry point is not supported on this platform",
+    )))
+}
+
+//not exported by the libc crate, the array of paths follows the header
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[repr(C)]
+struct DlSerpath {
+    dls_name: *mut c_char,
+    dls_flags: c_uint,
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[repr(C)]
+struct DlSerinfo {
+    dls_size: usize,
+    dls_cnt: c_uint,
+    dls_serpath: [DlSerpath; 1],
+}
+
+//Asks the dynamic linker for the directories it searches when the program loads a library:
+//RTLD_DI_SERINFOSIZE reports the size of the buffer that RTLD_DI_SERINFO then fills.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub fn search_paths() -> Result<Vec<PathBuf>, Error> {
+    unsafe {
+        let handle = open_self().map_err(|err| match err {
+            Error::OpeningLibraryError(err) => Error::LibraryInfoError(err),
+            err => err,
+        })?;
+        let result = {
+            let _lock = lock_dlerror_mutex();
+            let mut size = MaybeUninit::<DlSerinfo>::zeroed();
+            if dlinfo(
+                handle,
+                RTLD_DI_SERINFOSIZE,
+                size.as_mut_ptr() as *mut c_void,
+            ) != 0
+            {
+                Err(Error::LibraryInfoError(IoError::other(dlerror_message())))
+            } else {
+                let size = size.assume_init();
+                //u64 keeps the buffer aligned for the pointers inside
+                let mut buffer = vec![0u64; size.dls_size.div_ceil(size_of::<u64>())];
+                let info = buffer.as_mut_ptr() as *mut DlSerinfo;
+                (*info).dls_size = size.dls_size;
+                (*info).dls_cnt = size.dls_cnt;
+                if dlinfo(handle, RTLD_DI_SERINFO, info as *mut c_void) != 0 {
+                    Err(Error::LibraryInfoError(IoError::other(dlerror_message())))
+                } else {
+                    let paths = std::slice::from_raw_parts(
+                        (&raw const (*info).dls_serpath) as *const DlSerpath,
+                        (*info).dls_cnt as usize,
+                    );
+                    Ok(paths
+                        .iter()
+                        .map(|path| {
+                            PathBuf::from(OsString::from_vec(
+                                CStr::from_ptr(path.dls_name).to_bytes().to_vec(),
+                            ))
+                        })
+                        .collect())
+                }
+            }
+        };
+        close_lib(handle);
+        result
+    }
+}
+
+#[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+pub fn search_paths() -> Result<Vec<PathBuf>, Error> {
+    Err(Error::LibraryInfoError(IoError::new(
+        ErrorKind::Unsupported,
+        "Listing the search paths is not supported on this platform",
+    )))
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[inline]
+pub unsafe fn lib_info(handle: Handle, request: DlInfoRequest) -> Result<DlInfoValue, Error> {
+    unsafe {
+        match request {
+            DlInfoRequest::Origin => {
+                let _lock = lock_dlerror_mutex();
+                let mut buffer = vec![0u8; PATH_MAX as usize + 1];
+                if dlinfo(handle, RTLD_DI_ORIGIN, buffer.as_mut_ptr() as *mut c_void) != 0 {
+                    return Err(Error::LibraryInfoError(IoError::other(dlerror_message())));
+                }
+                let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+                buffer.truncate(len);
+                Ok(DlInfoValue::Origin(OsString::from_vec(buffer).into()))
+            }
+            DlInfoRequest::LinkMap => {
+                let link_map = link_map(handle).map_err(Error::LibraryInfoError)?;
+                let name = (*link_map).l_name;
+                let path = if name.is_null() {
+                    OsString::new()
+                } else {
+                    OsString::from_vec(CStr::from_ptr(name).to_bytes().to_vec())
+                };
+                Ok(DlInfoValue::LinkMap {
+                    base_addr: (*link_map).l_addr as *const (),
+                    path: path.into(),
+                })
+            }
+        }
+    }
+}
+
+//not exported by the libc crate
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+const RTLD_DL_LINKMAP: c_int = 2;
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[inline]
+pub unsafe fn get_local_sym(handle: Handle, name: &CStr) -> Result<*mut (), Error> {
+    unsafe {
+        let symbol = get_sym(handle, name)?;
+        if symbol.is_null() {
+            return Ok(symbol);
+        }
+        //dlsym() also searches the dependencies of the library,
+        //so check which library actually defines the symbol
+        let own = link_map(handle).map_err(Error::SymbolGettingError)?;
+        let mut info = MaybeUninit::<Dl_info>::uninit();
+        let mut defining: *mut c_void = null_mut();
+        if dladdr1(
+            symbol as *const c_void,
+            info.as_mut_ptr(),
+            &mut defining,
+            RTLD_DL_LINKMAP,
+        ) != 0
+            && defining as *const LinkMap == own
+        {
+            Ok(symbol)
+        } else {
+            Err(Error::SymbolGettingError(IoError::other(format!(
+                "Symbol {} is not defined by the library itself",
+                name.to_string_lossy()
+            ))))
+        }
+    }
+}
+
 #[inline]
-pub unsafe fn addr_info_init() {}
+pub unsafe fn addr_info_init(_search_path: Option<&OsStr>) {}
 #[inline]
 pub unsafe fn addr_info_cleanup() {}
 
 use std::mem::MaybeUninit;
 #[inline]
-pub fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
+pub fn addr_info_obtain(addr: *const (), demangle: Option<bool>) -> Result<AddressInfo, Error> {
     // let mut dlinfo: Dl_info = unsafe{uninitialized()};
     let mut dlinfo = MaybeUninit::<Dl_info>::uninit();
     let result = unsafe { dladdr(addr as *const c_void, dlinfo.as_mut_ptr()) };
@@ -112,15 +1164,28 @@ pub fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
         let os = if dlinfo.dli_saddr.is_null() || dlinfo.dli_sname.is_null() {
             None
         } else {
+            let name = unsafe {
+                CStr::from_ptr(dlinfo.dli_sname)
+                    .to_string_lossy()
+                    .into_owned()
+            };
+            //dladdr() always returns mangled names
+            #[cfg(feature = "demangle")]
+            let name = match demangle {
+                Some(true) => demangle_name(&name).unwrap_or(name),
+                _ => name,
+            };
+            #[cfg(not(feature = "demangle"))]
+            let _ = demangle;
             Some(OverlappingSymbol {
                 addr: dlinfo.dli_saddr as *const (),
-                name: unsafe {
-                    CStr::from_ptr(dlinfo.dli_sname)
-                        .to_string_lossy()
-                        .into_owned()
-                },
+                name,
             })
         };
+        let offset_from_symbol = match os {
+            Some(ref os) => (addr as usize).wrapping_sub(os.addr as usize),
+            None => 0,
+        };
         Ok(AddressInfo {
             dll_path: unsafe {
                 CStr::from_ptr(dlinfo.dli_fname)
@@ -129,15 +1194,70 @@ pub fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
             },
             dll_base_addr: dlinfo.dli_fbase as *const (),
             overlapping_symbol: os,
+            offset_from_symbol,
         })
     }
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn addr_protection(addr: *const ()) -> Result<MemoryProtection, Error> {
+    let maps = std::fs::read_to_string("/proc/self/maps").map_err(Error::MemoryQueryError)?;
+    let addr = addr as usize;
+    //each line looks like "7f0c5a2d1000-7f0c5a2f3000 r-xp 00000000 08:01 1234 /lib/libc.so.6"
+    for line in maps.lines() {
+        let mut fields = line.split_ascii_whitespace();
+        let (range, perms) = match (fields.next(), fields.next()) {
+            (Some(range), Some(perms)) => (range, perms.as_bytes()),
+            _ => continue,
+        };
+        let (start, end) = match range.split_once('-') {
+            Some((start, end)) => (start, end),
+            None => continue,
+        };
+        let (start, end) = match (
+            usize::from_str_radix(start, 16),
+            usize::from_str_radix(end, 16),
+        ) {
+            (Ok(start), Ok(end)) => (start, end),
+            _ => continue,
+        };
+        if (start..end).contains(&addr) && perms.len() >= 3 {
+            return Ok(MemoryProtection {
+                readable: perms[0] == b'r',
+                writable: perms[1] == b'w',
+                executable: perms[2] == b'x',
+            });
+        }
+    }
+    Err(Error::MemoryQueryError(IoError::new(
+        ErrorKind::NotFound,
+        "The address is not mapped",
+    )))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn addr_protection(_addr: *const ()) -> Result<MemoryProtection, Error> {
+    Err(Error::MemoryQueryError(IoError::new(
+        ErrorKind::Unsupported,
+        "Querying the memory protection is not supported on this platform",
+    )))
+}
+
 #[inline]
 pub fn close_lib(handle: Handle) -> Handle {
-    let result = unsafe { dlclose(handle) };
-    if result != 0 {
+    if try_close_lib(handle).is_err() {
         panic!("Call to dlclose() failed");
     }
     null_mut()
 }
+
+#[inline]
+pub fn try_close_lib(handle: Handle) -> Result<(), Error> {
+    if unsafe { dlclose(handle) } == 0 {
+        Ok(())
+    } else {
+        Err(Error::ClosingLibraryError(IoError::other(unsafe {
+            dlerror_message()
+        })))
+    }
+}
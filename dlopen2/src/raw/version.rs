@@ -0,0 +1,32 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Version of a library as stored in its version resource (`major.minor.build.revision`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u16,
+    pub minor: u16,
+    pub build: u16,
+    pub revision: u16,
+}
+
+impl Version {
+    //versions are stored as two 32-bit halves: major and minor, then build and revision
+    pub(crate) fn from_parts(most_significant: u32, least_significant: u32) -> Version {
+        Version {
+            major: (most_significant >> 16) as u16,
+            minor: most_significant as u16,
+            build: (least_significant >> 16) as u16,
+            revision: least_significant as u16,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.build, self.revision
+        )
+    }
+}
@@ -1,26 +1,54 @@
 use crate::utils;
 
 use super::super::err::Error;
-use super::common::{AddressInfo, OverlappingSymbol};
+use super::common::{AddressInfo, BindingMode, ExportedSymbol, OverlappingSymbol};
+use super::protection::MemoryProtection;
+use super::version::Version;
 use once_cell::sync::{Lazy, OnceCell};
-use std::ffi::{CStr, OsStr, OsString};
-use std::io::Error as IoError;
+use std::ffi::{CStr, CString, OsStr, OsString};
+use std::fs::{OpenOptions, remove_file};
+use std::io::Write;
+use std::io::{Error as IoError, ErrorKind};
+use std::mem::MaybeUninit;
 use std::mem::size_of;
+use std::os::raw::c_char;
 use std::os::windows::ffi::{OsStrExt, OsStringExt};
-use std::ptr::null_mut;
+use std::path::{Path, PathBuf, absolute};
+use std::ptr::{null, null_mut};
 use std::slice;
 use std::sync::Mutex;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use winapi::ctypes::c_void;
 use winapi::shared::basetsd::DWORD64;
-use winapi::shared::minwindef::{DWORD, HMODULE, TRUE};
-use winapi::shared::winerror::ERROR_CALL_NOT_IMPLEMENTED;
-use winapi::um::dbghelp::{SYMBOL_INFOW, SymFromAddrW, SymGetModuleBase64, SymInitializeW};
-use winapi::um::errhandlingapi::{GetLastError, SetErrorMode, SetThreadErrorMode};
+use winapi::shared::minwindef::{BOOL, DWORD, HMODULE, TRUE};
+use winapi::shared::winerror::{
+    ERROR_BAD_EXE_FORMAT, ERROR_CALL_NOT_IMPLEMENTED, ERROR_RESOURCE_DATA_NOT_FOUND,
+    ERROR_RESOURCE_TYPE_NOT_FOUND,
+};
+use winapi::um::dbghelp::{
+    SYMBOL_INFOW, SYMOPT_UNDNAME, SymFromAddrW, SymGetModuleBase64, SymGetOptions, SymInitializeW,
+    SymSetOptions,
+};
+use winapi::um::errhandlingapi::{GetLastError, SetErrorMode, SetLastError, SetThreadErrorMode};
 use winapi::um::libloaderapi::{
-    FreeLibrary, GetModuleFileNameW, GetModuleHandleExW, GetProcAddress, LoadLibraryW,
+    AddDllDirectory, FindResourceW, FreeLibrary, GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS,
+    GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT, GetModuleFileNameW, GetModuleHandleExW,
+    GetModuleHandleW, GetProcAddress, LOAD_LIBRARY_AS_IMAGE_RESOURCE,
+    LOAD_LIBRARY_SEARCH_DEFAULT_DIRS, LoadLibraryExW, LoadLibraryW, LoadResource, LockResource,
+    RemoveDllDirectory, SizeofResource,
 };
+use winapi::um::memoryapi::VirtualQuery;
 use winapi::um::processthreadsapi::GetCurrentProcess;
-use winapi::um::winnt::WCHAR;
+use winapi::um::sysinfoapi::{GetSystemDirectoryW, GetWindowsDirectoryW};
+use winapi::um::winbase::GetDllDirectoryW;
+use winapi::um::winnt::{
+    HANDLE, IMAGE_DIRECTORY_ENTRY_EXPORT, IMAGE_DIRECTORY_ENTRY_IMPORT, IMAGE_DIRECTORY_ENTRY_TLS,
+    IMAGE_DOS_HEADER, IMAGE_DOS_SIGNATURE, IMAGE_EXPORT_DIRECTORY, IMAGE_IMPORT_DESCRIPTOR,
+    IMAGE_NT_HEADERS, IMAGE_NT_OPTIONAL_HDR_MAGIC, IMAGE_NT_SIGNATURE, IMAGE_TLS_DIRECTORY,
+    MEM_COMMIT, MEMORY_BASIC_INFORMATION, PAGE_EXECUTE, PAGE_EXECUTE_READ, PAGE_EXECUTE_READWRITE,
+    PAGE_EXECUTE_WRITECOPY, PAGE_READONLY, PAGE_READWRITE, PAGE_WRITECOPY, WCHAR,
+};
+use winapi::um::winver::{GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW};
 
 static USE_ERRORMODE: AtomicBool = AtomicBool::new(false);
 
@@ -41,6 +69,13 @@ static SET_ERR_MODE_DATA: Lazy<Mutex<SetErrorModeData>> = Lazy::new(|| {
 static SYM_MUTEX: OnceCell<Mutex<()>> = OnceCell::new();
 
 pub type Handle = HMODULE;
+pub type NameChar = WCHAR;
+//LoadLibraryW always resolves imports when the library gets loaded
+pub const NOW_FLAGS: Option<i32> = None;
+
+pub fn flags_binding_mode(_flags: Option<i32>) -> BindingMode {
+    BindingMode::Now
+}
 
 /*
 Windows has an ugly feature: by default not finding the given library opens a window
@@ -144,6 +179,65 @@ pub unsafe fn get_sym(handle: Handle, name: &CStr) -> Result<*mut (), Error> {
     }
 }
 
+#[inline]
+pub unsafe fn get_local_sym(handle: Handle, name: &CStr) -> Result<*mut (), Error> {
+    unsafe {
+        let symbol = get_sym(handle, name)?;
+        //forwarded exports resolve to a different module
+        let mut defining: Handle = null_mut();
+        if GetModuleHandleExW(
+            GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+            symbol as *const WCHAR,
+            &mut defining,
+        ) == 0
+        {
+            return Err(Error::SymbolGettingError(get_win_error()));
+        }
+        if defining == handle {
+            Ok(symbol)
+        } else {
+            Err(Error::SymbolGettingError(IoError::other(format!(
+                "Symbol {} is not defined by the library itself",
+                name.to_string_lossy()
+            ))))
+        }
+    }
+}
+
+//GetProcAddress() is case-sensitive, so look for the exact name in the export table first
+pub unsafe fn get_sym_case_insensitive(handle: Handle, name: &CStr) -> Result<*mut (), Error> {
+    unsafe {
+        if let Ok(symbol) = get_sym(handle, name) {
+            return Ok(symbol);
+        }
+        let base = handle as *const u8;
+        let nt_headers = nt_headers(base).map_err(Error::SymbolGettingError)?;
+        let wanted = name.to_bytes();
+        match named_exports(base, nt_headers)
+            .into_iter()
+            .find(|(export, _)| export.as_bytes().eq_ignore_ascii_case(wanted))
+        {
+            //resolving the exact name also follows forwarded exports
+            Some((export, _)) => match CString::new(export) {
+                Ok(export) => get_sym(handle, &export),
+                Err(err) => Err(Error::NullCharacter(err)),
+            },
+            None => Err(Error::SymbolGettingError(IoError::other(format!(
+                "Symbol {} not found ignoring case",
+                name.to_string_lossy()
+            )))),
+        }
+    }
+}
+
+#[inline]
+pub unsafe fn get_scoped_sym(_next: bool, _name: &CStr) -> Result<*mut (), Error> {
+    Err(Error::SymbolGettingError(IoError::new(
+        ErrorKind::Unsupported,
+        "Searching symbols in the global scope is not supported on this platform",
+    )))
+}
+
 #[inline]
 pub unsafe fn open_self() -> Result<Handle, Error> {
     unsafe {
@@ -157,36 +251,523 @@ pub unsafe fn open_self() -> Result<Handle, Error> {
 }
 
 #[inline]
-pub unsafe fn open_lib(name: &OsStr, _flags: Option<i32>) -> Result<Handle, Error> {
+pub unsafe fn open_lib(name: &OsStr, flags: Option<i32>) -> Result<Handle, Error> {
+    unsafe { open_lib_in(name, &mut Vec::new(), flags) }
+}
+
+#[inline]
+pub unsafe fn open_lib_in(
+    name: &OsStr,
+    wide_name: &mut Vec<NameChar>,
+    _flags: Option<i32>,
+) -> Result<Handle, Error> {
     unsafe {
-        let wide_name: Vec<u16> = name.encode_wide().chain(Some(0)).collect();
+        wide_name.clear();
+        wide_name.extend(name.encode_wide().chain(Some(0)));
         let _guard = match ErrorModeGuard::new() {
             Ok(val) => val,
             Err(err) => return Err(Error::OpeningLibraryError(err)),
         };
         let handle = LoadLibraryW(wide_name.as_ptr());
         if handle.is_null() {
-            Err(Error::OpeningLibraryError(get_win_error()))
+            let error = get_win_error();
+            if error.raw_os_error() == Some(ERROR_BAD_EXE_FORMAT as i32) {
+                Err(Error::ArchitectureMismatch(error))
+            } else {
+                Err(Error::OpeningLibraryError(error))
+            }
+        } else {
+            Ok(handle)
+        }
+    }
+}
+
+//PE files have no equivalent of the SONAME
+pub unsafe fn lib_soname(_handle: Handle) -> Result<Option<String>, Error> {
+    Ok(None)
+}
+
+//Calls a function that copies a path into the buffer and returns its length, or the required
+//size of the buffer if it is too small. An empty path is returned as `None`.
+fn read_wide_path<F>(mut read: F) -> Result<Option<PathBuf>, Error>
+where
+    F: FnMut(&mut [WCHAR]) -> DWORD,
+{
+    let mut buffer = vec![0; PATH_MAX as usize];
+    loop {
+        unsafe { SetLastError(0) };
+        let len = read(&mut buffer) as usize;
+        if len < buffer.len() {
+            if len == 0 {
+                let error = unsafe { get_win_error() };
+                return match error.raw_os_error() {
+                    Some(0) | None => Ok(None),
+                    Some(_) => Err(Error::LibraryInfoError(error)),
+                };
+            }
+            return Ok(Some(OsString::from_wide(&buffer[..len]).into()));
+        }
+        buffer.resize((len + 1).max(buffer.len() * 2), 0);
+    }
+}
+
+//Follows the documented search order of LoadLibrary() with the safe DLL search mode:
+//a directory set by SetDllDirectory() takes the place of the current directory.
+pub fn search_paths() -> Result<Vec<PathBuf>, Error> {
+    unsafe {
+        let mut paths = Vec::new();
+        let exe = std::env::current_exe().map_err(Error::LibraryInfoError)?;
+        paths.extend(exe.parent().map(Path::to_path_buf));
+        let dll_dir =
+            read_wide_path(|buffer| GetDllDirectoryW(buffer.len() as DWORD, buffer.as_mut_ptr()))?;
+        let has_dll_dir = dll_dir.is_some();
+        paths.extend(dll_dir);
+        let system_dir =
+            read_wide_path(|buffer| GetSystemDirectoryW(buffer.as_mut_ptr(), buffer.len() as u32))?;
+        paths.extend(system_dir);
+        let windows_dir = read_wide_path(|buffer| {
+            GetWindowsDirectoryW(buffer.as_mut_ptr(), buffer.len() as u32)
+        })?;
+        if let Some(windows_dir) = windows_dir {
+            //the 16-bit system directory
+            paths.push(windows_dir.join("System"));
+            paths.push(windows_dir);
+        }
+        if !has_dll_dir {
+            paths.push(std::env::current_dir().map_err(Error::LibraryInfoError)?);
+        }
+        if let Some(path) = std::env::var_os("PATH") {
+            paths.extend(std::env::split_paths(&path).filter(|dir| !dir.as_os_str().is_empty()));
+        }
+        Ok(paths)
+    }
+}
+
+//directories added by AddDllDirectory() are used by all loads with the LOAD_LIBRARY_SEARCH_* flags,
+//serializing the loads limits each directory to the load it was added for
+static DLL_DIRECTORY_MUTEX: Mutex<()> = Mutex::new(());
+
+pub unsafe fn open_lib_with_dir(
+    name: &OsStr,
+    dir: &Path,
+    _flags: Option<i32>,
+) -> Result<Handle, Error> {
+    unsafe {
+        let dir = absolute(dir).map_err(Error::OpeningLibraryError)?;
+        let wide_dir: Vec<WCHAR> = dir.as_os_str().encode_wide().chain(Some(0)).collect();
+        let wide_name: Vec<WCHAR> = name.encode_wide().chain(Some(0)).collect();
+        let _guard = match ErrorModeGuard::new() {
+            Ok(val) => val,
+            Err(err) => return Err(Error::OpeningLibraryError(err)),
+        };
+        let _lock = DLL_DIRECTORY_MUTEX
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let cookie = AddDllDirectory(wide_dir.as_ptr());
+        if cookie.is_null() {
+            return Err(Error::OpeningLibraryError(get_win_error()));
+        }
+        let handle = LoadLibraryExW(
+            wide_name.as_ptr(),
+            null_mut(),
+            LOAD_LIBRARY_SEARCH_DEFAULT_DIRS,
+        );
+        let result = if handle.is_null() {
+            let error = get_win_error();
+            if error.raw_os_error() == Some(ERROR_BAD_EXE_FORMAT as i32) {
+                Err(Error::ArchitectureMismatch(error))
+            } else {
+                Err(Error::OpeningLibraryError(error))
+            }
         } else {
             Ok(handle)
+        };
+        RemoveDllDirectory(cookie);
+        result
+    }
+}
+
+//RT_RCDATA from winuser.h, resource types are passed as integer identifiers
+const RT_RCDATA: usize = 10;
+//distinguishes temporary files created by the same process
+static RESOURCE_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+//Copies an RT_RCDATA resource of the module to a new temporary file and loads it from there.
+//The file is created exclusively so that an existing file cannot be loaded instead,
+//it is removed again if the load fails.
+pub unsafe fn open_lib_from_resource(
+    module: Handle,
+    resource_id: u16,
+    flags: Option<i32>,
+) -> Result<(Handle, PathBuf), Error> {
+    unsafe {
+        let resource = FindResourceW(
+            module,
+            resource_id as usize as *const WCHAR,
+            RT_RCDATA as *const WCHAR,
+        );
+        if resource.is_null() {
+            return Err(Error::OpeningLibraryError(get_win_error()));
+        }
+        let data = LoadResource(module, resource);
+        if data.is_null() {
+            return Err(Error::OpeningLibraryError(get_win_error()));
+        }
+        let ptr = LockResource(data) as *const u8;
+        let len = SizeofResource(module, resource) as usize;
+        if ptr.is_null() {
+            return Err(Error::OpeningLibraryError(get_win_error()));
+        }
+        let bytes = slice::from_raw_parts(ptr, len);
+        let path = std::env::temp_dir().join(format!(
+            "dlopen2-{}-{}-{}.dll",
+            std::process::id(),
+            RESOURCE_FILE_COUNTER.fetch_add(1, Ordering::Relaxed),
+            resource_id
+        ));
+        let written = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(bytes));
+        if let Err(err) = written {
+            let _ = remove_file(&path);
+            return Err(Error::OpeningLibraryError(err));
+        }
+        match open_lib(path.as_os_str(), flags) {
+            Ok(handle) => Ok((handle, path)),
+            Err(err) => {
+                let _ = remove_file(&path);
+                Err(err)
+            }
+        }
+    }
+}
+
+//Reads the names exported by a module mapped as an image resource. Such a mapping
+//neither resolves the imports of the module nor runs any of its code.
+pub fn lib_exports(name: &OsStr) -> Result<Vec<String>, Error> {
+    unsafe {
+        let wide_name: Vec<WCHAR> = name.encode_wide().chain(Some(0)).collect();
+        let _guard = match ErrorModeGuard::new() {
+            Ok(val) => val,
+            Err(err) => return Err(Error::OpeningLibraryError(err)),
+        };
+        let handle = LoadLibraryExW(
+            wide_name.as_ptr(),
+            null_mut(),
+            LOAD_LIBRARY_AS_IMAGE_RESOURCE,
+        );
+        if handle.is_null() {
+            return Err(Error::OpeningLibraryError(get_win_error()));
+        }
+        let exports = mapped_exports(handle);
+        FreeLibrary(handle);
+        exports
+    }
+}
+
+unsafe fn mapped_exports(handle: Handle) -> Result<Vec<String>, Error> {
+    unsafe {
+        //the low bits of the handle mark the kind of the mapping
+        let base = (handle as usize & !3) as *const u8;
+        let nt_headers = nt_headers(base).map_err(Error::OpeningLibraryError)?;
+        let optional = &(*nt_headers).OptionalHeader;
+        if optional.Magic != IMAGE_NT_OPTIONAL_HDR_MAGIC {
+            return Err(Error::ArchitectureMismatch(IoError::other(
+                "The module was built for a different architecture",
+            )));
+        }
+        Ok(named_exports(base, nt_headers)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect())
+    }
+}
+
+//Reads the export table of a module mapped as an image. Returns the name of every export
+//together with the RVA of the function, or None for exports forwarded to other modules.
+unsafe fn named_exports(
+    base: *const u8,
+    nt_headers: *const IMAGE_NT_HEADERS,
+) -> Vec<(String, Option<DWORD>)> {
+    unsafe {
+        let optional = &(*nt_headers).OptionalHeader;
+        if optional.NumberOfRvaAndSizes as usize <= IMAGE_DIRECTORY_ENTRY_EXPORT as usize {
+            return Vec::new();
+        }
+        let export_dir = optional.DataDirectory[IMAGE_DIRECTORY_ENTRY_EXPORT as usize];
+        if export_dir.VirtualAddress == 0 {
+            return Vec::new();
+        }
+        let exports =
+            &*(base.add(export_dir.VirtualAddress as usize) as *const IMAGE_EXPORT_DIRECTORY);
+        let names = slice::from_raw_parts(
+            base.add(exports.AddressOfNames as usize) as *const DWORD,
+            exports.NumberOfNames as usize,
+        );
+        let ordinals = slice::from_raw_parts(
+            base.add(exports.AddressOfNameOrdinals as usize) as *const u16,
+            exports.NumberOfNames as usize,
+        );
+        let functions = slice::from_raw_parts(
+            base.add(exports.AddressOfFunctions as usize) as *const DWORD,
+            exports.NumberOfFunctions as usize,
+        );
+        let export_range = export_dir.VirtualAddress..export_dir.VirtualAddress + export_dir.Size;
+        names
+            .iter()
+            .zip(ordinals)
+            .map(|(&name_rva, &ordinal)| {
+                let name = CStr::from_ptr(base.add(name_rva as usize) as *const c_char)
+                    .to_string_lossy()
+                    .into_owned();
+                //forwarded exports point to a "module.function" string inside the export directory
+                let function = functions
+                    .get(ordinal as usize)
+                    .copied()
+                    .filter(|rva| !export_range.contains(rva));
+                (name, function)
+            })
+            .collect()
+    }
+}
+
+pub unsafe fn lib_exported_symbols(handle: Handle) -> Result<Vec<ExportedSymbol>, Error> {
+    unsafe {
+        let base = handle as *const u8;
+        let nt_headers = nt_headers(base).map_err(Error::LibraryInfoError)?;
+        Ok(named_exports(base, nt_headers)
+            .into_iter()
+            .filter_map(|(name, function)| {
+                Some(ExportedSymbol {
+                    name,
+                    addr: base.add(function? as usize) as *const (),
+                })
+            })
+            .collect())
+    }
+}
+
+//not exported by winapi
+#[repr(C)]
+#[allow(non_snake_case)]
+struct VS_FIXEDFILEINFO {
+    dwSignature: DWORD,
+    dwStrucVersion: DWORD,
+    dwFileVersionMS: DWORD,
+    dwFileVersionLS: DWORD,
+    dwProductVersionMS: DWORD,
+    dwProductVersionLS: DWORD,
+    dwFileFlagsMask: DWORD,
+    dwFileFlags: DWORD,
+    dwFileOS: DWORD,
+    dwFileType: DWORD,
+    dwFileSubtype: DWORD,
+    dwFileDateMS: DWORD,
+    dwFileDateLS: DWORD,
+}
+
+const VS_FFI_SIGNATURE: DWORD = 0xFEEF04BD;
+
+//Reads the fixed part of the version resource of the module file, which contains both versions.
+//Returns the file version, or the product version if `product` is set.
+pub unsafe fn lib_version(handle: Handle, product: bool) -> Result<Option<Version>, Error> {
+    unsafe {
+        let path = read_wide_path(|buffer| {
+            GetModuleFileNameW(handle, buffer.as_mut_ptr(), buffer.len() as DWORD)
+        })?
+        .ok_or_else(|| Error::LibraryInfoError(get_win_error()))?;
+        let wide_path: Vec<WCHAR> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let mut ignored = 0;
+        let size = GetFileVersionInfoSizeW(wide_path.as_ptr(), &mut ignored);
+        if size == 0 {
+            let error = get_win_error();
+            return match error.raw_os_error().map(|code| code as DWORD) {
+                Some(ERROR_RESOURCE_DATA_NOT_FOUND | ERROR_RESOURCE_TYPE_NOT_FOUND) => Ok(None),
+                _ => Err(Error::LibraryInfoError(error)),
+            };
+        }
+        let mut data = vec![0u8; size as usize];
+        if GetFileVersionInfoW(
+            wide_path.as_ptr(),
+            0,
+            size,
+            data.as_mut_ptr() as *mut c_void,
+        ) == 0
+        {
+            return Err(Error::LibraryInfoError(get_win_error()));
+        }
+        let root: Vec<WCHAR> = "\\".encode_utf16().chain(Some(0)).collect();
+        let mut info: *mut c_void = null_mut();
+        let mut len = 0;
+        if VerQueryValueW(
+            data.as_ptr() as *const c_void,
+            root.as_ptr(),
+            &mut info,
+            &mut len,
+        ) == 0
+            || (len as usize) < size_of::<VS_FIXEDFILEINFO>()
+        {
+            //the resource exists, but without the fixed part
+            return Ok(None);
+        }
+        let info = (info as *const VS_FIXEDFILEINFO).read_unaligned();
+        if info.dwSignature != VS_FFI_SIGNATURE {
+            return Ok(None);
+        }
+        Ok(Some(if product {
+            Version::from_parts(info.dwProductVersionMS, info.dwProductVersionLS)
+        } else {
+            Version::from_parts(info.dwFileVersionMS, info.dwFileVersionLS)
+        }))
+    }
+}
+
+//Reads the names of the functions imported from other modules. Functions imported
+//by ordinal have no name and are listed as "#<ordinal>".
+pub unsafe fn lib_undefined_symbols(handle: Handle) -> Result<Vec<String>, Error> {
+    const ORDINAL_FLAG: usize = 1 << (usize::BITS - 1);
+    unsafe {
+        let base = handle as *const u8;
+        let nt_headers = nt_headers(base).map_err(Error::LibraryInfoError)?;
+        let optional = &(*nt_headers).OptionalHeader;
+        if optional.NumberOfRvaAndSizes as usize <= IMAGE_DIRECTORY_ENTRY_IMPORT as usize {
+            return Ok(Vec::new());
+        }
+        let import_dir = optional.DataDirectory[IMAGE_DIRECTORY_ENTRY_IMPORT as usize];
+        if import_dir.VirtualAddress == 0 {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        let mut descriptor =
+            base.add(import_dir.VirtualAddress as usize) as *const IMAGE_IMPORT_DESCRIPTOR;
+        while (*descriptor).Name != 0 {
+            //the import address table is already bound, the lookup table keeps the names
+            let lookup = match *(*descriptor).u.OriginalFirstThunk() {
+                0 => (*descriptor).FirstThunk,
+                rva => rva,
+            };
+            let mut thunk = base.add(lookup as usize) as *const usize;
+            while *thunk != 0 {
+                if *thunk & ORDINAL_FLAG != 0 {
+                    names.push(format!("#{}", *thunk & 0xffff));
+                } else {
+                    //IMAGE_IMPORT_BY_NAME starts with a 16-bit hint
+                    let name = CStr::from_ptr(base.add(*thunk + 2) as *const c_char);
+                    names.push(name.to_string_lossy().into_owned());
+                }
+                thunk = thunk.add(1);
+            }
+            descriptor = descriptor.add(1);
+        }
+        Ok(names)
+    }
+}
+
+#[inline]
+pub unsafe fn find_lib(name: &OsStr) -> Option<Handle> {
+    unsafe { find_lib_in(name, &mut Vec::new()) }
+}
+
+#[inline]
+pub unsafe fn find_lib_in(name: &OsStr, wide_name: &mut Vec<NameChar>) -> Option<Handle> {
+    unsafe {
+        wide_name.clear();
+        wide_name.extend(name.encode_wide().chain(Some(0)));
+        //GetModuleHandleW does not increment the reference count
+        let handle = GetModuleHandleW(wide_name.as_ptr());
+        if handle.is_null() { None } else { Some(handle) }
+    }
+}
+
+//Finds the NT headers of a module mapped as an image at the given address.
+unsafe fn nt_headers(base: *const u8) -> Result<*const IMAGE_NT_HEADERS, IoError> {
+    unsafe {
+        let dos_header = base as *const IMAGE_DOS_HEADER;
+        if (*dos_header).e_magic != IMAGE_DOS_SIGNATURE {
+            return Err(IoError::other(
+                "The module does not start with a DOS header",
+            ));
+        }
+        let nt_headers = base.offset((*dos_header).e_lfanew as isize) as *const IMAGE_NT_HEADERS;
+        if (*nt_headers).Signature != IMAGE_NT_SIGNATURE {
+            return Err(IoError::other(
+                "The module does not contain valid NT headers",
+            ));
         }
+        Ok(nt_headers)
     }
 }
 
+//Checks if the module has an entry point or TLS callbacks, which run when it gets loaded.
+pub unsafe fn lib_has_initializers(handle: Handle) -> Result<bool, Error> {
+    unsafe {
+        //the handle of a module is the address it is mapped at
+        let nt_headers = nt_headers(handle as *const u8).map_err(Error::LibraryInfoError)?;
+        let optional = &(*nt_headers).OptionalHeader;
+        if optional.AddressOfEntryPoint != 0 {
+            return Ok(true);
+        }
+        if optional.NumberOfRvaAndSizes as usize <= IMAGE_DIRECTORY_ENTRY_TLS as usize {
+            return Ok(false);
+        }
+        let tls_dir = optional.DataDirectory[IMAGE_DIRECTORY_ENTRY_TLS as usize];
+        if tls_dir.VirtualAddress == 0 {
+            return Ok(false);
+        }
+        let tls = (handle as *const u8).add(tls_dir.VirtualAddress as usize)
+            as *const IMAGE_TLS_DIRECTORY;
+        //AddressOfCallBacks is already relocated and points to a null-terminated array
+        let callbacks = (*tls).AddressOfCallBacks as *const usize;
+        Ok(!callbacks.is_null() && *callbacks != 0)
+    }
+}
+
+pub unsafe fn lib_entry_point(handle: Handle) -> Result<Option<*const ()>, Error> {
+    unsafe {
+        //the handle of a module is the address it is mapped at
+        let nt_headers = nt_headers(handle as *const u8).map_err(Error::LibraryInfoError)?;
+        match (*nt_headers).OptionalHeader.AddressOfEntryPoint {
+            0 => Ok(None),
+            rva => Ok(Some((handle as *const u8).add(rva as usize) as *const ())),
+        }
+    }
+}
+
+//winapi does not provide this function
+#[link(name = "dbghelp")]
+unsafe extern "system" {
+    fn SymSetSearchPathW(hProcess: HANDLE, SearchPath: *const WCHAR) -> BOOL;
+}
+
 #[inline]
-pub unsafe fn addr_info_init() {
+pub unsafe fn addr_info_init(search_path: Option<&OsStr>) {
     unsafe {
+        let wide_path: Option<Vec<WCHAR>> =
+            search_path.map(|path| path.encode_wide().chain(Some(0)).collect());
+        let path_ptr = wide_path.as_ref().map_or(null(), |path| path.as_ptr());
+        let mut initialized = false;
         // calls to Sym* functions are not thread safe.
-        SYM_MUTEX.get_or_init(|| {
+        let mutex = SYM_MUTEX.get_or_init(|| {
             let process_handle = GetCurrentProcess();
-            let _result = SymInitializeW(process_handle, null_mut(), TRUE);
+            let _result = SymInitializeW(process_handle, path_ptr, TRUE);
+            initialized = true;
             Mutex::new(())
         });
+        //the symbol handler is initialized only once per process
+        if !initialized && wide_path.is_some() {
+            let _lock = mutex.lock().expect("Mutex got poisoned");
+            let _result = SymSetSearchPathW(GetCurrentProcess(), path_ptr);
+        }
     }
 }
 
 #[inline]
-pub unsafe fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
+pub unsafe fn addr_info_obtain(
+    addr: *const (),
+    demangle: Option<bool>,
+) -> Result<AddressInfo, Error> {
     unsafe {
         let process_handle = GetCurrentProcess();
 
@@ -195,9 +776,19 @@ pub unsafe fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
             u8,
             { size_of::<SYMBOL_INFOW>() + MAX_SYMBOL_LEN * size_of::<WCHAR>() },
         >();
-        let (module_base, path_len, symbol_info, result) = {
+        let (module_base, path_len, symbol_info, displacement, result) = {
             // calls to Sym* functions are not thread safe.
             let mut _lock = SYM_MUTEX.get().unwrap().lock().expect("Mutex got poisoned");
+            //symbol options are global, so they are only changed for the duration of the call
+            let previous_options = demangle.map(|demangle| {
+                let options = SymGetOptions();
+                SymSetOptions(if demangle {
+                    options | SYMOPT_UNDNAME
+                } else {
+                    options & !SYMOPT_UNDNAME
+                });
+                options
+            });
             let module_base = SymGetModuleBase64(process_handle, addr as u64);
 
             if module_base == 0 {
@@ -220,9 +811,17 @@ pub unsafe fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
                 &mut displacement,
                 symbol_info,
             );
-            (module_base, path_len, symbol_info, result)
+            if let Some(options) = previous_options {
+                SymSetOptions(options);
+            }
+            (module_base, path_len, symbol_info, displacement, result)
         };
 
+        let offset_from_symbol = if result == TRUE {
+            displacement as usize
+        } else {
+            0
+        };
         let os = if result == TRUE {
             let name_len = (*symbol_info).NameLen as usize;
             let name_slice = slice::from_raw_parts((*symbol_info).Name.as_ptr(), name_len);
@@ -237,7 +836,8 @@ pub unsafe fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
             };
             Some(OverlappingSymbol {
                 name,
-                addr, // on Windows there is no overlappping, just a straight match
+                //the symbol starts before the queried address by the displacement
+                addr: (addr as usize).wrapping_sub(offset_from_symbol) as *const (),
             })
         } else {
             None
@@ -251,6 +851,7 @@ pub unsafe fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
                 .into_owned(),
                 dll_base_addr: module_base as *const (),
                 overlapping_symbol: os,
+                offset_from_symbol,
             }
         })
     }
@@ -259,13 +860,55 @@ pub unsafe fn addr_info_obtain(addr: *const ()) -> Result<AddressInfo, Error> {
 #[inline]
 pub unsafe fn addr_info_cleanup() {}
 
+pub fn addr_protection(addr: *const ()) -> Result<MemoryProtection, Error> {
+    let mut info = MaybeUninit::<MEMORY_BASIC_INFORMATION>::uninit();
+    let written = unsafe {
+        VirtualQuery(
+            addr as *const c_void,
+            info.as_mut_ptr(),
+            size_of::<MEMORY_BASIC_INFORMATION>(),
+        )
+    };
+    if written == 0 {
+        return Err(Error::MemoryQueryError(unsafe { get_win_error() }));
+    }
+    let info = unsafe { info.assume_init() };
+    if info.State != MEM_COMMIT {
+        return Err(Error::MemoryQueryError(IoError::new(
+            ErrorKind::NotFound,
+            "The address is not mapped",
+        )));
+    }
+    //the upper bits contain modifiers like PAGE_GUARD
+    let (readable, writable, executable) = match info.Protect & 0xff {
+        PAGE_READONLY => (true, false, false),
+        PAGE_READWRITE | PAGE_WRITECOPY => (true, true, false),
+        PAGE_EXECUTE => (false, false, true),
+        PAGE_EXECUTE_READ => (true, false, true),
+        PAGE_EXECUTE_READWRITE | PAGE_EXECUTE_WRITECOPY => (true, true, true),
+        _ => (false, false, false),
+    };
+    Ok(MemoryProtection {
+        readable,
+        writable,
+        executable,
+    })
+}
+
 #[inline]
 pub fn close_lib(handle: Handle) -> Handle {
-    if unsafe { FreeLibrary(handle) } == 0 {
+    if let Err(err) = try_close_lib(handle) {
         //this should not happen
-        panic!("FreeLibrary() failed, the error is {}", unsafe {
-            get_win_error()
-        });
+        panic!("FreeLibrary() failed, the error is {err}");
     }
     null_mut()
 }
+
+#[inline]
+pub fn try_close_lib(handle: Handle) -> Result<(), Error> {
+    if unsafe { FreeLibrary(handle) } != 0 {
+        Ok(())
+    } else {
+        Err(Error::ClosingLibraryError(unsafe { get_win_error() }))
+    }
+}
@@ -47,13 +47,20 @@ fn main(){
 Please notice several supported features:
 
 * By default `SymBorApi` uses the field name to obtain a symbol from the library.
-  You can override the symbol name using the `dlopen2_name` attribute.
+  You can override the symbol name using the `dlopen2_name` attribute. Its value can be
+  a string literal, a `concat!(...)` invocation or, using the `dlopen2_name(NAME)` form,
+  a path to a `const &str`.
+* Symbol names can be transformed before the lookup using the `dlopen2_mangle` attribute
+  of the structure. It names a function `fn(&str) -> String` that is applied to every name.
 * All kind of objects from the `symbor` module implement the Deref or DerefMut trait.
   This means that you can use them as if you would use primitive types that they wrap.
 * You can obtain optional symbols. This is very useful when you are dealing with
   different versions of libraries and the new versions support more functions.
   If it is not possible to obtain the given symbol, the option is set to `None',
   otherwise it contains the obtained symbol.
+* Applying the `dlopen2_all_optional` attribute (from this module) to the structure before
+  the derive wraps every field that is not optional yet in `Option`, which is convenient
+  for best-effort loading from libraries of varying completeness.
 * Both `Symbol` and `Ref` or `RefMut` can be used to obtain references to statically
   allocated objects. But `Ref` and `RefMut` are just easier to use - they require
   less dereferences to access the final value.
@@ -80,6 +80,21 @@ where
         }
     }
 
+    /// Load all symbols from an already opened library.
+    ///
+    /// This allows using a library that was opened in a custom way (e.g. with specific flags).
+    pub unsafe fn from_raw_library(lib: raw::Library) -> Result<Self, Error> {
+        unsafe {
+            let lib = Library::from_raw(lib);
+            //this is cheating of course
+            //but it is safe because Library and api is placed in the same structure
+            //and therefore it is released at the same time.
+            let static_ref: &'static Library = transmute(&lib);
+            let api = T::load(static_ref)?;
+            Ok(Self { api, lib })
+        }
+    }
+
     /**
     Returns the raw OS handle for the opened library.
 
@@ -1,5 +1,6 @@
 use super::super::err::Error;
 use super::ptr_or_null::PtrOrNull;
+use std::borrow::Cow;
 pub type RawResult<'a> = Result<PtrOrNull<'a, ()>, Error>;
 
 /// Allows conversion of raw symbol result into the given symbol.
@@ -11,6 +12,7 @@ pub type RawResult<'a> = Result<PtrOrNull<'a, ()>, Error>;
 /// **Note:** `Option<T> where T: FromRawResult` also implements `FromRawResult`.
 /// This allows you to use options in structures implementing `SymBorApi`. If
 /// the symbol is found, the variable contains `Some(symbol)`, otherwise `None`.
+/// With the `strict` feature enabled, `Error::NullSymbol` is returned as an error.
 ///
 /// **Note:** You probably won't need to use it directly.
 pub trait FromRawResult
@@ -18,4 +20,15 @@ where
     Self: Sized,
 {
     unsafe fn from_raw_result(raw: RawResult) -> Result<Self, Error>;
+
+    /// Same as `from_raw_result()`, but also receives the name the symbol was looked up by
+    /// (after mangling). Used by the derive macro, types that can record the name
+    /// (like `NamedSymbol`) store it, the default implementation ignores it.
+    unsafe fn from_named_raw_result(
+        raw: RawResult,
+        name: Cow<'static, str>,
+    ) -> Result<Self, Error> {
+        let _ = name;
+        unsafe { Self::from_raw_result(raw) }
+    }
 }
@@ -46,14 +46,28 @@ pub struct Library {
 }
 
 impl Library {
+    pub(crate) fn from_raw(mut lib: RawLib) -> Library {
+        //symbols obtained through this library borrow it and cannot dangle
+        lib.set_managed();
+        Library { lib }
+    }
+
     /// Open dynamic link library using provided file name or path.
     pub fn open<S>(name: S) -> Result<Library, Error>
     where
         S: AsRef<OsStr>,
     {
-        Ok(Library {
-            lib: RawLib::open(name)?,
-        })
+        Ok(Library::from_raw(RawLib::open(name)?))
+    }
+
+    /// Open dynamic link library resolving all its relocations immediately.
+    ///
+    /// See `raw::Library::open_now()` for details.
+    pub fn open_now<S>(name: S) -> Result<Library, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        Ok(Library::from_raw(RawLib::open_now(name)?))
     }
 
     /// Open the program itself as library.
@@ -61,9 +75,7 @@ impl Library {
     /// This allows a shared library to load symbols of the program it was
     /// loaded into.
     pub fn open_self() -> Result<Library, Error> {
-        Ok(Library {
-            lib: RawLib::open_self()?,
-        })
+        Ok(Library::from_raw(RawLib::open_self()?))
     }
 
     /// Obtain a symbol from library.
@@ -167,5 +179,21 @@ impl Library {
     }
 }
 
+/// Wraps an already opened raw library, so that its symbols can be obtained without reopening it.
+impl From<RawLib> for Library {
+    fn from(lib: RawLib) -> Library {
+        Library::from_raw(lib)
+    }
+}
+
+/// Unwraps the raw library. Symbols obtained from it afterwards are no longer tied to its lifetime.
+impl From<Library> for RawLib {
+    fn from(lib: Library) -> RawLib {
+        let mut lib = lib.lib;
+        lib.set_unmanaged();
+        lib
+    }
+}
+
 unsafe impl Send for Library {}
 unsafe impl Sync for Library {}
@@ -57,6 +57,8 @@ This is very useful when you are dealing with
     different versions of libraries and the newer versions support more functions.
     If it is not possible to obtain the given symbol, the option is set to `None',
     otherwise it contains the obtained symbol.
+    With the `strict` feature enabled, a symbol with a null value is reported
+    as `Error::NullSymbol` instead of giving `None`.
 
 Unfortunately in Rust it is not possible to create an API for dynamic link libraries that would
 be 100% safe. This API aims to be 99% safe by providing zero cost wrappers around raw symbols.
@@ -89,7 +91,9 @@ mod api;
 mod container;
 mod from_raw;
 mod library;
+mod named_symbol;
 mod option;
+mod owned;
 mod ptr_or_null;
 mod ptr_or_null_mut;
 mod reference;
@@ -100,6 +104,8 @@ pub use self::api::SymBorApi;
 pub use self::container::Container;
 pub use self::from_raw::FromRawResult;
 pub use self::library::Library;
+pub use self::named_symbol::NamedSymbol;
+pub use self::owned::OwnedSymbol;
 pub use self::ptr_or_null::PtrOrNull;
 pub use self::ptr_or_null_mut::PtrOrNullMut;
 pub use self::reference::Ref;
@@ -107,4 +113,4 @@ pub use self::reference_mut::RefMut;
 pub use self::symbol::Symbol;
 
 #[cfg(feature = "derive")]
-pub use dlopen2_derive::SymBorApi;
+pub use dlopen2_derive::{SymBorApi, dlopen2_all_optional};
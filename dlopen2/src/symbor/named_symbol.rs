@@ -0,0 +1,78 @@
+use super::super::err::Error;
+use super::from_raw::{FromRawResult, RawResult};
+use super::symbol::Symbol;
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+
+/// `Symbol` that also remembers its name.
+///
+/// Symbols of this type loaded by the `SymBorApi` derive macro record the name they were
+/// resolved by, which is available through `name()` for diagnostics. Plain `Symbol` does not
+/// store the name and stays as small as the wrapped symbol.
+#[derive(Debug, Clone)]
+pub struct NamedSymbol<'lib, T: 'lib> {
+    symbol: Symbol<'lib, T>,
+    name: Option<Cow<'static, str>>,
+}
+
+impl<'lib, T> NamedSymbol<'lib, T> {
+    pub fn new<N>(symbol: Symbol<'lib, T>, name: N) -> NamedSymbol<'lib, T>
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        NamedSymbol {
+            symbol,
+            name: Some(name.into()),
+        }
+    }
+
+    /// Returns the name of the symbol if it is known.
+    ///
+    /// The name is known for symbols loaded by the `SymBorApi` derive macro (the name that was
+    /// looked up, after mangling) and for symbols created by `new()` or `Symbol::with_name()`.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Drops the name and returns the symbol.
+    pub fn into_symbol(self) -> Symbol<'lib, T> {
+        self.symbol
+    }
+}
+
+impl<'lib, T> FromRawResult for NamedSymbol<'lib, T> {
+    unsafe fn from_raw_result(raw_result: RawResult) -> Result<Self, Error> {
+        unsafe {
+            Symbol::from_raw_result(raw_result).map(|symbol| NamedSymbol { symbol, name: None })
+        }
+    }
+
+    unsafe fn from_named_raw_result(
+        raw_result: RawResult,
+        name: Cow<'static, str>,
+    ) -> Result<Self, Error> {
+        unsafe { Symbol::from_raw_result(raw_result).map(|symbol| NamedSymbol::new(symbol, name)) }
+    }
+}
+
+impl<'lib, T> Deref for NamedSymbol<'lib, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.symbol
+    }
+}
+
+impl<'lib, T> DerefMut for NamedSymbol<'lib, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.symbol
+    }
+}
+
+/// Named symbols are compared like `Symbol`, by their addresses, the names are ignored.
+impl<'lib, T> PartialEq for NamedSymbol<'lib, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol == other.symbol
+    }
+}
+
+impl<'lib, T> Eq for NamedSymbol<'lib, T> {}
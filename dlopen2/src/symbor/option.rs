@@ -1,5 +1,7 @@
+use super::super::STRICT;
 use super::super::err::Error;
 use super::from_raw::{FromRawResult, RawResult};
+use std::borrow::Cow;
 
 impl<T> FromRawResult for Option<T>
 where
@@ -9,6 +11,20 @@ where
         unsafe {
             match T::from_raw_result(raw_result) {
                 Ok(val) => Ok(Some(val)),
+                Err(Error::NullSymbol) if STRICT => Err(Error::NullSymbol),
+                Err(_) => Ok(None),
+            }
+        }
+    }
+
+    unsafe fn from_named_raw_result(
+        raw_result: RawResult,
+        name: Cow<'static, str>,
+    ) -> Result<Option<T>, Error> {
+        unsafe {
+            match T::from_named_raw_result(raw_result, name) {
+                Ok(val) => Ok(Some(val)),
+                Err(Error::NullSymbol) if STRICT => Err(Error::NullSymbol),
                 Err(_) => Ok(None),
             }
         }
@@ -0,0 +1,64 @@
+use super::super::err::Error;
+use super::library::Library;
+use std::ffi::{CStr, CString};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/**
+Symbol that keeps the library it was obtained from loaded.
+
+Unlike `Symbol`, which borrows the library, `OwnedSymbol` holds a reference counted
+handle to it. It has no lifetime and can therefore be stored in structures or passed around
+independently - the library stays loaded until the last `OwnedSymbol` and `Arc` pointing to it
+are dropped. Like `Symbol` it doesn't accept null values.
+
+# Example
+
+```no_run
+use dlopen2::symbor::{Library, OwnedSymbol};
+use std::sync::Arc;
+
+struct Plugin {
+    run: OwnedSymbol<unsafe extern "C" fn()>,
+}
+
+fn main() {
+    let lib = Arc::new(Library::open("libexample.so").unwrap());
+    let plugin = Plugin {
+        run: unsafe { OwnedSymbol::new(lib, "run") }.unwrap(),
+    };
+    unsafe { (plugin.run)() };
+}
+```
+*/
+#[derive(Clone)]
+pub struct OwnedSymbol<T: Copy> {
+    symbol: T,
+    lib: Arc<Library>,
+}
+
+impl<T: Copy> OwnedSymbol<T> {
+    /// Obtains the symbol with the given name from the library.
+    pub unsafe fn new(lib: Arc<Library>, name: &str) -> Result<OwnedSymbol<T>, Error> {
+        let cname = CString::new(name)?;
+        unsafe { Self::new_cstr(lib, cname.as_ref()) }
+    }
+
+    /// Equivalent of the `new()` method but takes `CStr` as a argument.
+    pub unsafe fn new_cstr(lib: Arc<Library>, name: &CStr) -> Result<OwnedSymbol<T>, Error> {
+        let symbol = *unsafe { lib.symbol_cstr::<T>(name) }?;
+        Ok(OwnedSymbol { symbol, lib })
+    }
+
+    /// Returns the library the symbol was obtained from.
+    pub fn library(&self) -> &Arc<Library> {
+        &self.lib
+    }
+}
+
+impl<T: Copy> Deref for OwnedSymbol<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.symbol
+    }
+}
@@ -19,6 +19,28 @@ impl<'lib, T> PtrOrNullMut<'lib, T> {
             pd: PhantomData,
         }
     }
+
+    /// Calculates the offset from the pointer, keeping the result bound to the library.
+    ///
+    /// # Safety
+    ///
+    /// The same rules as for
+    /// [`pointer::offset`](https://doc.rust-lang.org/std/primitive.pointer.html#method.offset)
+    /// apply: the pointer must not be null and both the starting and the resulting pointer must
+    /// be in bounds of the same allocated object (e.g. an array exported by the library).
+    pub unsafe fn offset(&self, count: isize) -> PtrOrNullMut<'lib, T> {
+        Self::new(unsafe { self.pointer.offset(count) })
+    }
+
+    /// Calculates the offset from the pointer (`count` elements forward), keeping the result
+    /// bound to the library.
+    ///
+    /// # Safety
+    ///
+    /// The same rules as for `offset()` apply.
+    pub unsafe fn add(&self, count: usize) -> PtrOrNullMut<'lib, T> {
+        Self::new(unsafe { self.pointer.add(count) })
+    }
 }
 
 impl<'lib, T> FromRawResult for PtrOrNullMut<'lib, T> {
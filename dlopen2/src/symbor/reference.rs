@@ -2,6 +2,7 @@ use super::super::err::Error;
 use super::from_raw::{FromRawResult, RawResult};
 
 use std::ops::Deref;
+use std::ptr;
 
 /// Safe wrapper around cont reference.
 ///
@@ -16,6 +17,14 @@ impl<'lib, T> Ref<'lib, T> {
     pub fn new(reference: &'lib T) -> Ref<'lib, T> {
         Ref { reference }
     }
+
+    /// Returns the wrapped reference with the lifetime of the library.
+    ///
+    /// Unlike dereferencing, the returned reference doesn't borrow the `Ref` itself,
+    /// so it can be kept after the `Ref` is gone - but, like the `Ref`, not after the library.
+    pub fn get(&self) -> &'lib T {
+        self.reference
+    }
 }
 
 impl<'lib, T> FromRawResult for Ref<'lib, T> {
@@ -44,5 +53,14 @@ impl<'lib, T> Deref for Ref<'lib, T> {
     }
 }
 
+/// References are compared by the addresses they point to, not by the values.
+impl<'lib, T> PartialEq for Ref<'lib, T> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.reference, other.reference)
+    }
+}
+
+impl<'lib, T> Eq for Ref<'lib, T> {}
+
 unsafe impl<'lib, T: Send> Send for Ref<'lib, T> {}
 unsafe impl<'lib, T: Sync> Sync for Ref<'lib, T> {}
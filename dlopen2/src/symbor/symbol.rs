@@ -1,14 +1,19 @@
 use super::super::err::Error;
 use super::from_raw::{FromRawResult, RawResult};
+use super::named_symbol::NamedSymbol;
+use std::borrow::Cow;
 use std::marker::PhantomData;
-use std::mem::transmute_copy;
+use std::mem::{size_of, transmute_copy};
 use std::ops::{Deref, DerefMut};
+use std::ptr;
 
 /// Safe wrapper around a symbol obtained from `Library`.
 ///
 /// This is the most generic type, valid for obtaining functions, references and pointers.
 /// It does not accept null value of the library symbol. Other types may provide
 /// more specialized functionality better for some use cases.
+///
+/// Use `NamedSymbol` if the symbol should also remember its name.
 #[derive(Debug, Clone, Copy)]
 pub struct Symbol<'lib, T: 'lib> {
     symbol: T,
@@ -22,6 +27,14 @@ impl<'lib, T> Symbol<'lib, T> {
             pd: PhantomData,
         }
     }
+
+    /// Attaches a name to the symbol, see `NamedSymbol`.
+    pub fn with_name<N>(self, name: N) -> NamedSymbol<'lib, T>
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        NamedSymbol::new(self, name)
+    }
 }
 
 impl<'lib, T> FromRawResult for Symbol<'lib, T> {
@@ -33,10 +46,7 @@ impl<'lib, T> FromRawResult for Symbol<'lib, T> {
                         Err(Error::NullSymbol)
                     } else {
                         let raw: *const () = *ptr;
-                        Ok(Symbol {
-                            symbol: transmute_copy(&raw),
-                            pd: PhantomData,
-                        })
+                        Ok(Symbol::new(transmute_copy(&raw)))
                     }
                 }
                 Err(err) => Err(err),
@@ -59,5 +69,23 @@ impl<'lib, T> DerefMut for Symbol<'lib, T> {
     }
 }
 
+/// Symbols are compared by their addresses.
+///
+/// Symbols obtained from a library always have the size of a pointer. Symbols of other sizes,
+/// which can only be created with `new()`, have no address and are equal only to themselves.
+impl<'lib, T> PartialEq for Symbol<'lib, T> {
+    fn eq(&self, other: &Self) -> bool {
+        //this comparison should be calculated by compiler at compilation time - zero cost
+        if size_of::<T>() != size_of::<*const ()>() {
+            return ptr::eq(self, other);
+        }
+        let addr: *const () = unsafe { transmute_copy(&self.symbol) };
+        let other_addr: *const () = unsafe { transmute_copy(&other.symbol) };
+        addr == other_addr
+    }
+}
+
+impl<'lib, T> Eq for Symbol<'lib, T> {}
+
 unsafe impl<'lib, T: Send> Send for Symbol<'lib, T> {}
 unsafe impl<'lib, T: Sync> Sync for Symbol<'lib, T> {}
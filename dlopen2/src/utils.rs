@@ -6,6 +6,11 @@ use std::{
     ffi::{OsStr, OsString},
     mem::MaybeUninit,
 };
+#[cfg(feature = "testing")]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 //library naming patterns
 /* Naming pattern goes as follows:
@@ -56,6 +61,78 @@ where
     result
 }
 
+/// Recursively searches `dir` for a dynamic link library with the given core name.
+///
+/// Besides the exact platform-specific file name (see `platform_file_name`), files with
+/// a hash appended to the core name, like "libexample-0123abcd.so", match too. This is how
+/// Cargo names libraries in its `target/*/deps` directories. If several files match,
+/// the most recently modified one is returned. Symbolic links to directories are not followed.
+///
+/// This is mostly useful for test suites that need to locate a library built by Cargo.
+#[cfg(feature = "testing")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "testing")))]
+pub fn find_library<P, S>(dir: P, base_name: S) -> Option<PathBuf>
+where
+    P: AsRef<Path>,
+    S: AsRef<str>,
+{
+    let mut found = None;
+    find_library_in(dir.as_ref(), base_name.as_ref(), &mut found);
+    found.map(|(path, _)| path)
+}
+
+#[cfg(feature = "testing")]
+fn find_library_in(
+    dir: &Path,
+    base_name: &str,
+    found: &mut Option<(PathBuf, std::time::SystemTime)>,
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        //the file type of the entry itself, so symlinked directories (which may form cycles)
+        //are not followed
+        if entry.file_type().is_ok_and(|ty| ty.is_dir()) {
+            find_library_in(&path, base_name, found);
+            continue;
+        }
+        let matches = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_some_and(|name| is_library_name(name, base_name));
+        if !matches {
+            continue;
+        }
+        let modified = match entry.metadata().and_then(|meta| meta.modified()) {
+            Ok(modified) => modified,
+            Err(_) => continue,
+        };
+        if found.as_ref().is_none_or(|(_, newest)| modified > *newest) {
+            *found = Some((path, modified));
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+fn is_library_name(file_name: &str, base_name: &str) -> bool {
+    let stem = match file_name
+        .strip_prefix(PLATFORM_FILE_PREFIX)
+        .and_then(|name| name.strip_suffix(PLATFORM_FILE_EXTENSION))
+        .and_then(|name| name.strip_suffix('.'))
+        .and_then(|name| name.strip_prefix(base_name))
+    {
+        Some(stem) => stem,
+        None => return false,
+    };
+    match stem.strip_prefix('-') {
+        None => stem.is_empty(),
+        Some(hash) => !hash.is_empty() && hash.chars().all(|c| c.is_ascii_alphanumeric()),
+    }
+}
+
 pub const unsafe fn maybe_uninit_slice_assume_init_ref<T>(slice: &[MaybeUninit<T>]) -> &[T] {
     unsafe { &*(slice as *const [MaybeUninit<T>] as *const [T]) }
 }
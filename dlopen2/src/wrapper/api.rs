@@ -1,5 +1,5 @@
 use super::super::err::Error;
-use super::super::raw::Library;
+use super::super::raw::{Library, SymbolSource};
 
 /**
 Trait for defining library API.
@@ -9,7 +9,7 @@ This trait is intended to be used with `#[derive(WrapperApi)]` macro defined in
 
 * Only structures can implement this trait.
 * All fields need to be private.
-* Only functions, references and pointers are allowed.
+* Only functions, references, pointers and addresses (`usize`) are allowed.
 * You can't define a type using `type Fun =fn();` and use it in the structure. This is a limitation
   of the Rust reflection mechanism. Only raw functions, references and pointers are allowed.
 * All arguments of functions need to be named.
@@ -20,6 +20,29 @@ safe wrappers around the loaded symbols. These wrappers are named exactly like t
 they wrap. Wrappers of functions have the same arguments like original functions and wrappers of
 references are just simple accessors in the form of `<field_name>(&self) -> &FieldType` or
 `<field_name>_mut(&mut self) -> &mut FieldType`.
+Fields of type `usize` contain the address of the symbol and get an accessor in the form
+of `<field_name>(&self) -> usize`.
+The references returned by accessors (including `Option<&FieldType>` ones) borrow the structure
+instead of having the lifetime parameter of the structure, which `Container` does not tie
+to the library. A reference obtained from a `Container` therefore can't outlive it and the
+library it keeps loaded. This is also why fields need to be private: copying the reference out
+of a field would not be bound to the structure.
+Wrappers of optional functions return `Option<ReturnType>`, which is `None` if the function
+could not be loaded. If the function returns an `Option` itself, assign the "dlopen2_result"
+attribute to the field: its wrapper then returns `Result<ReturnType, Error>` with
+`Error::SymbolNotLoaded` for a missing function.
+Wrappers of Rust-ABI functions (`fn`, not `extern "C" fn`) marked with the "dlopen2_catch_unwind"
+attribute call the function inside `catch_unwind` and return `std::thread::Result<ReturnType>`.
+Assigning the attribute to the structure applies it to all its required Rust-ABI functions.
+Note that only panics raised by the same copy of the standard library can be caught:
+a panic unwinding out of a `cdylib`, which contains its own copy, aborts the process.
+Mutable references wrapped in a `Mutex` (`Mutex<&'a mut FieldType>`) and marked with the
+"dlopen2_sync" attribute get a single accessor in the form of
+`<field_name>(&self) -> SyncGuard<FieldType>` instead, which serializes all accesses to the field
+using the lock of the structure.
+Fields marked with the "dlopen2_flatten" attribute contain another structure implementing
+`WrapperApi` that gets loaded from the same library. They get an accessor in the form of
+`as_<field_name>(&self) -> &FieldType`, which allows passing a narrower view of the API around.
 Wrappers are not generated only for:
 
 * Pointers - there is no safe way of preventing dangling symbols if a user has a direct access to
@@ -66,19 +89,153 @@ fn main () {
 **Note**: `WrapperApi` should only be used together with `Container` structure, never to create
 a standalone object. API and library handle need to be kept together to prevent dangling symbols.
 
+**Note:** Functions can use any ABI, including unwinding ones like `extern "C-unwind"`.
+Generated wrappers call the function through the field, so its ABI is preserved.
+
 **Note:** By default obtained symbol name is the field name. You can change this by
-assigning the "dlopen2_name" attribute to the given field.
+assigning the "dlopen2_name" attribute to the given field. Its value can be a string literal,
+a `concat!(...)` invocation or, using the `dlopen2_name(NAME)` form, a path to a `const &str`.
+Names are converted to C strings at compile time, so a path to a value that is not constant
+or a name containing a null character fails to compile.
+
+**Note:** Symbol names can be transformed before the lookup by assigning the
+"dlopen2_mangle" attribute to the structure. Its value is a path to a function
+`fn(&str) -> String` that is applied to the name of every field
+(e.g. `#[dlopen2_mangle = "my_mangler"]`). By default names are not mangled.
 
 **Note:** By default `Error::NullSymbol` is returned if the loaded symbol name has a null value.
 While null is a valid value of a exported symbol, it is usually not expected by users of libraries.
 If in your scenario null is an acceptable value, you should assign
 "dlopen2_allow_null" attribute to the given field. Of course this makes sense only if the field
 is of pointer type.
+
+**Note:** Optional fields (`Option<...>` and fields with a fallback) normally treat a null symbol
+like a missing one. With the `strict` feature of this crate enabled, a null symbol always makes
+loading fail with `Error::NullSymbol` instead, so that unexpected nulls are not hidden.
+Fields marked with "dlopen2_allow_null" are not affected.
+
+**Note:** A function that is not present in every version of the library can be given a
+replacement by assigning the "dlopen2_fallback" attribute to the field
+(e.g. `#[dlopen2_fallback = "my_stub"]`). Its value is a path to a function of the field's type
+that gets stored instead if the symbol is missing, so that it doesn't need to be optional.
+
+**Note:** Loading can be made to fail if the library exports certain symbols by assigning the
+"dlopen2_forbid" attribute to the structure (e.g. `#[dlopen2_forbid("debug_backdoor")]`).
+If any of the listed symbols is exported by the library itself, `Error::ForbiddenSymbol`
+is returned. On Linux (glibc) and Windows the export table of the library is checked,
+on other platforms symbols of its dependencies are found too. The names are not mangled.
+
+**Note:** Tuples of up to 8 structures implementing `WrapperApi` implement it too. This allows
+loading several independent APIs from a library that gets opened only once
+(e.g. `Container<(CoreApi, ExtensionApi)>`). Loading fails if any of the APIs fails to load.
+
+**Note:** The derive also implements `required_symbols()` and `optional_symbols()`, which list
+the names of the symbols the structure loads, so that they can be compared against the exports
+of a library before loading it.
 */
 pub trait WrapperApi
 where
     Self: Sized,
 {
     /// Load symbols from provided library.
-    unsafe fn load(lib: &Library) -> Result<Self, Error>;
+    ///
+    /// Besides a `Library`, any other `SymbolSource` (e.g. a mock in tests) can be loaded from.
+    unsafe fn load(lib: &impl SymbolSource) -> Result<Self, Error>;
+
+    /**
+    Checks, without loading anything, the conditions that `load()` can verify on its own.
+
+    The derive macro implements it to check that:
+
+    * the type every field gets loaded as has the size of a pointer, otherwise
+      `Error::SymbolSizeMismatch` with the name of the symbol is returned (`load()` panics then),
+    * all required symbols are present and not null (`Error::SymbolGettingError` otherwise).
+      Fields marked with "dlopen2_allow_null" are not checked as null is acceptable for them.
+
+    Whether the types match the actual symbols (e.g. the signatures and ABIs of functions)
+    can't be checked, which is why loading stays unsafe. Flattened structures get checked too,
+    optional symbols only for their size. The default implementation does not check anything.
+    */
+    fn check(_lib: &Library) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /**
+    Checks the signatures of the functions against the API manifest exported by the library
+    with `export_api!`.
+
+    The derive macro implements it: every function field (including `Option<...>` ones) needs
+    to be listed in the manifest with the same signature, otherwise `Error::SignatureMismatch`
+    with the name of the function is returned. Optional functions may be missing from
+    the manifest. The types are compared as written, so both sides need to spell them
+    the same way (e.g. `c_int` and not `std::os::raw::c_int` on one of them).
+    Flattened structures get checked too. The default implementation does not check anything.
+    */
+    fn check_manifest(_lib: &Library) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /**
+    Same as `check_manifest()`, but all functions (and the manifest itself) may be missing.
+
+    This is used for APIs that may be missing as a whole, e.g. `Option<...>`: functions listed
+    in the manifest still need to have the same signature. The derive macro implements it,
+    the default implementation does not check anything.
+    */
+    fn check_manifest_optional(_lib: &Library) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Same as `load()`, but performs `check()` first, so that problems it detects
+    /// are reported as errors before any symbol is loaded.
+    unsafe fn try_load_checked(lib: &Library) -> Result<Self, Error> {
+        Self::check(lib)?;
+        unsafe { Self::load(lib) }
+    }
+
+    /// Checks that the symbols still resolve to the addresses they were loaded from.
+    ///
+    /// Returns `Error::SymbolAddressMismatch` with the name of the first symbol that doesn't.
+    /// The derive macro implements it, the default implementation does not check anything.
+    fn verify(&self, _lib: &Library) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /**
+    Names of the symbols that must be present in the library for loading to succeed.
+
+    The derive macro fills it in at compile time for structures that don't use
+    `#[dlopen2_mangle]` or `#[dlopen2_flatten]`: the names are the ones symbols get resolved by,
+    each listed once. Other structures, tuples and `Option<...>` keep the empty default and only
+    implement `required_symbols()`.
+    */
+    const REQUIRED_SYMBOLS: &'static [&'static str] = &[];
+
+    /// Names of the symbols that get loaded only if the library provides them
+    /// (`Option<...>` fields and fields with a fallback), in the same way as `REQUIRED_SYMBOLS`.
+    /// Symbols that are also required are not listed.
+    const OPTIONAL_SYMBOLS: &'static [&'static str] = &[];
+
+    /**
+    Lists the names of the symbols that must be present in the library for loading to succeed.
+
+    Unlike `REQUIRED_SYMBOLS` this also works for all APIs: the derive macro implements it for
+    structures that mangle names (listing the names after mangling) or flatten other structures
+    (including their symbols), the default implementation returns `REQUIRED_SYMBOLS`.
+    */
+    fn required_symbols() -> Vec<String> {
+        Self::REQUIRED_SYMBOLS
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Lists the names of the symbols that get loaded only if the library provides them,
+    /// in the same way as `required_symbols()`. Symbols that are also required are not listed.
+    fn optional_symbols() -> Vec<String> {
+        Self::OPTIONAL_SYMBOLS
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
 }
@@ -3,8 +3,12 @@ use crate::raw;
 use super::super::Error;
 use super::super::raw::Library;
 use super::api::WrapperApi;
+use super::mapped::MappedContainer;
+use super::teardown::Teardown;
 use std::ffi::OsStr;
 use std::ops::{Deref, DerefMut};
+#[cfg(feature = "metrics")]
+use {super::metrics::LoadMetrics, std::time::Instant};
 
 /**
 Container for both a dynamic load library handle and its API.
@@ -48,6 +52,8 @@ pub struct Container<T>
 where
     T: WrapperApi,
 {
+    //declared before the library so that the teardown function runs before the library is closed
+    teardown: Teardown,
     #[allow(dead_code)]
     //this is not dead code because destructor of Library deallocates the library
     lib: Library,
@@ -64,20 +70,128 @@ where
         S: AsRef<OsStr>,
     {
         unsafe {
-            let lib = Library::open(name)?;
+            let mut lib = Library::open(name)?;
+            lib.set_managed();
             let api = T::load(&lib)?;
-            Ok(Self { lib, api })
+            Ok(Self {
+                teardown: Teardown::default(),
+                lib,
+                api,
+            })
         }
     }
+    /**
+    Open the library, load all symbols and call the initialization function of the library.
+
+    The function named `init_name` gets obtained as `F` and passed to `call`, which invokes it
+    with the desired arguments. Both the container and the result of the call are returned.
+    The function is called only if all symbols of the API were loaded successfully.
+
+    # Example
+
+    ```no_run
+    use dlopen2::wrapper::{Container, WrapperApi};
+    use std::os::raw::c_void;
+
+    #[derive(WrapperApi)]
+    struct Plugin {
+        plugin_run: unsafe extern "C" fn(state: *mut c_void),
+    }
+
+    fn main() {
+        let host_api: *const c_void = std::ptr::null();
+        let (plugin, state): (Container<Plugin>, *mut c_void) = unsafe {
+            Container::load_and_init(
+                "libplugin.so",
+                "plugin_init",
+                |init: unsafe extern "C" fn(*const c_void) -> *mut c_void| init(host_api),
+            )
+        }
+        .unwrap();
+        unsafe { plugin.plugin_run(state) };
+    }
+    ```
+    */
+    pub unsafe fn load_and_init<S, F, R>(
+        name: S,
+        init_name: &str,
+        call: impl FnOnce(F) -> R,
+    ) -> Result<(Container<T>, R), Error>
+    where
+        S: AsRef<OsStr>,
+        F: Copy,
+    {
+        unsafe {
+            let container = Self::load(name)?;
+            let init: F = container.lib.symbol(init_name)?;
+            let result = call(init);
+            Ok((container, result))
+        }
+    }
+
+    /**
+    Makes the container call the cleanup function of the library before closing it.
+
+    The function named `symbol_name` gets obtained as `extern "C" fn()` and is called when the
+    container (or a `MappedContainer` created from it) is dropped, right before the library is
+    closed. This is intended for libraries that require an explicit teardown before being unloaded.
+    Calling this method again replaces the previously registered function.
+
+    # Example
+
+    ```no_run
+    use dlopen2::wrapper::{Container, WrapperApi};
+
+    #[derive(WrapperApi)]
+    struct Api {
+        example_run: unsafe extern "C" fn(),
+    }
+
+    fn main() {
+        let cont: Container<Api> = unsafe { Container::load("libexample.so") }.unwrap();
+        let cont = unsafe { cont.with_teardown("example_shutdown") }.unwrap();
+        unsafe { cont.example_run() };
+        //example_shutdown() gets called here
+        drop(cont);
+    }
+    ```
+    */
+    pub unsafe fn with_teardown(mut self, symbol_name: &str) -> Result<Container<T>, Error> {
+        unsafe {
+            self.teardown.0 = Some(self.lib.symbol(symbol_name)?);
+            Ok(self)
+        }
+    }
+
     /// Load all symbols from the program itself.
     ///
     /// This allows a shared library to load symbols of the program it was
     /// loaded into.
     pub unsafe fn load_self() -> Result<Container<T>, Error> {
         unsafe {
-            let lib = Library::open_self()?;
+            let mut lib = Library::open_self()?;
+            lib.set_managed();
+            let api = T::load(&lib)?;
+            Ok(Self {
+                teardown: Teardown::default(),
+                lib,
+                api,
+            })
+        }
+    }
+
+    /// Load all symbols from an already opened library.
+    ///
+    /// This allows using a library that was opened in a custom way (e.g. with specific flags).
+    pub unsafe fn from_raw_library(mut lib: Library) -> Result<Container<T>, Error> {
+        unsafe {
+            lib.set_managed();
             let api = T::load(&lib)?;
-            Ok(Self { lib, api })
+            Ok(Self {
+                teardown: Teardown::default(),
+                lib,
+                api,
+            })
         }
     }
 
@@ -88,15 +202,107 @@ where
         unsafe { self.lib.into_raw() }
     }
 
+    /// Checks that all symbols of the API still resolve to the addresses they were loaded from.
+    ///
+    /// This is a diagnostic for long-running hosts, see `WrapperApi::verify()`.
+    pub fn verify(&self) -> Result<(), Error> {
+        self.api.verify(&self.lib)
+    }
+
+    /// Transforms the loaded API into another value (e.g. a higher-level wrapper around it)
+    /// while keeping the library loaded.
+    pub fn map<U, F>(self, f: F) -> MappedContainer<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        let Container { teardown, lib, api } = self;
+        MappedContainer::new(lib, teardown, f(api))
+    }
+
+    /// Same as load_with_flags(), except the SHA-256 hash of the library file is verified before
+    /// opening it. See `Library::open_verified()` for details.
+    #[cfg(feature = "checksum")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "checksum")))]
+    pub unsafe fn load_verified<S>(
+        name: S,
+        expected_hash: &[u8; 32],
+        flags: Option<i32>,
+    ) -> Result<Container<T>, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        unsafe {
+            let mut lib = Library::open_verified(name, expected_hash, flags)?;
+            lib.set_managed();
+            let api = T::load(&lib)?;
+            Ok(Self {
+                teardown: Teardown::default(),
+                lib,
+                api,
+            })
+        }
+    }
+
+    /// Same as load(), except all relocations of the library are resolved immediately.
+    ///
+    /// See `raw::Library::open_now()` for details.
+    pub unsafe fn load_now<S>(name: S) -> Result<Container<T>, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        unsafe {
+            let mut lib = Library::open_now(name)?;
+            lib.set_managed();
+            let api = T::load(&lib)?;
+            Ok(Self {
+                teardown: Teardown::default(),
+                lib,
+                api,
+            })
+        }
+    }
+
+    /// Same as load(), except the time spent opening the library and loading the symbols
+    /// is measured and returned too.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "metrics")))]
+    pub unsafe fn load_with_metrics<S>(name: S) -> Result<(Container<T>, LoadMetrics), Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        unsafe {
+            let start = Instant::now();
+            let mut lib = Library::open(name)?;
+            let open = start.elapsed();
+            lib.set_managed();
+            let start = Instant::now();
+            let api = T::load(&lib)?;
+            let symbols = start.elapsed();
+            Ok((
+                Self {
+                    teardown: Teardown::default(),
+                    lib,
+                    api,
+                },
+                LoadMetrics { open, symbols },
+            ))
+        }
+    }
+
     /// Same as load(), except specify flags used by libc::dlopen
     pub unsafe fn load_with_flags<S>(name: S, flags: Option<i32>) -> Result<Container<T>, Error>
     where
         S: AsRef<OsStr>,
     {
         unsafe {
-            let lib = Library::open_with_flags(name, flags)?;
+            let mut lib = Library::open_with_flags(name, flags)?;
+            lib.set_managed();
             let api = T::load(&lib)?;
-            Ok(Self { lib, api })
+            Ok(Self {
+                teardown: Teardown::default(),
+                lib,
+                api,
+            })
         }
     }
 }
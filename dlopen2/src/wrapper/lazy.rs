@@ -0,0 +1,105 @@
+use super::super::Error;
+use super::api::WrapperApi;
+use super::container::Container;
+use std::ffi::{OsStr, OsString};
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+/**
+Container that opens the library and loads its API only when it gets used for the first time.
+
+This is useful for optional features that may never be exercised: neither the cost of loading
+the library nor a possible failure come up until the API is actually needed.
+
+The library gets loaded by the first call of `get()` or the first dereference.
+If loading fails, `get()` returns the error and the next access tries loading the library again.
+Dereferencing panics instead, so use `get()` when the failure should be handled.
+
+#Example
+
+```no_run
+use dlopen2::wrapper::{LazyContainer, WrapperApi};
+
+#[derive(WrapperApi)]
+struct Api {
+    add_one: unsafe extern "C" fn (arg: i32) -> i32,
+}
+
+fn main () {
+    let lazy: LazyContainer<Api> = unsafe { LazyContainer::new("libexample.so") };
+    //the library gets loaded here
+    match lazy.get() {
+        Ok(api) => println!("1+1= {}", unsafe { api.add_one(1) }),
+        Err(err) => println!("The feature is not available: {err}"),
+    }
+}
+```
+*/
+pub struct LazyContainer<T>
+where
+    T: WrapperApi,
+{
+    name: OsString,
+    flags: Option<i32>,
+    container: OnceLock<Container<T>>,
+}
+
+impl<T> LazyContainer<T>
+where
+    T: WrapperApi,
+{
+    /// Creates a container that loads the library using the provided file name or path
+    /// once it gets used.
+    ///
+    /// This is unsafe for the same reason as `Container::load()`, the library just gets loaded later.
+    pub unsafe fn new<S>(name: S) -> LazyContainer<T>
+    where
+        S: AsRef<OsStr>,
+    {
+        unsafe { Self::with_flags(name, None) }
+    }
+
+    /// Same as new(), except specify flags used by libc::dlopen
+    pub unsafe fn with_flags<S>(name: S, flags: Option<i32>) -> LazyContainer<T>
+    where
+        S: AsRef<OsStr>,
+    {
+        LazyContainer {
+            name: name.as_ref().to_os_string(),
+            flags,
+            container: OnceLock::new(),
+        }
+    }
+
+    /// Returns the loaded API, loading the library first if that did not happen yet.
+    pub fn get(&self) -> Result<&Container<T>, Error> {
+        if let Some(container) = self.container.get() {
+            return Ok(container);
+        }
+        let container = unsafe { Container::load_with_flags(&self.name, self.flags) }?;
+        //another thread may have loaded the library in the meantime, then ours gets dropped
+        let _ = self.container.set(container);
+        Ok(self.container.get().unwrap())
+    }
+
+    /// Checks if the library was already loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.container.get().is_some()
+    }
+}
+
+impl<T> Deref for LazyContainer<T>
+where
+    T: WrapperApi,
+{
+    type Target = Container<T>;
+    fn deref(&self) -> &Container<T> {
+        match self.get() {
+            Ok(container) => container,
+            Err(err) => panic!(
+                "Could not load library {}: {err}",
+                self.name.to_string_lossy()
+            ),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use super::super::Error;
+use super::super::raw::Library;
+use std::slice;
+
+//name of the symbol emitted by `export_api!`
+const MANIFEST_SYMBOL: &std::ffi::CStr = c"__dlopen2_api_manifest";
+
+/**
+Manifest of the functions a library exports with `export_api!`.
+
+For every function the manifest contains its name and its signature as written in the source
+(e.g. `extern "C" fn(c_int) -> c_int`, argument names and `unsafe` are left out). The derive
+of `WrapperApi` compares the signatures of the structure's fields against it in
+`WrapperApi::check_manifest()`. The layout is fixed (`repr(C)`), so the manifest can be read
+regardless of the compiler that built the library.
+*/
+#[repr(C)]
+#[derive(Debug)]
+pub struct ApiManifest {
+    entries: *const ManifestEntry,
+    len: usize,
+}
+
+/// Entry of an `ApiManifest`, created by `export_api!`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ManifestEntry {
+    name: *const u8,
+    name_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+}
+
+//the manifest points to immutable static data
+unsafe impl Sync for ApiManifest {}
+unsafe impl Sync for ManifestEntry {}
+
+impl ManifestEntry {
+    /// Creates an entry, used by `export_api!`.
+    pub const fn new(name: &'static str, signature: &'static str) -> ManifestEntry {
+        ManifestEntry {
+            name: name.as_ptr(),
+            name_len: name.len(),
+            signature: signature.as_ptr(),
+            signature_len: signature.len(),
+        }
+    }
+
+    /// Returns the name of the function.
+    pub fn name(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.name, self.name_len) }
+    }
+
+    /// Returns the signature of the function.
+    pub fn signature(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.signature, self.signature_len) }
+    }
+}
+
+impl ApiManifest {
+    /// Creates a manifest, used by `export_api!`.
+    pub const fn new(entries: &'static [ManifestEntry]) -> ApiManifest {
+        ApiManifest {
+            entries: entries.as_ptr(),
+            len: entries.len(),
+        }
+    }
+
+    /// Obtains the manifest exported by the library.
+    ///
+    /// `Error::SymbolGettingError` is returned if the library does not export a manifest.
+    pub fn from_library(lib: &Library) -> Result<&ApiManifest, Error> {
+        unsafe { lib.symbol_cstr(MANIFEST_SYMBOL) }
+    }
+
+    /// Returns the entries of the manifest.
+    pub fn entries(&self) -> &[ManifestEntry] {
+        unsafe { slice::from_raw_parts(self.entries, self.len) }
+    }
+
+    /**
+    Checks that the function `name` is listed with the given signature.
+
+    Functions missing from the manifest are accepted only if they are `optional`.
+    `Error::SignatureMismatch` with the name of the function is returned otherwise.
+    */
+    pub fn check_signature(
+        &self,
+        name: &str,
+        signature: &str,
+        optional: bool,
+    ) -> Result<(), Error> {
+        match self
+            .entries()
+            .iter()
+            .find(|entry| entry.name() == name.as_bytes())
+        {
+            Some(entry) if entry.signature() == signature.as_bytes() => Ok(()),
+            None if optional => Ok(()),
+            _ => Err(Error::SignatureMismatch(name.to_owned())),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::raw;
+
+use super::super::raw::Library;
+use super::teardown::Teardown;
+use std::ops::{Deref, DerefMut};
+
+/**
+Container for a dynamic load library handle and a value built from its API.
+
+It is created by [`Container::map`](./struct.Container.html#method.map) and allows building
+higher-level abstractions on top of the loaded symbols while keeping the library loaded for as
+long as the value exists.
+
+#Example
+
+```no_run
+use dlopen2::wrapper::{Container, MappedContainer, WrapperApi};
+
+#[derive(WrapperApi)]
+struct Api {
+    add_one: unsafe extern "C" fn (arg: i32) -> i32,
+}
+
+struct Calculator(Api);
+
+impl Calculator {
+    fn increment(&self, value: i32) -> i32 {
+        unsafe { self.0.add_one(value) }
+    }
+}
+
+fn main () {
+    let container: Container<Api> = unsafe { Container::load("libexample.dylib")}.unwrap();
+    let calculator: MappedContainer<Calculator> = container.map(Calculator);
+    println!("1+1= {}", calculator.increment(1));
+}
+```
+*/
+pub struct MappedContainer<U> {
+    //declared first so that it gets dropped before the library is closed
+    value: U,
+    //calls the teardown function registered on the container before the library is closed
+    teardown: Teardown,
+    #[allow(dead_code)]
+    //this is not dead code because destructor of Library deallocates the library
+    lib: Library,
+}
+
+impl<U> MappedContainer<U> {
+    pub(crate) fn new(lib: Library, teardown: Teardown, value: U) -> MappedContainer<U> {
+        MappedContainer {
+            value,
+            teardown,
+            lib,
+        }
+    }
+
+    /// Transforms the contained value while keeping the library loaded.
+    pub fn map<V, F>(self, f: F) -> MappedContainer<V>
+    where
+        F: FnOnce(U) -> V,
+    {
+        let MappedContainer {
+            value,
+            teardown,
+            lib,
+        } = self;
+        MappedContainer::new(lib, teardown, f(value))
+    }
+
+    /// Returns the raw OS handle for the opened library.
+    ///
+    /// This is `HMODULE` on Windows and `*mut c_void` on Unix systems. Don't use unless absolutely necessary.
+    pub unsafe fn into_raw(&self) -> raw::Handle {
+        unsafe { self.lib.into_raw() }
+    }
+}
+
+impl<U> Deref for MappedContainer<U> {
+    type Target = U;
+    fn deref(&self) -> &U {
+        &self.value
+    }
+}
+
+impl<U> DerefMut for MappedContainer<U> {
+    fn deref_mut(&mut self) -> &mut U {
+        &mut self.value
+    }
+}
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+/**
+Time spent loading a library, returned by `Container::load_with_metrics()`
+and `OptionalContainer::load_with_metrics()`.
+
+This helps finding slow-loading plugins when profiling the startup of an application.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadMetrics {
+    /// Time spent opening the library, which includes loading its dependencies
+    /// and running its initializers.
+    pub open: Duration,
+    /// Time spent obtaining all symbols of the API.
+    pub symbols: Duration,
+}
+
+impl LoadMetrics {
+    /// Returns the total time spent loading the library.
+    pub fn total(&self) -> Duration {
+        self.open + self.symbols
+    }
+}
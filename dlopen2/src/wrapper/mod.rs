@@ -73,13 +73,33 @@ and
 
 mod api;
 mod container;
+mod lazy;
+mod manifest;
+mod mapped;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod multi_api;
 mod option;
 mod optional;
+mod symbols;
+mod sync;
+mod teardown;
+mod tuple;
+mod versioned;
 pub use self::api::WrapperApi;
 pub use self::container::Container;
+pub use self::lazy::LazyContainer;
+pub use self::manifest::{ApiManifest, ManifestEntry};
+pub use self::mapped::MappedContainer;
+#[cfg(feature = "metrics")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "metrics")))]
+pub use self::metrics::LoadMetrics;
 pub use self::multi_api::WrapperMultiApi;
 pub use self::optional::OptionalContainer;
+#[doc(hidden)]
+pub use self::symbols::{unique_symbol_count, unique_symbols};
+pub use self::sync::SyncGuard;
+pub use self::versioned::{VersionedContainer, WrapperApiVersions};
 
 #[cfg(feature = "derive")]
 pub use dlopen2_derive::{WrapperApi, WrapperMultiApi};
@@ -12,6 +12,13 @@ library APIs with multiple optional parts.
 structure where all fields implement the `WrapperApi` trait (this includes `Option<T>` where
 `T` implements `WrapperApi`). The derive macro will generate required implementation.
 
+**Note:** Wrappers are namespaced by sub-API: each sub-API keeps its own wrappers, which are
+reached through the field holding it (e.g. `container.optional1.static_val()`). Sub-APIs can
+therefore load symbols of the same name and have identically named wrappers without
+colliding. Only the wrappers generated for a single structure share one namespace - colliding
+names there are reported at compile time and need to be resolved by renaming one of the
+fields and keeping its symbol name with the "dlopen2_name" attribute.
+
 **Note**: `WrapperMultiApi` should only be used together with `Container` structure, never to create
 a standalone object. API and library handle need to be kept together to prevent dangling symbols.
 
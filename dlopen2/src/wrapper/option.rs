@@ -1,17 +1,49 @@
 use super::super::Error;
-use super::super::raw::Library;
+use super::super::STRICT;
+use super::super::raw::{Library, SymbolSource};
 use super::api::WrapperApi;
+use super::tuple::unique_symbols;
 
 impl<T> WrapperApi for Option<T>
 where
     T: WrapperApi,
 {
-    unsafe fn load(lib: &Library) -> Result<Self, Error> {
+    unsafe fn load(lib: &impl SymbolSource) -> Result<Self, Error> {
         unsafe {
             match T::load(lib) {
                 Ok(val) => Ok(Some(val)),
+                Err(Error::NullSymbol) if STRICT => Err(Error::NullSymbol),
                 Err(_) => Ok(None),
             }
         }
     }
+
+    //the API is allowed to be missing, but not to have fields of the wrong size
+    fn check(lib: &Library) -> Result<(), Error> {
+        match T::check(lib) {
+            Err(err @ Error::SymbolSizeMismatch(_)) => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    //the API may be missing from the manifest, but not have functions with other signatures
+    fn check_manifest(lib: &Library) -> Result<(), Error> {
+        T::check_manifest_optional(lib)
+    }
+
+    fn check_manifest_optional(lib: &Library) -> Result<(), Error> {
+        T::check_manifest_optional(lib)
+    }
+
+    //nothing is required if the whole API may be missing
+    fn optional_symbols() -> Vec<String> {
+        unique_symbols(Vec::new(), [T::required_symbols(), T::optional_symbols()])
+    }
+
+    fn verify(&self, lib: &Library) -> Result<(), Error> {
+        match self {
+            Some(api) => api.verify(lib),
+            None => Ok(()),
+        }
+    }
 }
@@ -1,8 +1,12 @@
 use super::super::Error;
 use super::super::raw::Library;
 use super::api::WrapperApi;
+#[cfg(feature = "metrics")]
+use super::metrics::LoadMetrics;
 use std::ffi::OsStr;
 use std::ops::{Deref, DerefMut};
+#[cfg(feature = "metrics")]
+use std::time::Instant;
 
 /**
 Container for a library handle and both obligatory and optional APIs inside one structure.
@@ -11,6 +15,7 @@ A common problem with dynamic link libraries is that they often have different v
 of those versions have broader API than others. This structure allows you to use two APIs at the
 same time - one obligatory and one optional. If symbols of the optional API are found in the
 library, the optional API gets loaded. Otherwise the `optional()` method will return `None`.
+With the `strict` feature enabled, loading fails if a symbol of the optional API is null.
 
 #Example
 
@@ -43,9 +48,14 @@ fn main () {
         },
         &None => println!("The optional API was not loaded!")
     }
+    //or without matching:
+    let _result: Option<i32> = container.optional_map(|opt| unsafe { opt.add_one(5) });
 }
 ```
 
+**Note:** Both APIs are always dropped before the library is closed, so their destructors
+may still use symbols of the library.
+
 **Note:** For more complex cases (multiple versions of API) you can use
 [`WrapperMultiApi`](./trait.WrapperMultiApi.html).
 */
@@ -54,11 +64,13 @@ where
     Api: WrapperApi,
     Optional: WrapperApi,
 {
+    //declared before the library so that both APIs get dropped before the library is closed,
+    //their symbols (and anything their destructors use) point into it
+    api: Api,
+    optional: Option<Optional>,
     #[allow(dead_code)]
     //this is not dead code because destructor of Library deallocates the library
     lib: Library,
-    api: Api,
-    optional: Option<Optional>,
 }
 
 impl<Api, Optional> OptionalContainer<Api, Optional>
@@ -73,13 +85,53 @@ where
         S: AsRef<OsStr>,
     {
         unsafe {
-            let lib = Library::open(name)?;
+            let mut lib = Library::open(name)?;
+            lib.set_managed();
+            let api = Api::load(&lib)?;
+            let optional = Option::<Optional>::load(&lib)?;
+            Ok(Self { lib, api, optional })
+        }
+    }
+
+    /// Same as load(), except all relocations of the library are resolved immediately.
+    ///
+    /// See `raw::Library::open_now()` for details.
+    pub unsafe fn load_now<S>(name: S) -> Result<OptionalContainer<Api, Optional>, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        unsafe {
+            let mut lib = Library::open_now(name)?;
+            lib.set_managed();
             let api = Api::load(&lib)?;
-            let optional = Optional::load(&lib).ok();
+            let optional = Option::<Optional>::load(&lib)?;
             Ok(Self { lib, api, optional })
         }
     }
 
+    /// Same as load(), except the time spent opening the library and loading the symbols
+    /// (including optional) is measured and returned too.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "metrics")))]
+    pub unsafe fn load_with_metrics<S>(
+        name: S,
+    ) -> Result<(OptionalContainer<Api, Optional>, LoadMetrics), Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        unsafe {
+            let start = Instant::now();
+            let mut lib = Library::open(name)?;
+            let open = start.elapsed();
+            lib.set_managed();
+            let start = Instant::now();
+            let api = Api::load(&lib)?;
+            let optional = Option::<Optional>::load(&lib)?;
+            let symbols = start.elapsed();
+            Ok((Self { lib, api, optional }, LoadMetrics { open, symbols }))
+        }
+    }
+
     /// Opens the library using provided file name or path and flags, and loads all symbols (including optional
     /// if it is possible).
     pub unsafe fn load_with_flags<S>(
@@ -90,9 +142,10 @@ where
         S: AsRef<OsStr>,
     {
         unsafe {
-            let lib = Library::open_with_flags(name, flags)?;
+            let mut lib = Library::open_with_flags(name, flags)?;
+            lib.set_managed();
             let api = Api::load(&lib)?;
-            let optional = Optional::load(&lib).ok();
+            let optional = Option::<Optional>::load(&lib)?;
             Ok(Self { lib, api, optional })
         }
     }
@@ -104,13 +157,34 @@ where
     /// loaded into.
     pub unsafe fn load_self() -> Result<OptionalContainer<Api, Optional>, Error> {
         unsafe {
-            let lib = Library::open_self()?;
+            let mut lib = Library::open_self()?;
+            lib.set_managed();
             let api = Api::load(&lib)?;
-            let optional = Optional::load(&lib).ok();
+            let optional = Option::<Optional>::load(&lib)?;
             Ok(Self { lib, api, optional })
         }
     }
 
+    /// Load all symbols (including optional if it is possible) from an already opened library.
+    ///
+    /// This allows using a library that was opened in a custom way (e.g. with specific flags).
+    pub unsafe fn from_raw_library(
+        mut lib: Library,
+    ) -> Result<OptionalContainer<Api, Optional>, Error> {
+        unsafe {
+            lib.set_managed();
+            let api = Api::load(&lib)?;
+            let optional = Option::<Optional>::load(&lib)?;
+            Ok(Self { lib, api, optional })
+        }
+    }
+
+    /// Checks that all symbols of both APIs still resolve to the addresses they were loaded from.
+    pub fn verify(&self) -> Result<(), Error> {
+        self.api.verify(&self.lib)?;
+        self.optional.verify(&self.lib)
+    }
+
     /// Gives access to the optional API - constant version.
     pub fn optional(&self) -> &Option<Optional> {
         &self.optional
@@ -120,6 +194,28 @@ where
     pub fn optional_mut(&mut self) -> &mut Option<Optional> {
         &mut self.optional
     }
+
+    /// Checks if the optional API was loaded.
+    pub fn has_optional(&self) -> bool {
+        self.optional.is_some()
+    }
+
+    /// Calls `f` with the optional API if it was loaded, see `Option::map()`.
+    pub fn optional_map<U, F>(&self, f: F) -> Option<U>
+    where
+        F: FnOnce(&Optional) -> U,
+    {
+        self.optional.as_ref().map(f)
+    }
+
+    /// Calls `f` with the optional API if it was loaded and returns its result,
+    /// see `Option::and_then()`.
+    pub fn optional_and_then<U, F>(&self, f: F) -> Option<U>
+    where
+        F: FnOnce(&Optional) -> Option<U>,
+    {
+        self.optional.as_ref().and_then(f)
+    }
 }
 
 impl<Api, Optional> Deref for OptionalContainer<Api, Optional>
@@ -0,0 +1,64 @@
+//Const functions used by the derive macro to list the symbols of an API at compile time.
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn contains(names: &[&str], len: usize, name: &str) -> bool {
+    let mut i = 0;
+    while i < len {
+        if str_eq(names[i], name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+//a name is kept if it is not excluded and wasn't listed before
+const fn keep(names: &[&str], index: usize, excluded: &[&str]) -> bool {
+    !contains(names, index, names[index]) && !contains(excluded, excluded.len(), names[index])
+}
+
+/// Number of names returned by `unique_symbols()`.
+pub const fn unique_symbol_count(names: &[&str], excluded: &[&str]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < names.len() {
+        if keep(names, i, excluded) {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+/// Returns the names in their original order without duplicates and without the excluded ones.
+pub const fn unique_symbols<const N: usize>(
+    names: &[&'static str],
+    excluded: &[&'static str],
+) -> [&'static str; N] {
+    let mut unique = [""; N];
+    let mut count = 0;
+    let mut i = 0;
+    while i < names.len() {
+        if keep(names, i, excluded) {
+            unique[count] = names[i];
+            count += 1;
+        }
+        i += 1;
+    }
+    assert!(count == N, "The number of unique symbols does not match");
+    unique
+}
@@ -0,0 +1,44 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/**
+Synchronized access to mutable data exported by a library.
+
+This is returned by accessors of `WrapperApi` fields marked with the `dlopen2_sync` attribute.
+Such fields are declared as `Mutex<&'a mut FieldType>`, so every structure has its own lock.
+The lock is held for as long as the guard exists, so accesses through the same field
+get serialized even if they happen on different threads.
+
+**Note:** Only accesses through the generated accessor of the same structure are synchronized.
+The library itself (or any other code obtaining the same symbol) can still access the data
+at any time.
+*/
+#[derive(Debug)]
+pub struct SyncGuard<'a, 'b, T: 'b> {
+    guard: MutexGuard<'a, &'b mut T>,
+}
+
+impl<'a, 'b, T> SyncGuard<'a, 'b, T> {
+    /// Locks the given mutex and gives access to the referenced data.
+    ///
+    /// This function is used by the code generated by the `WrapperApi` derive macro.
+    pub fn new(lock: &'a Mutex<&'b mut T>) -> SyncGuard<'a, 'b, T> {
+        //a panic while holding the lock can't leave the reference itself in a broken state
+        SyncGuard {
+            guard: lock.lock().unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+}
+
+impl<T> Deref for SyncGuard<'_, '_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for SyncGuard<'_, '_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
@@ -0,0 +1,12 @@
+//Calls the teardown function of a library when dropped. Containers declare it before the library
+//so that the function gets called before the library is closed.
+#[derive(Default)]
+pub(crate) struct Teardown(pub(crate) Option<unsafe extern "C" fn()>);
+
+impl Drop for Teardown {
+    fn drop(&mut self) {
+        if let Some(teardown) = self.0 {
+            unsafe { teardown() }
+        }
+    }
+}
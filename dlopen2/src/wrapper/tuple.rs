@@ -0,0 +1,70 @@
+use super::super::Error;
+use super::super::raw::{Library, SymbolSource};
+use super::api::WrapperApi;
+
+//appends the names that are not listed yet
+pub(crate) fn unique_symbols<const N: usize>(
+    mut unique: Vec<String>,
+    lists: [Vec<String>; N],
+) -> Vec<String> {
+    for name in lists.into_iter().flatten() {
+        if !unique.contains(&name) {
+            unique.push(name);
+        }
+    }
+    unique
+}
+
+//tuples of APIs load all their elements from the same library, which allows
+//opening the library once for several independent APIs: Container<(ApiA, ApiB)>
+macro_rules! impl_wrapper_api_for_tuple {
+    ($($api:ident : $idx:tt),+) => {
+        impl<$($api),+> WrapperApi for ($($api,)+)
+        where
+            $($api: WrapperApi),+
+        {
+            unsafe fn load(lib: &impl SymbolSource) -> Result<Self, Error> {
+                unsafe { Ok(($($api::load(lib)?,)+)) }
+            }
+
+            fn check(lib: &Library) -> Result<(), Error> {
+                $($api::check(lib)?;)+
+                Ok(())
+            }
+
+            fn check_manifest(lib: &Library) -> Result<(), Error> {
+                $($api::check_manifest(lib)?;)+
+                Ok(())
+            }
+
+            fn check_manifest_optional(lib: &Library) -> Result<(), Error> {
+                $($api::check_manifest_optional(lib)?;)+
+                Ok(())
+            }
+
+            fn verify(&self, lib: &Library) -> Result<(), Error> {
+                $(self.$idx.verify(lib)?;)+
+                Ok(())
+            }
+
+            fn required_symbols() -> Vec<String> {
+                unique_symbols(Vec::new(), [$($api::required_symbols()),+])
+            }
+
+            fn optional_symbols() -> Vec<String> {
+                let required = Self::required_symbols();
+                let len = required.len();
+                unique_symbols(required, [$($api::optional_symbols()),+]).split_off(len)
+            }
+        }
+    };
+}
+
+impl_wrapper_api_for_tuple!(A: 0);
+impl_wrapper_api_for_tuple!(A: 0, B: 1);
+impl_wrapper_api_for_tuple!(A: 0, B: 1, C: 2);
+impl_wrapper_api_for_tuple!(A: 0, B: 1, C: 2, D: 3);
+impl_wrapper_api_for_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_wrapper_api_for_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_wrapper_api_for_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_wrapper_api_for_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
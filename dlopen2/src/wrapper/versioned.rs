@@ -0,0 +1,174 @@
+use crate::raw;
+
+use super::super::Error;
+use super::super::raw::Library;
+use super::api::WrapperApi;
+use std::ffi::OsStr;
+use std::ops::{Deref, DerefMut};
+
+/**
+Set of alternative versions of an API, used by `VersionedContainer`.
+
+It is implemented for tuples of up to 8 types implementing `WrapperApi`. The versions are
+ordered from the newest (most complete) to the oldest one. The loaded API is represented by
+a tuple of options, in which only the selected version is `Some`.
+*/
+pub trait WrapperApiVersions
+where
+    Self: Sized,
+{
+    /// Tuple of options holding the loaded version of the API.
+    type Loaded;
+    /// Loads the first version whose symbols can all be loaded, returning it together with
+    /// its index. If no version can be loaded, the error of the last (oldest) one is returned.
+    unsafe fn load_first(lib: &Library) -> Result<(Self::Loaded, usize), Error>;
+}
+
+macro_rules! impl_wrapper_api_versions {
+    ($($name:ident => $index:tt),* ; $last:ident => $last_index:tt) => {
+        impl<$($name,)* $last> WrapperApiVersions for ($($name,)* $last,)
+        where
+            $($name: WrapperApi,)*
+            $last: WrapperApi,
+        {
+            type Loaded = ($(Option<$name>,)* Option<$last>,);
+
+            unsafe fn load_first(lib: &Library) -> Result<(Self::Loaded, usize), Error> {
+                unsafe {
+                    let mut loaded: Self::Loaded = Default::default();
+                    $(
+                        if let Ok(api) = $name::load(lib) {
+                            loaded.$index = Some(api);
+                            return Ok((loaded, $index));
+                        }
+                    )*
+                    //the error of the oldest version is the most relevant one
+                    loaded.$last_index = Some($last::load(lib)?);
+                    Ok((loaded, $last_index))
+                }
+            }
+        }
+    };
+}
+
+impl_wrapper_api_versions!(; A => 0);
+impl_wrapper_api_versions!(A => 0; B => 1);
+impl_wrapper_api_versions!(A => 0, B => 1; C => 2);
+impl_wrapper_api_versions!(A => 0, B => 1, C => 2; D => 3);
+impl_wrapper_api_versions!(A => 0, B => 1, C => 2, D => 3; E => 4);
+impl_wrapper_api_versions!(A => 0, B => 1, C => 2, D => 3, E => 4; F => 5);
+impl_wrapper_api_versions!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5; G => 6);
+impl_wrapper_api_versions!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6; H => 7);
+
+/**
+Container for a library handle and the newest version of an API that the library supports.
+
+Libraries often come in several versions with incrementally larger APIs. `VersionedContainer`
+takes a tuple of `WrapperApi` types ordered from the newest to the oldest one and loads the
+first of them whose symbols can all be found in the library.
+It dereferences to a tuple of options in which only the selected version is `Some`.
+
+#Example
+
+```no_run
+use dlopen2::wrapper::{VersionedContainer, WrapperApi};
+
+#[derive(WrapperApi)]
+struct ApiV1 {
+    do_something: extern "C" fn(),
+}
+
+#[derive(WrapperApi)]
+struct ApiV2 {
+    do_something: extern "C" fn(),
+    do_something_else: extern "C" fn(),
+}
+
+fn main () {
+    let container: VersionedContainer<(ApiV2, ApiV1)> = unsafe {
+        VersionedContainer::load("libexample.so")
+    }.unwrap();
+    println!("Loaded version at index {}", container.version());
+    match &*container {
+        (Some(v2), _) => v2.do_something_else(),
+        (_, Some(v1)) => v1.do_something(),
+        _ => unreachable!(),
+    }
+}
+```
+*/
+pub struct VersionedContainer<V>
+where
+    V: WrapperApiVersions,
+{
+    #[allow(dead_code)]
+    //this is not dead code because destructor of Library deallocates the library
+    lib: Library,
+    api: V::Loaded,
+    version: usize,
+}
+
+impl<V> VersionedContainer<V>
+where
+    V: WrapperApiVersions,
+{
+    /// Open the library using provided file name or path and load the newest supported version
+    /// of the API.
+    pub unsafe fn load<S>(name: S) -> Result<VersionedContainer<V>, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        unsafe { Self::from_raw_library(Library::open(name)?) }
+    }
+
+    /// Same as load(), except specify flags used by libc::dlopen
+    pub unsafe fn load_with_flags<S>(
+        name: S,
+        flags: Option<i32>,
+    ) -> Result<VersionedContainer<V>, Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        unsafe { Self::from_raw_library(Library::open_with_flags(name, flags)?) }
+    }
+
+    /// Load the newest supported version of the API from an already opened library.
+    pub unsafe fn from_raw_library(mut lib: Library) -> Result<VersionedContainer<V>, Error> {
+        unsafe {
+            lib.set_managed();
+            let (api, version) = V::load_first(&lib)?;
+            Ok(Self { lib, api, version })
+        }
+    }
+
+    /// Index of the loaded version in the tuple of versions (0 is the newest one).
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    /// Returns the raw OS handle for the opened library.
+    ///
+    /// This is `HMODULE` on Windows and `*mut c_void` on Unix systems. Don't use unless absolutely necessary.
+    pub unsafe fn into_raw(&self) -> raw::Handle {
+        unsafe { self.lib.into_raw() }
+    }
+}
+
+impl<V> Deref for VersionedContainer<V>
+where
+    V: WrapperApiVersions,
+{
+    type Target = V::Loaded;
+    fn deref(&self) -> &V::Loaded {
+        &self.api
+    }
+}
+
+impl<V> DerefMut for VersionedContainer<V>
+where
+    V: WrapperApiVersions,
+{
+    fn deref_mut(&mut self) -> &mut V::Loaded {
+        &mut self.api
+    }
+}
@@ -0,0 +1,34 @@
+use dlopen2::build::{missing_symbols, require_symbols};
+
+mod commons;
+use commons::example_lib_path;
+
+#[test]
+fn missing_symbols_reported() {
+    let lib_path = example_lib_path();
+    let missing = missing_symbols(
+        &lib_path,
+        &[
+            "rust_fun_add_one",
+            "rust_fun_not_found",
+            "c_fun_add_two",
+            "c_int_not_found",
+        ],
+    )
+    .expect("Could not open library");
+    assert_eq!(missing, ["rust_fun_not_found", "c_int_not_found"]);
+    assert!(missing_symbols("libnot_existing_library.so", &["rust_fun_add_one"]).is_err());
+}
+
+#[test]
+fn require_present_symbols() {
+    let lib_path = example_lib_path();
+    require_symbols(&lib_path, &["rust_fun_print_something", "rust_i32"]);
+}
+
+#[test]
+#[should_panic(expected = "missing required symbols: rust_fun_not_found")]
+fn require_missing_symbols() {
+    let lib_path = example_lib_path();
+    require_symbols(&lib_path, &["rust_fun_add_one", "rust_fun_not_found"]);
+}
@@ -83,6 +83,7 @@ fn recursive_find(path: &Path, file_regex: &regex::Regex) -> Option<PathBuf> {
     }
 }
 
+#[allow(dead_code)] //not all tests use this and this generates warnings
 #[repr(C)]
 pub struct SomeData {
     pub first: c_int,
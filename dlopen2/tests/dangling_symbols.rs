@@ -0,0 +1,33 @@
+#![cfg(debug_assertions)]
+
+use dlopen2::Error;
+use dlopen2::raw::{Library, clear_error_hook, set_error_hook};
+use std::sync::{Arc, Mutex};
+
+mod commons;
+use commons::example_lib_path;
+
+// runs in its own process, because the error hook is global
+#[test]
+fn closing_library_reports_dangling_symbols() {
+    let reported: Arc<Mutex<Vec<usize>>> = Arc::default();
+    let recorded = reported.clone();
+    set_error_hook(move |_name, err| {
+        if let Error::DanglingSymbols(count) = err {
+            recorded.lock().unwrap().push(*count);
+        }
+    });
+
+    let untracked = Library::open(example_lib_path()).expect("Could not open library");
+    let _ = unsafe { untracked.symbol::<fn()>("rust_fun_print_something") }.unwrap();
+    drop(untracked);
+
+    let mut tracked = Library::open(example_lib_path()).expect("Could not open library");
+    tracked.set_track_symbols(true);
+    let _ = unsafe { tracked.symbol::<fn()>("rust_fun_print_something") }.unwrap();
+    let _ = unsafe { tracked.symbol::<fn(i32) -> i32>("rust_fun_add_one") }.unwrap();
+    drop(tracked);
+    clear_error_hook();
+
+    assert_eq!(*reported.lock().unwrap(), [2]);
+}
@@ -0,0 +1,75 @@
+use dlopen2::raw::{Library, clear_error_hook, set_error_hook};
+use std::sync::{Arc, Mutex};
+
+mod commons;
+use commons::example_lib_path;
+
+#[test]
+fn error_hook_reports_failures() {
+    let failures: Arc<Mutex<Vec<(String, bool)>>> = Arc::default();
+    let recorded = failures.clone();
+    set_error_hook(move |name, err| {
+        let is_symbol_error = matches!(err, dlopen2::Error::SymbolGettingError(_));
+        recorded
+            .lock()
+            .unwrap()
+            .push((name.to_owned(), is_symbol_error));
+    });
+
+    assert!(Library::open("libdlopen2_hook_missing.so").is_err());
+    #[cfg(target_os = "linux")]
+    let fd_name = {
+        use std::os::fd::{AsFd, AsRawFd};
+        //a file that is not a library
+        let path = std::env::temp_dir().join(format!("dlopen2-hook-{}.txt", std::process::id()));
+        std::fs::write(&path, "not a library").unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(Library::open_from_fd(file.as_fd(), None).is_err());
+        format!("file descriptor {}", file.as_raw_fd())
+    };
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    assert!(unsafe { lib.symbol::<fn()>("rust_fun_not_found") }.is_err());
+    let _ = unsafe { lib.symbol::<fn()>("rust_fun_print_something") }.unwrap();
+    //a set reports a symbol once, and only if none of its libraries provides it
+    #[cfg(any(target_os = "linux", windows))]
+    {
+        #[cfg(target_os = "linux")]
+        const SYSTEM_LIB: &str = "libm.so.6";
+        #[cfg(windows)]
+        const SYSTEM_LIB: &str = "kernel32.dll";
+        let mut set = dlopen2::raw::LibrarySet::new();
+        set.push(Library::open(SYSTEM_LIB).expect("Could not open system library"));
+        set.open(example_lib_path())
+            .expect("Could not open library");
+        let _ = unsafe { set.symbol::<fn()>("rust_fun_print_something") }.unwrap();
+        assert!(unsafe { set.symbol::<fn()>("rust_fun_not_found") }.is_err());
+    }
+    clear_error_hook();
+    assert!(Library::open("libdlopen2_hook_after_clear.so").is_err());
+
+    let failures = failures.lock().unwrap();
+    assert_eq!(
+        *failures,
+        [
+            ("libdlopen2_hook_missing.so".to_owned(), false),
+            #[cfg(target_os = "linux")]
+            (fd_name, false),
+            ("rust_fun_not_found".to_owned(), true),
+            #[cfg(any(target_os = "linux", windows))]
+            ("rust_fun_not_found".to_owned(), true),
+        ]
+    );
+
+    // the hook may fail itself and replace the hook without deadlocking
+    let reentrant: Arc<Mutex<Vec<String>>> = Arc::default();
+    let recorded = reentrant.clone();
+    set_error_hook(move |name, _err| {
+        recorded.lock().unwrap().push(name.to_owned());
+        clear_error_hook();
+        assert!(Library::open("libdlopen2_hook_nested.so").is_err());
+    });
+    assert!(Library::open("libdlopen2_hook_reentrant.so").is_err());
+    assert!(Library::open("libdlopen2_hook_after_reentrant.so").is_err());
+    assert_eq!(*reentrant.lock().unwrap(), ["libdlopen2_hook_reentrant.so"]);
+}
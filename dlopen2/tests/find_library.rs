@@ -0,0 +1,37 @@
+#![cfg(feature = "testing")]
+
+use dlopen2::utils::find_library;
+use std::fs;
+
+mod commons;
+use commons::example_lib_path;
+
+#[test]
+fn find_example_library() {
+    let lib_path = example_lib_path();
+    let deps_dir = lib_path.parent().unwrap();
+    let found = find_library(deps_dir, "example").expect("Could not find the example library");
+    assert_eq!(
+        fs::canonicalize(found.parent().unwrap()).unwrap(),
+        fs::canonicalize(deps_dir).unwrap()
+    );
+    let file_name = found.file_name().unwrap().to_str().unwrap();
+    assert!(file_name.contains("example"));
+    assert!(find_library(deps_dir, "exam").is_none());
+    assert!(find_library(deps_dir, "example_not_existing").is_none());
+}
+
+#[test]
+#[cfg(unix)]
+fn find_library_skips_symlink_cycles() {
+    let lib_path = example_lib_path();
+    let dir = std::env::temp_dir().join(format!("dlopen2-find-cycle-{}", std::process::id()));
+    let nested = dir.join("nested");
+    fs::create_dir_all(&nested).unwrap();
+    //a link back to the parent directory would be walked forever if it was followed
+    std::os::unix::fs::symlink(&dir, nested.join("parent")).unwrap();
+    fs::copy(&lib_path, nested.join(lib_path.file_name().unwrap())).unwrap();
+    let found = find_library(&dir, "example");
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(found, Some(nested.join(lib_path.file_name().unwrap())));
+}
@@ -0,0 +1,59 @@
+use dlopen2::raw::Library;
+use dlopen2::symbor::{Ref, SymBorApi, Symbol};
+use dlopen2::wrapper::{Container, OptionalContainer, WrapperApi};
+use std::os::raw::c_int;
+
+mod commons;
+use commons::example_lib_path;
+
+#[derive(WrapperApi)]
+struct WrapperExample<'a> {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+    rust_i32: &'a i32,
+}
+
+#[derive(WrapperApi)]
+struct WrapperNotFound {
+    c_fun_not_found: unsafe extern "C" fn(),
+}
+
+#[derive(SymBorApi)]
+struct SymBorExample<'a> {
+    pub c_fun_add_two: Symbol<'a, unsafe extern "C" fn(c_int) -> c_int>,
+    pub rust_i32: Ref<'a, i32>,
+}
+
+fn open_with_custom_flags() -> Library {
+    #[cfg(unix)]
+    let flags = Some(libc::RTLD_NOW | libc::RTLD_LOCAL);
+    #[cfg(windows)]
+    let flags = None;
+    Library::open_with_flags(example_lib_path(), flags).expect("Could not open library")
+}
+
+#[test]
+fn wrapper_container_from_raw_library() {
+    let cont: Container<WrapperExample> =
+        unsafe { Container::from_raw_library(open_with_custom_flags()) }
+            .expect("Could not load symbols");
+    assert_eq!(unsafe { cont.c_fun_add_two(2) }, 4);
+    assert_eq!(43, *cont.rust_i32());
+}
+
+#[test]
+fn optional_container_from_raw_library() {
+    let cont: OptionalContainer<WrapperExample, WrapperNotFound> =
+        unsafe { OptionalContainer::from_raw_library(open_with_custom_flags()) }
+            .expect("Could not load symbols");
+    assert_eq!(unsafe { cont.c_fun_add_two(2) }, 4);
+    assert!(cont.optional().is_none());
+}
+
+#[test]
+fn symbor_container_from_raw_library() {
+    let cont: dlopen2::symbor::Container<SymBorExample> =
+        unsafe { dlopen2::symbor::Container::from_raw_library(open_with_custom_flags()) }
+            .expect("Could not load symbols");
+    assert_eq!(unsafe { (cont.c_fun_add_two)(2) }, 4);
+    assert_eq!(43, *cont.rust_i32);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "metrics")]
+
+use dlopen2::wrapper::{Container, OptionalContainer, WrapperApi};
+use std::os::raw::c_int;
+use std::time::Instant;
+
+mod commons;
+use commons::example_lib_path;
+
+#[derive(WrapperApi)]
+struct Api {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+    rust_fun_add_one: fn(arg: i32) -> i32,
+}
+
+#[derive(WrapperApi)]
+struct MissingApi {
+    c_fun_add_two_not_found: unsafe extern "C" fn(arg: c_int),
+}
+
+#[test]
+fn load_with_metrics() {
+    let lib_path = example_lib_path();
+    //the measured phases are part of the whole call
+    let start = Instant::now();
+    let (cont, metrics): (Container<Api>, _) = unsafe { Container::load_with_metrics(&lib_path) }
+        .expect("Could not open library or load symbols");
+    let elapsed = start.elapsed();
+    assert_eq!(unsafe { cont.c_fun_add_two(2) }, 4);
+    assert_eq!(metrics.total(), metrics.open + metrics.symbols);
+    assert!(metrics.total() <= elapsed);
+
+    let start = Instant::now();
+    let (cont, metrics): (OptionalContainer<Api, MissingApi>, _) =
+        unsafe { OptionalContainer::load_with_metrics(&lib_path) }
+            .expect("Could not open library or load symbols");
+    let elapsed = start.elapsed();
+    assert!(cont.optional().is_none());
+    assert_eq!(metrics.total(), metrics.open + metrics.symbols);
+    assert!(metrics.total() <= elapsed);
+}
@@ -0,0 +1,19 @@
+#![cfg(feature = "open_count")]
+
+use dlopen2::raw::{Library, open_count};
+
+mod commons;
+use commons::example_lib_path;
+
+#[test]
+fn open_count_of_example_library() {
+    let lib_path = example_lib_path();
+    assert_eq!(open_count(&lib_path), 0);
+    let lib = Library::open(&lib_path).expect("Could not open library");
+    let another = Library::open(&lib_path).expect("Could not open library");
+    assert_eq!(open_count(&lib_path), 2);
+    drop(another);
+    assert_eq!(open_count(&lib_path), 1);
+    drop(lib);
+    assert_eq!(open_count(&lib_path), 0);
+}
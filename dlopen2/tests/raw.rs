@@ -1,6 +1,9 @@
-use dlopen2::raw::{AddressInfoObtainer, Library};
+use dlopen2::raw::{
+    AddressInfoObtainer, Handle, LibRef, Library, NameBuffer, RawHandle, SendSymbol, open_dir,
+};
 use std::ffi::CStr;
 use std::os::raw::{c_char, c_int};
+use std::sync::LazyLock;
 
 mod commons;
 use commons::{SomeData, example_lib_path};
@@ -57,12 +60,112 @@ fn example_address_info() {
         unsafe { lib.symbol("c_fun_add_two") }.unwrap();
     let aio = AddressInfoObtainer::new();
     let ai = unsafe { aio.obtain(c_fun_add_two as *const ()) }.unwrap();
+    let cloned = ai.clone();
+    assert_eq!(cloned, ai);
     assert_eq!(&ai.dll_path, lib_path.to_str().unwrap());
+    assert_eq!(ai.offset_from_symbol, 0);
     let os = ai.overlapping_symbol.unwrap();
     assert_eq!(os.name, "c_fun_add_two");
     assert_eq!(os.addr, c_fun_add_two as *const ())
 }
 
+#[test]
+#[cfg(all(unix, feature = "demangle"))]
+fn demangled_address_info() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(&lib_path).expect("Could not open library");
+    let rust_fun_mangled: fn() -> i32 =
+        unsafe { lib.symbol("_ZN13example_dylib11mangled_fun17h0123456789abcdefE") }.unwrap();
+    assert_eq!(rust_fun_mangled(), 7);
+    let aio = AddressInfoObtainer::builder().demangle(true).build();
+    let ai = unsafe { aio.obtain(rust_fun_mangled as *const ()) }.unwrap();
+    assert_eq!(
+        ai.overlapping_symbol.unwrap().name,
+        "example_dylib::mangled_fun"
+    );
+    let ai = unsafe { AddressInfoObtainer::new().obtain(rust_fun_mangled as *const ()) }.unwrap();
+    assert_eq!(
+        ai.overlapping_symbol.unwrap().name,
+        "_ZN13example_dylib11mangled_fun17h0123456789abcdefE"
+    );
+}
+
+#[test]
+#[cfg(all(unix, feature = "demangle"))]
+fn demangled_symbol_name() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(&lib_path).expect("Could not open library");
+    let rust_fun_mangled: fn() -> i32 =
+        unsafe { lib.symbol("_ZN13example_dylib11mangled_fun17h0123456789abcdefE") }.unwrap();
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    let aio = AddressInfoObtainer::new();
+    let os = unsafe { aio.obtain(rust_fun_mangled as *const ()) }
+        .unwrap()
+        .overlapping_symbol
+        .unwrap();
+    assert_eq!(
+        os.name,
+        "_ZN13example_dylib11mangled_fun17h0123456789abcdefE"
+    );
+    assert_eq!(
+        os.demangled_name().as_deref(),
+        Some("example_dylib::mangled_fun")
+    );
+    let os = unsafe { aio.obtain(c_fun_add_two as *const ()) }
+        .unwrap()
+        .overlapping_symbol
+        .unwrap();
+    assert_eq!(os.demangled_name(), None);
+}
+
+#[test]
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+fn inspect_exports() {
+    use dlopen2::raw::InspectedLibrary;
+
+    let lib_path = example_lib_path();
+    let lib = InspectedLibrary::open(&lib_path).expect("Could not inspect library");
+    assert!(lib.has_export("c_fun_add_two"));
+    assert!(lib.has_export("rust_fun_add_one"));
+    assert!(lib.has_export("rust_i32"));
+    assert!(!lib.has_export("rust_i32_not_exported"));
+    assert!(lib.exports().any(|name| name == "c_int"));
+    assert!(InspectedLibrary::open(lib_path.with_file_name("not_existing_library")).is_err());
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn inspect_with_missing_dependency() {
+    use dlopen2::raw::InspectedLibrary;
+
+    //rename the libc dependency of a copy of the library, so that it can't be loaded
+    let lib_path = example_lib_path();
+    let mut data = std::fs::read(&lib_path).unwrap();
+    let needle = b"libc.so.6";
+    let mut renamed = false;
+    for start in 0..data.len() - needle.len() {
+        if &data[start..start + needle.len()] == needle {
+            data[start + 3] = b'q';
+            renamed = true;
+        }
+    }
+    assert!(renamed);
+    let dir = std::env::temp_dir().join(format!("dlopen2-inspect-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let copy_path = dir.join(lib_path.file_name().unwrap());
+    std::fs::write(&copy_path, data).unwrap();
+    let opened = Library::open(&copy_path);
+    let inspected = InspectedLibrary::open(&copy_path);
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(opened.is_err());
+    assert!(
+        inspected
+            .expect("Could not inspect library")
+            .has_export("c_fun_add_two")
+    );
+}
+
 // https://github.com/OpenByteDev/dlopen2/issues/3
 #[test]
 #[cfg(windows)]
@@ -76,3 +179,1264 @@ fn double_sym_init_does_not_panic() {
     // Panics because SymInitializeW returns an error
     unsafe { AddressInfoObtainer::new().obtain(pointer) }.unwrap();
 }
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn promote_to_global() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    lib.promote_to_global().expect("Could not promote library");
+    let program = Library::open_self().expect("Could not open the program itself");
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { program.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+}
+
+#[test]
+fn has_symbol() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    assert!(lib.has_symbol_cstr(c"c_fun_add_two"));
+    assert!(!lib.has_symbol_cstr(c"c_fun_made_up"));
+    assert!(lib.has_symbol("rust_i32"));
+    assert!(!lib.has_symbol("rust_made_up"));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn open_from_fd() {
+    use std::os::fd::AsFd;
+
+    let lib_path = example_lib_path();
+    let file = std::fs::File::open(lib_path).expect("Could not open library file");
+    let lib = Library::open_from_fd(file.as_fd(), None).expect("Could not open library");
+    drop(file);
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+}
+
+#[test]
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+fn symbol_local() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let rust_fun_add_one: fn(i32) -> i32 = unsafe { lib.symbol_local("rust_fun_add_one") }.unwrap();
+    assert_eq!(rust_fun_add_one(5), 6);
+    //malloc is visible through the handle because libc is a dependency of the library,
+    //but it is not defined by the library itself
+    #[cfg(unix)]
+    {
+        assert!(unsafe { lib.symbol::<*const ()>("malloc") }.is_ok());
+        assert!(matches!(
+            unsafe { lib.symbol_local::<*const ()>("malloc") },
+            Err(dlopen2::Error::SymbolGettingError(_))
+        ));
+    }
+}
+
+#[test]
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+fn library_initializers() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    //the Rust standard library registers initializers of its own
+    assert!(
+        lib.has_initializers()
+            .expect("Could not inspect the library")
+    );
+}
+
+#[test]
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+fn exported_symbols() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let symbols = lib.exported_symbols().expect("Could not list symbols");
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    let enumerated = symbols
+        .iter()
+        .find(|symbol| symbol.name == "c_fun_add_two")
+        .expect("c_fun_add_two should be exported");
+    assert_eq!(enumerated.addr, c_fun_add_two as *const ());
+    let rust_i32: *const i32 = unsafe { lib.symbol("rust_i32") }.unwrap();
+    assert!(
+        symbols
+            .iter()
+            .any(|symbol| symbol.name == "rust_i32" && symbol.addr == rust_i32 as *const ())
+    );
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn symbol_in_scope() {
+    use dlopen2::raw::{symbol_default, symbol_default_cstr, symbol_next, symbol_next_cstr};
+
+    let libc = Library::open("libc.so.6").expect("Could not open libc");
+    let malloc: *const () = unsafe { libc.symbol("malloc") }.unwrap();
+    let default: *const () = unsafe { symbol_default_cstr(c"malloc") }.unwrap();
+    assert_eq!(default, malloc);
+    //the test executable does not define malloc, so the next definition is the one of libc
+    let next: *const () = unsafe { symbol_next_cstr(c"malloc") }.unwrap();
+    assert_eq!(next, malloc);
+    assert_eq!(
+        unsafe { symbol_default::<*const ()>("malloc") }.unwrap(),
+        malloc
+    );
+    assert_eq!(
+        unsafe { symbol_next::<*const ()>("malloc") }.unwrap(),
+        malloc
+    );
+    assert!(matches!(
+        unsafe { symbol_default::<*const ()>("mal\0loc") },
+        Err(dlopen2::Error::NullCharacter(_))
+    ));
+    assert!(matches!(
+        unsafe { symbol_default_cstr::<*const ()>(c"malloc_not_existing") },
+        Err(dlopen2::Error::SymbolGettingError(_))
+    ));
+    assert!(matches!(
+        unsafe { symbol_default_cstr::<*const ()>(c"errno") },
+        Err(dlopen2::Error::ThreadLocalSymbol)
+    ));
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn library_info() {
+    use dlopen2::raw::{DlInfoRequest, DlInfoValue};
+
+    let lib_path = example_lib_path();
+    let lib = Library::open(&lib_path).expect("Could not open library");
+    let expected_dir = lib_path.parent().unwrap().canonicalize().unwrap();
+    match lib.info(DlInfoRequest::Origin).unwrap() {
+        DlInfoValue::Origin(dir) => assert_eq!(dir.canonicalize().unwrap(), expected_dir),
+        other => panic!("Unexpected info {other:?}"),
+    }
+    match lib.info(DlInfoRequest::LinkMap).unwrap() {
+        DlInfoValue::LinkMap { base_addr, path } => {
+            assert!(!base_addr.is_null());
+            assert_eq!(path.file_name(), lib_path.file_name());
+        }
+        other => panic!("Unexpected info {other:?}"),
+    }
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn thread_local_symbol() {
+    //errno is a thread-local variable exported by glibc
+    let lib = Library::open("libc.so.6").expect("Could not open libc");
+    assert!(matches!(
+        unsafe { lib.symbol::<*mut c_int>("errno") },
+        Err(dlopen2::Error::ThreadLocalSymbol)
+    ));
+    assert!(unsafe { lib.symbol::<*const ()>("malloc") }.is_ok());
+    assert!(lib.has_symbol("errno"));
+
+    //the example library finds errno through its libc dependency
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    assert!(matches!(
+        unsafe { lib.symbol::<*mut c_int>("errno") },
+        Err(dlopen2::Error::ThreadLocalSymbol)
+    ));
+    assert!(lib.has_symbol("errno"));
+    let errno: &c_int = unsafe { lib.symbol_tls("errno") }.unwrap();
+    assert_eq!(errno as *const c_int, unsafe { libc::__errno_location() }
+        as *const c_int);
+}
+
+#[test]
+#[cfg(unix)]
+fn open_non_utf8_path() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let lib_path = example_lib_path();
+    let dir = std::env::temp_dir().join(format!("dlopen2-non-utf8-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let copy_path = dir.join(OsStr::from_bytes(b"libexample_\xff\xfe.so"));
+    std::fs::copy(&lib_path, &copy_path).unwrap();
+    let result = Library::open(&copy_path);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let lib = result.expect("Could not open library with a non-UTF-8 path");
+    let rust_fun_add_one: fn(i32) -> i32 = unsafe { lib.symbol("rust_fun_add_one") }.unwrap();
+    assert_eq!(rust_fun_add_one(5), 6);
+
+    //a null character would otherwise silently truncate the path
+    let mut truncated = lib_path.into_os_string();
+    truncated.push("\0.bak");
+    assert!(matches!(
+        Library::open(&truncated),
+        Err(dlopen2::Error::InvalidLibraryName(_))
+    ));
+}
+
+#[test]
+fn invalid_library_names() {
+    for name in ["", "  \t", "libexample\0.so"] {
+        match Library::open(name) {
+            Err(dlopen2::Error::InvalidLibraryName(rejected)) => assert_eq!(rejected, name),
+            _ => panic!("Library name {name:?} should be rejected"),
+        }
+        assert!(matches!(
+            Library::open_in(name, &mut NameBuffer::new()),
+            Err(dlopen2::Error::InvalidLibraryName(_))
+        ));
+    }
+}
+
+#[test]
+fn drop_while_unwinding() {
+    //use copies so that no other test has them loaded
+    let lib_path = example_lib_path();
+    let dir = std::env::temp_dir().join(format!("dlopen2-unwinding-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for close_on_unwind in [true, false] {
+        let copy_path = dir.join(format!(
+            "{close_on_unwind}_{}",
+            lib_path.file_name().unwrap().to_string_lossy()
+        ));
+        std::fs::copy(&lib_path, &copy_path).unwrap();
+        let open_path = copy_path.clone();
+        let result = std::panic::catch_unwind(move || {
+            let mut lib = Library::open(open_path).expect("Could not open library");
+            lib.set_close_on_unwind(close_on_unwind);
+            let rust_fun_add_one: fn(i32) -> i32 =
+                unsafe { lib.symbol("rust_fun_add_one") }.unwrap();
+            assert_eq!(rust_fun_add_one(5), 6);
+            panic!("unwinding with an open library");
+        });
+        let message = *result.unwrap_err().downcast::<&str>().unwrap();
+        assert_eq!(message, "unwinding with an open library");
+        //a library that was kept loaded is not loaded again
+        let reopened = Library::open(&copy_path).expect("Could not open library");
+        assert_eq!(reopened.was_newly_loaded(), close_on_unwind);
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn symbol_macro() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let c_fun_add_two =
+        unsafe { dlopen2::symbol!(lib, "c_fun_add_two": unsafe extern "C" fn(c_int) -> c_int) }
+            .unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+    let rust_i32: &i32 = unsafe { dlopen2::symbol!(lib, "rust_i32") }.unwrap();
+    assert_eq!(*rust_i32, 43);
+    let lib_ref = &lib;
+    assert!(unsafe { dlopen2::symbol!(lib_ref, "rust_i32_mut": &i32) }.is_ok());
+    assert!(unsafe { dlopen2::symbol!(lib, "rust_made_up": *const ()) }.is_err());
+}
+
+#[test]
+fn newly_loaded() {
+    //use a copy so that no other test has it loaded
+    let lib_path = example_lib_path();
+    let dir = std::env::temp_dir().join(format!("dlopen2-newly-loaded-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let copy_path = dir.join(lib_path.file_name().unwrap());
+    std::fs::copy(&lib_path, &copy_path).unwrap();
+    let first = Library::open(&copy_path);
+    let second = Library::open(&copy_path);
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(first.expect("Could not open library").was_newly_loaded());
+    assert!(!second.expect("Could not open library").was_newly_loaded());
+    assert!(!Library::open_self().unwrap().was_newly_loaded());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn architecture_mismatch() {
+    let dir = std::env::temp_dir().join(format!("dlopen2-arch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    //ELF header of a library with the opposite class
+    let mut other_class = [0u8; 64];
+    other_class[..4].copy_from_slice(b"\x7fELF");
+    other_class[4] = if cfg!(target_pointer_width = "64") {
+        1
+    } else {
+        2
+    };
+    other_class[5] = if cfg!(target_endian = "little") { 1 } else { 2 };
+    other_class[6] = 1;
+    //ELF header of a library of the right class, but for a made up machine
+    let mut other_machine = other_class;
+    other_machine[4] = if cfg!(target_pointer_width = "64") {
+        2
+    } else {
+        1
+    };
+    other_machine[16] = 3; //ET_DYN
+    other_machine[18] = 0xfe;
+    other_machine[19] = 0xfe;
+    other_machine[20] = 1; //EV_CURRENT
+    for (file_name, header) in [
+        ("other_class.so", other_class),
+        ("other_machine.so", other_machine),
+    ] {
+        let path = dir.join(file_name);
+        std::fs::write(&path, header).unwrap();
+        match Library::open(&path) {
+            Err(dlopen2::Error::ArchitectureMismatch(_)) => (),
+            Err(err) => panic!("Unexpected error for {file_name}: {err}"),
+            Ok(_) => panic!("Opening {file_name} should fail"),
+        }
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+    //a missing library is not an architecture mismatch
+    assert!(matches!(
+        Library::open(dir.join("missing.so")),
+        Err(dlopen2::Error::OpeningLibraryError(_))
+    ));
+}
+
+#[test]
+fn opening_error_message() {
+    let bogus = example_lib_path().with_file_name("libbogus_dependency_name.so");
+    match Library::open(&bogus) {
+        Err(dlopen2::Error::OpeningLibraryError(err)) => {
+            let message = err.to_string();
+            assert!(!message.is_empty());
+            #[cfg(unix)]
+            assert!(message.contains("libbogus_dependency_name.so"));
+            let display = dlopen2::Error::OpeningLibraryError(err).to_string();
+            assert!(display.contains(&message));
+        }
+        _ => panic!("Opening a bogus library should fail"),
+    }
+}
+
+#[test]
+fn flags_are_stored() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(&lib_path).expect("Could not open library");
+    assert_eq!(lib.flags(), None);
+    #[cfg(unix)]
+    let flags = libc::RTLD_NOW | libc::RTLD_LOCAL;
+    #[cfg(windows)]
+    let flags = 0;
+    let lib = Library::open_with_flags(&lib_path, Some(flags)).expect("Could not open library");
+    assert_eq!(lib.flags(), Some(flags));
+}
+
+#[test]
+fn open_now() {
+    let lib_path = example_lib_path();
+    let lib = Library::open_now(&lib_path).expect("Could not open library");
+    #[cfg(unix)]
+    assert_eq!(lib.flags(), Some(libc::RTLD_NOW | libc::RTLD_LOCAL));
+    #[cfg(windows)]
+    assert_eq!(lib.flags(), None);
+    let rust_fun_add_one: fn(i32) -> i32 =
+        unsafe { lib.symbol("rust_fun_add_one") }.expect("Could not get symbol");
+    assert_eq!(rust_fun_add_one(5), 6);
+}
+
+#[test]
+fn send_symbol_to_thread() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    let c_int_ptr: *const c_int = unsafe { lib.symbol("c_int") }.unwrap();
+    let c_fun_add_two = unsafe { SendSymbol::new(c_fun_add_two) };
+    let c_int_ptr = unsafe { SendSymbol::new(c_int_ptr) };
+    let result = std::thread::spawn(move || unsafe { c_fun_add_two(*c_int_ptr.get()) })
+        .join()
+        .unwrap();
+    assert_eq!(result, 47);
+    drop(lib);
+}
+
+#[test]
+fn raw_handle_round_trip() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(&lib_path).expect("Could not open library");
+    let raw = RawHandle::from(unsafe { lib.into_raw() });
+    std::mem::forget(lib);
+    let handle = std::thread::spawn(move || raw).join().unwrap();
+    assert_eq!(handle, raw);
+    let lib = unsafe { Library::from_raw(handle) };
+    assert_eq!(Handle::from(raw), unsafe { lib.into_raw() });
+    assert_eq!(lib.flags(), None);
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", windows))]
+fn protection_of_symbols() {
+    use dlopen2::raw::symbol_protection;
+
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    let protection = symbol_protection(c_fun_add_two as *const ()).unwrap();
+    assert!(protection.executable);
+    assert!(!protection.writable);
+    let rust_i32_mut: *mut i32 = unsafe { lib.symbol("rust_i32_mut") }.unwrap();
+    let protection = symbol_protection(rust_i32_mut as *const ()).unwrap();
+    assert!(protection.readable && protection.writable && !protection.executable);
+}
+
+#[test]
+fn open_libraries_in_dir() {
+    use dlopen2::utils::platform_file_name;
+
+    let lib_path = example_lib_path();
+    let dir = std::env::temp_dir().join(format!("dlopen2-open-dir-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy(&lib_path, dir.join(platform_file_name("example"))).unwrap();
+    std::fs::write(
+        dir.join(platform_file_name("example_broken")),
+        b"not a library",
+    )
+    .unwrap();
+    std::fs::write(dir.join(platform_file_name("other")), b"not a library").unwrap();
+    std::fs::write(dir.join("example.txt"), b"not a library").unwrap();
+    let all = open_dir(&dir, "*", None).expect("Could not read the directory");
+    let matching = open_dir(&dir, "exam?le*", None).expect("Could not read the directory");
+    let exact = open_dir(&dir, "example", None).expect("Could not read the directory");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(all.len(), 3);
+    let names: Vec<_> = matching
+        .iter()
+        .map(|(path, _)| path.file_name().unwrap().to_owned())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            platform_file_name("example"),
+            platform_file_name("example_broken")
+        ]
+    );
+    let lib = matching[0].1.as_ref().expect("Could not open library");
+    let rust_fun_add_one: fn(i32) -> i32 = unsafe { lib.symbol("rust_fun_add_one") }.unwrap();
+    assert_eq!(rust_fun_add_one(5), 6);
+    assert!(matching[1].1.is_err());
+    assert_eq!(exact.len(), 1);
+    assert!(open_dir(dir.join("not_existing"), "*", None).is_err());
+}
+
+#[test]
+fn leak_and_symbol() {
+    static ADD_ONE: LazyLock<fn(i32) -> i32> = LazyLock::new(|| {
+        let lib = Library::open(example_lib_path()).expect("Could not open library");
+        unsafe { lib.leak_and_symbol_cstr(c"rust_fun_add_one") }.expect("Could not get symbol")
+    });
+    assert_eq!(ADD_ONE(5), 6);
+
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    let rust_i32: &'static i32 =
+        unsafe { lib.leak_and_symbol("rust_i32") }.expect("Could not get symbol");
+    assert_eq!(*rust_i32, 43);
+}
+
+#[test]
+fn open_first_candidate() {
+    let lib_path = example_lib_path();
+    let bogus = lib_path.with_file_name("libnot_existing_library.so");
+    let lib = Library::open_first(&[bogus.as_path(), lib_path.as_path()], None)
+        .expect("Could not open library");
+    let rust_fun_add_one: fn(i32) -> i32 = unsafe { lib.symbol("rust_fun_add_one") }.unwrap();
+    assert_eq!(rust_fun_add_one(5), 6);
+
+    match Library::open_first(&[bogus.as_path(), bogus.as_path()], None) {
+        Err(dlopen2::Error::NoCandidateOpened(failures)) => {
+            assert_eq!(failures.len(), 2);
+            assert_eq!(failures[0].0, bogus.as_os_str());
+        }
+        _ => panic!("Expected opening all candidates to fail"),
+    }
+}
+
+#[test]
+fn open_in_reuses_buffer() {
+    let lib_path = example_lib_path();
+    let mut buffer = NameBuffer::new();
+    let lib = Library::open_in(&lib_path, &mut buffer).expect("Could not open library");
+    let rust_fun_add_one: fn(i32) -> i32 =
+        unsafe { lib.symbol_in("rust_fun_add_one", &mut buffer) }.unwrap();
+    assert_eq!(rust_fun_add_one(5), 6);
+    let capacity = buffer.capacity();
+    assert!(capacity > 0);
+    for _ in 0..10 {
+        let lib = Library::open_in(&lib_path, &mut buffer).expect("Could not open library");
+        let rust_fun_add_one: fn(i32) -> i32 =
+            unsafe { lib.symbol_in("rust_fun_add_one", &mut buffer) }.unwrap();
+        assert_eq!(rust_fun_add_one(5), 6);
+    }
+    assert_eq!(buffer.capacity(), capacity);
+    assert!(capacity >= "rust_fun_add_one".len());
+    assert_eq!(NameBuffer::new().capacity(), 0);
+    assert!(NameBuffer::with_capacity(64).capacity() >= 64);
+    assert!(matches!(
+        unsafe { lib.symbol_in::<*const ()>("rust_fun\0add_one", &mut buffer) },
+        Err(dlopen2::Error::NullCharacter(_))
+    ));
+}
+
+#[test]
+fn lib_ref_in_struct() {
+    struct Plugin<'a> {
+        lib: &'a Library,
+        rust_i32: LibRef<'a, i32>,
+        c_struct: LibRef<'a, SomeData>,
+    }
+
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let plugin = Plugin {
+        lib: &lib,
+        rust_i32: unsafe { lib.reference("rust_i32") }.unwrap(),
+        c_struct: unsafe { lib.reference_cstr(c"c_struct") }.unwrap(),
+    };
+    assert_eq!(43, *plugin.rust_i32);
+    assert_eq!(1, plugin.c_struct.first);
+    assert_eq!(2, plugin.c_struct.second);
+    assert!(plugin.lib.has_symbol("rust_i32"));
+}
+
+#[test]
+#[cfg(feature = "checksum")]
+fn open_verified() {
+    use dlopen2::Error;
+    use sha2::{Digest, Sha256};
+
+    let lib_path = example_lib_path();
+    let hash: [u8; 32] = Sha256::digest(std::fs::read(&lib_path).unwrap()).into();
+    let lib = Library::open_verified(&lib_path, &hash, None).expect("Could not open library");
+    assert!(lib.has_symbol("c_fun_add_two"));
+
+    let mut wrong_hash = hash;
+    wrong_hash[0] ^= 0xff;
+    match Library::open_verified(&lib_path, &wrong_hash, None) {
+        Err(Error::ChecksumMismatch) => (),
+        _ => panic!("Library with a wrong checksum should not get opened"),
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn open_async() {
+    let lib_path = example_lib_path();
+    let lib = Library::open_async(lib_path, None)
+        .await
+        .expect("Could not open library");
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+}
+
+// naked functions are not available with the minimum supported Rust version
+#[cfg(target_arch = "x86_64")]
+mod jump_thunks {
+    //the target is defined in assembly too, so there is exactly one copy of it
+    //and its address can be compared, which is not guaranteed for Rust functions
+    static TARGET: extern "C" fn() -> i32 = thunk_target;
+
+    // symbols of C functions get an underscore prefix on Apple platforms
+    macro_rules! define_thunks {
+        ($prefix:literal) => {
+            std::arch::global_asm!(
+                concat!(".globl ", $prefix, "dlopen2_test_thunk_target"),
+                concat!($prefix, "dlopen2_test_thunk_target:"),
+                "mov eax, 42",
+                "ret",
+                concat!(".globl ", $prefix, "dlopen2_test_indirect_thunk"),
+                concat!($prefix, "dlopen2_test_indirect_thunk:"),
+                "jmp qword ptr [rip + {target}]",
+                concat!(".globl ", $prefix, "dlopen2_test_direct_thunk"),
+                concat!($prefix, "dlopen2_test_direct_thunk:"),
+                concat!("jmp ", $prefix, "dlopen2_test_thunk_target"),
+                target = sym TARGET,
+            );
+        };
+    }
+    #[cfg(target_vendor = "apple")]
+    define_thunks!("_");
+    #[cfg(not(target_vendor = "apple"))]
+    define_thunks!("");
+
+    unsafe extern "C" {
+        #[link_name = "dlopen2_test_thunk_target"]
+        pub safe fn thunk_target() -> i32;
+        #[link_name = "dlopen2_test_indirect_thunk"]
+        pub safe fn indirect_thunk() -> i32;
+        #[link_name = "dlopen2_test_direct_thunk"]
+        pub safe fn direct_thunk() -> i32;
+    }
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn resolve_jump_thunks() {
+    use dlopen2::raw::resolve_thunk;
+    use jump_thunks::{direct_thunk, indirect_thunk, thunk_target};
+
+    assert_eq!(indirect_thunk(), 42);
+    assert_eq!(direct_thunk(), 42);
+    assert_eq!(thunk_target(), 42);
+    let target = thunk_target as *const ();
+    assert_eq!(
+        unsafe { resolve_thunk(indirect_thunk as *const ()) }.unwrap(),
+        target
+    );
+    assert_eq!(
+        unsafe { resolve_thunk(direct_thunk as *const ()) }.unwrap(),
+        target
+    );
+    assert_eq!(unsafe { resolve_thunk(target) }.unwrap(), target);
+    assert!(matches!(
+        unsafe { resolve_thunk(std::ptr::null()) },
+        Err(dlopen2::Error::NullSymbol)
+    ));
+}
+
+#[test]
+fn open_with_working_directory() {
+    use dlopen2::utils::platform_file_name;
+
+    let lib_path = example_lib_path();
+    let dir = std::env::temp_dir().join(format!("dlopen2-cwd-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let name = platform_file_name("example_in_cwd");
+    let other_name = platform_file_name("example_in_cwd_other");
+    std::fs::copy(&lib_path, dir.join(&name)).unwrap();
+    std::fs::copy(&lib_path, dir.join(&other_name)).unwrap();
+    let before = Library::open(&name);
+    let opened = Library::open_with_cwd(&name, &dir, None);
+    //the directory must not affect loads done afterwards
+    let after = Library::open(&other_name);
+    let result = opened.map(|lib| {
+        let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+            unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+        unsafe { c_fun_add_two(2) }
+    });
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(before.is_err());
+    assert_eq!(result.expect("Could not open library in directory"), 4);
+    assert!(after.is_err());
+}
+
+//Builds a DLL without code that imports `function` from `dll`, so that it can only be loaded
+//if the loader finds the dependency.
+#[cfg(all(windows, target_arch = "x86_64"))]
+fn dll_importing(dll: &str, function: &str) -> Vec<u8> {
+    fn put(image: &mut [u8], offset: usize, bytes: &[u8]) {
+        image[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+    const SECTION_RVA: u32 = 0x1000;
+    const SECTION_OFFSET: usize = 0x200;
+    let mut image = vec![0u8; 0x400];
+    put(&mut image, 0, b"MZ");
+    put(&mut image, 0x3c, &0x40u32.to_le_bytes());
+    put(&mut image, 0x40, b"PE\0\0");
+    //file header: AMD64, one section, size of the optional header, DLL | LARGE_ADDRESS_AWARE | EXECUTABLE_IMAGE
+    put(&mut image, 0x44, &0x8664u16.to_le_bytes());
+    put(&mut image, 0x46, &1u16.to_le_bytes());
+    put(&mut image, 0x54, &240u16.to_le_bytes());
+    put(&mut image, 0x56, &0x2022u16.to_le_bytes());
+    //optional header (PE32+)
+    let opt = 0x58;
+    put(&mut image, opt, &0x20bu16.to_le_bytes());
+    put(&mut image, opt + 8, &0x200u32.to_le_bytes()); //SizeOfInitializedData
+    put(&mut image, opt + 20, &SECTION_RVA.to_le_bytes()); //BaseOfCode
+    put(&mut image, opt + 24, &0x1_8000_0000u64.to_le_bytes()); //ImageBase
+    put(&mut image, opt + 32, &0x1000u32.to_le_bytes()); //SectionAlignment
+    put(&mut image, opt + 36, &0x200u32.to_le_bytes()); //FileAlignment
+    put(&mut image, opt + 40, &6u16.to_le_bytes()); //MajorOperatingSystemVersion
+    put(&mut image, opt + 48, &6u16.to_le_bytes()); //MajorSubsystemVersion
+    put(&mut image, opt + 56, &0x2000u32.to_le_bytes()); //SizeOfImage
+    put(&mut image, opt + 60, &0x200u32.to_le_bytes()); //SizeOfHeaders
+    put(&mut image, opt + 68, &2u16.to_le_bytes()); //Subsystem: GUI
+    put(&mut image, opt + 70, &0x160u16.to_le_bytes()); //NX_COMPAT | DYNAMIC_BASE | HIGH_ENTROPY_VA
+    put(&mut image, opt + 72, &0x10_0000u64.to_le_bytes()); //SizeOfStackReserve
+    put(&mut image, opt + 80, &0x1000u64.to_le_bytes()); //SizeOfStackCommit
+    put(&mut image, opt + 88, &0x10_0000u64.to_le_bytes()); //SizeOfHeapReserve
+    put(&mut image, opt + 96, &0x1000u64.to_le_bytes()); //SizeOfHeapCommit
+    put(&mut image, opt + 108, &16u32.to_le_bytes()); //NumberOfRvaAndSizes
+
+    //the section contains the import descriptors, the lookup and address tables and the names
+    let descriptors = SECTION_RVA;
+    let lookup_table = descriptors + 40;
+    let address_table = lookup_table + 16;
+    let hint_name = address_table + 16;
+    let dll_name = hint_name + 2 + (function.len() as u32 + 2) / 2 * 2;
+    put(&mut image, opt + 120, &descriptors.to_le_bytes()); //import directory
+    put(&mut image, opt + 124, &40u32.to_le_bytes());
+    put(&mut image, opt + 208, &address_table.to_le_bytes()); //import address table directory
+    put(&mut image, opt + 212, &16u32.to_le_bytes());
+    let section = opt + 240;
+    put(&mut image, section, b".idata\0\0");
+    put(&mut image, section + 8, &0x200u32.to_le_bytes()); //VirtualSize
+    put(&mut image, section + 12, &SECTION_RVA.to_le_bytes());
+    put(&mut image, section + 16, &0x200u32.to_le_bytes()); //SizeOfRawData
+    put(
+        &mut image,
+        section + 20,
+        &(SECTION_OFFSET as u32).to_le_bytes(),
+    );
+    put(&mut image, section + 36, &0xc000_0040u32.to_le_bytes()); //initialized data, read, write
+
+    let at = |rva: u32| SECTION_OFFSET + (rva - SECTION_RVA) as usize;
+    put(&mut image, at(descriptors), &lookup_table.to_le_bytes());
+    put(&mut image, at(descriptors) + 12, &dll_name.to_le_bytes());
+    put(
+        &mut image,
+        at(descriptors) + 16,
+        &address_table.to_le_bytes(),
+    );
+    put(
+        &mut image,
+        at(lookup_table),
+        &(hint_name as u64).to_le_bytes(),
+    );
+    put(
+        &mut image,
+        at(address_table),
+        &(hint_name as u64).to_le_bytes(),
+    );
+    put(&mut image, at(hint_name) + 2, function.as_bytes());
+    put(&mut image, at(dll_name), dll.as_bytes());
+    image
+}
+
+#[test]
+#[cfg(all(windows, target_arch = "x86_64"))]
+fn open_with_working_directory_resolves_dependencies() {
+    let dir = std::env::temp_dir().join(format!("dlopen2-cwd-deps-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    //the plugin imports a function of a copy of the example library, which is only
+    //found in the directory
+    std::fs::copy(example_lib_path(), dir.join("dlopen2_cwd_dependency.dll")).unwrap();
+    let plugin = "dlopen2_cwd_plugin.dll";
+    std::fs::write(
+        dir.join(plugin),
+        dll_importing("dlopen2_cwd_dependency.dll", "c_fun_add_two"),
+    )
+    .unwrap();
+    //without the directory the dependency is not searched for next to the plugin
+    let without_dir = Library::open(dir.join(plugin)).map(drop);
+    let with_dir = Library::open_with_cwd(plugin, &dir, None).map(drop);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(matches!(
+        without_dir,
+        Err(dlopen2::Error::OpeningLibraryError(_))
+    ));
+    with_dir.expect("The dependency should be found in the directory");
+}
+
+#[test]
+#[cfg(windows)]
+fn open_from_resource() {
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::um::winbase::{BeginUpdateResourceW, EndUpdateResourceW, UpdateResourceW};
+
+    //embed the example library into a copy of itself as RT_RCDATA resource 1
+    let lib_path = example_lib_path();
+    let host_path =
+        std::env::temp_dir().join(format!("dlopen2-resource-host-{}.dll", std::process::id()));
+    std::fs::copy(&lib_path, &host_path).unwrap();
+    let bytes = std::fs::read(&lib_path).unwrap();
+    let wide_path: Vec<u16> = host_path.as_os_str().encode_wide().chain(Some(0)).collect();
+    unsafe {
+        let update = BeginUpdateResourceW(wide_path.as_ptr(), 0);
+        assert!(!update.is_null());
+        assert_ne!(
+            UpdateResourceW(
+                update,
+                std::ptr::without_provenance(10),
+                std::ptr::without_provenance(1),
+                0,
+                bytes.as_ptr() as *mut _,
+                bytes.len() as u32,
+            ),
+            0
+        );
+        assert_ne!(EndUpdateResourceW(update, 0), 0);
+    }
+
+    let host = Library::open(&host_path).expect("Could not open host library");
+    let lib = Library::open_from_resource(&host, 1, None).expect("Could not open resource");
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+    let prefix = format!("dlopen2-{}-", std::process::id());
+    let extracted_files = || {
+        std::fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .filter(|entry| {
+                let name = entry.as_ref().unwrap().file_name();
+                name.to_string_lossy().starts_with(&prefix)
+            })
+            .count()
+    };
+    assert_eq!(extracted_files(), 1);
+    drop(lib);
+    assert_eq!(extracted_files(), 0);
+    assert!(Library::open_from_resource(&host, 2, None).is_err());
+    drop(host);
+    let _ = std::fs::remove_file(&host_path);
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn open_executable() {
+    //the test binary itself is a position-independent executable, which glibc refuses to open
+    let exe = std::env::current_exe().unwrap();
+    match Library::open(&exe) {
+        Err(dlopen2::Error::ExecutableNotLoadable(err)) => {
+            assert!(err.to_string().contains("executable"))
+        }
+        Err(err) => panic!("Unexpected error opening an executable: {err}"),
+        Ok(_) => panic!("Opening an executable should fail"),
+    }
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn library_soname() {
+    let libc = Library::open("libc.so.6").expect("Could not open libc");
+    assert_eq!(libc.soname().unwrap().as_deref(), Some("libc.so.6"));
+    //cargo doesn't assign a SONAME to cdylibs
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    assert_eq!(lib.soname().unwrap(), None);
+}
+
+#[test]
+fn validate_library() {
+    let lib_path = example_lib_path();
+    Library::validate(
+        &lib_path,
+        &[c"c_fun_add_two", c"rust_fun_add_one", c"rust_i32"],
+    )
+    .expect("The example library should be valid");
+    Library::validate(&lib_path, &[]).expect("The example library should be valid");
+    assert!(matches!(
+        Library::validate(&lib_path, &[c"c_fun_add_two", c"rust_fun_not_found"]),
+        Err(dlopen2::Error::SymbolGettingError(_))
+    ));
+    assert!(matches!(
+        Library::validate("libdlopen2_validate_missing.so", &[c"c_fun_add_two"]),
+        Err(dlopen2::Error::OpeningLibraryError(_))
+    ));
+}
+
+#[test]
+fn string_table() {
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    let table = unsafe { lib.symbol_string_table_cstr(c"c_string_table") }.unwrap();
+    assert_eq!(table, [c"first", c"second", c"third"]);
+    let empty = unsafe { lib.symbol_string_table("c_string_table_empty") }.unwrap();
+    assert!(empty.is_empty());
+    assert!(unsafe { lib.symbol_string_table("c_string_table_not_found") }.is_err());
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn open_through_loader() {
+    #[cfg(target_os = "linux")]
+    let libdl = Library::open("libdl.so.2").expect("Could not open libdl");
+    #[cfg(target_os = "android")]
+    let libdl = Library::open("libdl.so").expect("Could not open libdl");
+    let loader = libdl.as_loader().expect("Could not obtain dlopen");
+    let lib = loader
+        .open(example_lib_path(), None)
+        .expect("Could not open library through the loader");
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+    assert!(matches!(
+        loader.open("libdlopen2_loader_missing.so", None),
+        Err(dlopen2::Error::OpeningLibraryError(_))
+    ));
+    // an empty name would otherwise return the handle of the program
+    assert!(matches!(
+        loader.open("", None),
+        Err(dlopen2::Error::InvalidLibraryName(_))
+    ));
+}
+
+#[test]
+fn case_insensitive_symbol() {
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol_case_insensitive("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+    #[cfg(windows)]
+    {
+        let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+            unsafe { lib.symbol_case_insensitive_cstr(c"C_Fun_Add_Two") }.unwrap();
+        assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+    }
+    #[cfg(unix)]
+    assert!(unsafe { lib.symbol_case_insensitive::<*const ()>("C_Fun_Add_Two") }.is_err());
+}
+
+#[test]
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+fn undefined_symbols() {
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    let undefined = lib.undefined_symbols().expect("Could not list symbols");
+    //the allocator of the standard library is imported from the system
+    #[cfg(unix)]
+    let imported = "malloc";
+    #[cfg(windows)]
+    let imported = "HeapAlloc";
+    assert!(undefined.iter().any(|name| name == imported));
+    assert!(!undefined.iter().any(|name| name == "c_fun_add_two"));
+}
+
+#[test]
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+fn default_search_paths() {
+    let paths = dlopen2::raw::default_search_paths().unwrap();
+    assert!(!paths.is_empty());
+    assert!(paths.iter().all(|path| !path.as_os_str().is_empty()));
+}
+
+#[test]
+fn atomic_symbol() {
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let counter: &AtomicI32 = unsafe { lib.symbol_atomic_cstr(c"c_int_mut") }.unwrap();
+    let initial = counter.load(Ordering::SeqCst);
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+    assert_eq!(counter.load(Ordering::SeqCst), initial + 400);
+    let same: &AtomicI32 = unsafe { lib.symbol_atomic("c_int_mut") }.unwrap();
+    assert_eq!(same.load(Ordering::SeqCst), initial + 400);
+}
+
+#[test]
+fn sized_slice() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let table: &[u16] =
+        unsafe { lib.symbol_sized_slice_cstr::<u16, u32>(c"c_u16_table", c"c_u16_table_len") }
+            .unwrap();
+    assert_eq!(table, &[1, 2, 3, 5, 8]);
+    let same: &[u16] =
+        unsafe { lib.symbol_sized_slice::<u16, u32>("c_u16_table", "c_u16_table_len") }.unwrap();
+    assert_eq!(table, same);
+    //a negative length does not fit into usize
+    let invalid =
+        unsafe { lib.symbol_sized_slice_cstr::<u16, i32>(c"c_u16_table", c"c_invalid_table_len") };
+    assert!(matches!(
+        invalid,
+        Err(dlopen2::Error::SymbolGettingError(_))
+    ));
+    let huge =
+        unsafe { lib.symbol_sized_slice_cstr::<u16, usize>(c"c_u16_table", c"c_huge_table_len") };
+    assert!(matches!(huge, Err(dlopen2::Error::SymbolGettingError(_))));
+    let missing =
+        unsafe { lib.symbol_sized_slice_cstr::<u16, u32>(c"c_u16_table", c"c_missing_len") };
+    assert!(missing.is_err());
+}
+
+#[test]
+#[cfg(windows)]
+fn file_version() {
+    //system libraries carry version resources
+    let kernel32 = Library::open("kernel32.dll").expect("Could not open kernel32");
+    let version = kernel32
+        .file_version()
+        .unwrap()
+        .expect("kernel32 has no version");
+    assert!(version.major >= 6);
+    assert!(kernel32.product_version().unwrap().is_some());
+    assert_eq!(version.to_string().split('.').count(), 4);
+
+    //Cargo does not add a version resource
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    assert_eq!(lib.file_version().unwrap(), None);
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu", target_arch = "x86_64"))]
+fn version_qualified_symbol() {
+    use std::os::raw::c_void;
+
+    type Memcpy = unsafe extern "C" fn(*mut c_void, *const c_void, usize) -> *mut c_void;
+    let lib = Library::open("libc.so.6").expect("Could not open libc");
+    //memcpy@GLIBC_2.2.5 has memmove semantics, GLIBC_2.14 is the current version
+    let old: Memcpy = unsafe { lib.symbol("memcpy@GLIBC_2.2.5") }.unwrap();
+    let current: Memcpy = unsafe { lib.symbol_cstr(c"memcpy@@GLIBC_2.14") }.unwrap();
+    assert_ne!(old as usize, current as usize);
+    let same: Memcpy = unsafe { lib.symbol("memcpy@GLIBC_2.14") }.unwrap();
+    assert_eq!(current as usize, same as usize);
+
+    let mut dst = [0u8; 4];
+    unsafe { current(dst.as_mut_ptr().cast(), b"abcd".as_ptr().cast(), 4) };
+    assert_eq!(&dst, b"abcd");
+    let missing: Result<Memcpy, _> = unsafe { lib.symbol("memcpy@GLIBC_0.0") };
+    assert!(missing.is_err());
+}
+
+#[test]
+fn symbol_bytes() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let bytes = unsafe { lib.symbol_bytes_cstr(c"c_const_char_ptr", 4) }.unwrap();
+    drop(lib);
+    assert_eq!(bytes, b"Hi!\0");
+}
+
+#[test]
+#[cfg(any(target_os = "linux", windows))]
+fn library_set() {
+    use dlopen2::raw::LibrarySet;
+
+    //the example library depends on libc, so its handle already reaches libc symbols;
+    //libm is outside of its dependency tree
+    #[cfg(target_os = "linux")]
+    const SYSTEM_LIB: &str = "libm.so.6";
+    #[cfg(windows)]
+    const SYSTEM_LIB: &str = "kernel32.dll";
+    #[cfg(target_os = "linux")]
+    const SYSTEM_SYMBOL: &str = "cos";
+    #[cfg(windows)]
+    const SYSTEM_SYMBOL: &str = "GetCurrentProcessId";
+
+    let mut set = LibrarySet::new();
+    set.open(example_lib_path())
+        .expect("Could not open library");
+    set.push(Library::open(SYSTEM_LIB).expect("Could not open system library"));
+    assert_eq!(set.libraries().len(), 2);
+
+    //exported by the example library
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { set.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+    //exported only by the system library, so it has to come from the second entry
+    let first: Result<*const (), _> = unsafe { set.libraries()[0].symbol(SYSTEM_SYMBOL) };
+    assert!(first.is_err());
+    let second: *const () = unsafe { set.libraries()[1].symbol(SYSTEM_SYMBOL) }.unwrap();
+    let found: *const () = unsafe { set.symbol(SYSTEM_SYMBOL) }.unwrap();
+    assert_eq!(found, second);
+    #[cfg(target_os = "linux")]
+    {
+        let cos: unsafe extern "C" fn(f64) -> f64 = unsafe { std::mem::transmute(found) };
+        assert_eq!(unsafe { cos(0.0) }, 1.0);
+    }
+    #[cfg(windows)]
+    {
+        let process_id: unsafe extern "C" fn() -> u32 = unsafe { std::mem::transmute(found) };
+        assert_eq!(unsafe { process_id() }, std::process::id());
+    }
+    let missing: Result<*const (), _> = unsafe { set.symbol_cstr(c"c_fun_not_in_any") };
+    assert!(matches!(
+        missing,
+        Err(dlopen2::Error::SymbolGettingError(_))
+    ));
+    let empty: Result<*const (), _> = unsafe { LibrarySet::new().symbol("c_fun_add_two") };
+    assert!(empty.is_err());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn library_set_drop_order() {
+    use dlopen2::raw::LibrarySet;
+    use std::sync::Mutex;
+
+    static UNLOADED: Mutex<Vec<c_int>> = Mutex::new(Vec::new());
+    extern "C" fn record_unload(id: c_int) {
+        UNLOADED.lock().unwrap().push(id);
+    }
+
+    //separate copies are separate libraries, each with its own unload notification
+    let lib_path = example_lib_path();
+    let dir = std::env::temp_dir().join(format!("dlopen2-set-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut set = LibrarySet::new();
+    for id in 1..=3 {
+        let path = dir.join(format!("libexample_set_{id}.so"));
+        std::fs::copy(&lib_path, &path).unwrap();
+        let lib = Library::open(&path).expect("Could not open library copy");
+        let notify: unsafe extern "C" fn(extern "C" fn(c_int), c_int) =
+            unsafe { lib.symbol("c_fun_notify_unload") }.unwrap();
+        unsafe { notify(record_unload, id) };
+        set.push(lib);
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+
+    //removing keeps the order of the remaining libraries
+    drop(set.remove(1));
+    assert_eq!(*UNLOADED.lock().unwrap(), [2]);
+    drop(set);
+    assert_eq!(*UNLOADED.lock().unwrap(), [2, 3, 1]);
+}
+
+#[test]
+fn typed_symbol() {
+    use dlopen2::raw::{FnSig, SymbolSig};
+
+    struct AddTwo;
+    impl SymbolSig for AddTwo {
+        type Sig = unsafe extern "C" fn(c_int) -> c_int;
+    }
+
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    let add_two = unsafe { lib.symbol_typed::<AddTwo>("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { add_two(2) }, 4);
+    let add_two = unsafe { lib.symbol_typed_cstr::<AddTwo>(c"c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { add_two(3) }, 5);
+    assert_eq!(<AddTwo as SymbolSig>::Sig::ABI, "C");
+    assert_eq!(<AddTwo as SymbolSig>::Sig::ARITY, 1);
+    //function pointer types are their own markers
+    let print: unsafe extern "C" fn() =
+        unsafe { lib.symbol_typed::<unsafe extern "C" fn()>("c_fun_print_something_else") }
+            .unwrap();
+    assert_eq!(<unsafe extern "C" fn() as FnSig>::ARITY, 0);
+    unsafe { print() };
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn thread_local_symbol_instance() {
+    let lib = Library::open("libc.so.6").expect("Could not open libc");
+    //closing an invalid descriptor sets errno of the calling thread to EBADF
+    assert_eq!(unsafe { libc::close(-1) }, -1);
+    let errno: &c_int = unsafe { lib.symbol_tls_cstr(c"errno") }.unwrap();
+    assert_eq!(*errno, libc::EBADF);
+    assert_eq!(errno as *const c_int, unsafe { libc::__errno_location() }
+        as *const c_int);
+    //other threads have their own instance
+    let other = std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let errno: &c_int = unsafe { lib.symbol_tls("errno") }.unwrap();
+                errno as *const c_int as usize
+            })
+            .join()
+            .unwrap()
+    });
+    assert_ne!(other, errno as *const c_int as usize);
+    assert!(matches!(
+        unsafe { lib.symbol_tls::<c_int>("malloc") },
+        Err(dlopen2::Error::SymbolGettingError(_))
+    ));
+}
+
+#[test]
+fn abi_tag() {
+    use dlopen2::raw::ABI_TAG;
+
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    //the library is built by the same compiler as the tests
+    lib.check_abi_tag(ABI_TAG).unwrap();
+    match lib.check_abi_tag("rustc 0.0.0 other-target panic=abort") {
+        Err(dlopen2::Error::AbiTagMismatch(tag)) => assert_eq!(tag, ABI_TAG),
+        other => panic!("Unexpected result {other:?}"),
+    }
+}
+
+#[test]
+fn open_smart() {
+    let lib_path = example_lib_path();
+    //only the platform-specific file name of the library exists
+    let core_path = lib_path.with_file_name("example");
+    assert!(Library::open(&core_path).is_err());
+    let lib = Library::open_smart(&core_path, None).expect("Could not open library");
+    let add_two: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { add_two(2) }, 4);
+
+    match Library::open_smart(lib_path.with_file_name("not_existing_library"), None) {
+        Err(dlopen2::Error::NoCandidateOpened(failures)) => {
+            assert_eq!(failures.len(), 12);
+            assert_eq!(
+                failures[0].0,
+                lib_path
+                    .with_file_name("not_existing_library")
+                    .into_os_string()
+            );
+        }
+        _ => panic!("The library should not be opened"),
+    }
+}
+
+#[test]
+fn binding_mode() {
+    use dlopen2::raw::BindingMode;
+
+    let lib_path = example_lib_path();
+    let lib = Library::open_now(&lib_path).expect("Could not open library");
+    assert_eq!(lib.binding_mode(), BindingMode::Now);
+    let lib = Library::open(&lib_path).expect("Could not open library");
+    if cfg!(unix) {
+        assert_eq!(lib.binding_mode(), BindingMode::Lazy);
+    } else {
+        assert_eq!(lib.binding_mode(), BindingMode::Now);
+    }
+    #[cfg(unix)]
+    {
+        let lib = Library::open_with_flags(&lib_path, Some(libc::RTLD_LAZY | libc::RTLD_LOCAL))
+            .expect("Could not open library");
+        assert_eq!(lib.binding_mode(), BindingMode::Lazy);
+        let lib = Library::open_with_flags(&lib_path, Some(libc::RTLD_NOW | libc::RTLD_LOCAL))
+            .expect("Could not open library");
+        assert_eq!(lib.binding_mode(), BindingMode::Now);
+    }
+}
+
+#[test]
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), windows))]
+fn entry_point() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(&lib_path).expect("Could not open library");
+    let entry = lib.entry_point().unwrap();
+    assert_eq!(lib.entry_point().unwrap(), entry);
+    //the entry point, if there is one, is code of the library
+    if let Some(entry) = entry {
+        let ai = unsafe { AddressInfoObtainer::new().obtain(entry) }.unwrap();
+        assert_eq!(&ai.dll_path, lib_path.to_str().unwrap());
+    }
+    //glibc can be run as a program, system DLLs have a DllMain
+    #[cfg(unix)]
+    let system_lib = Library::open("libc.so.6").expect("Could not open libc");
+    #[cfg(windows)]
+    let system_lib = Library::open("kernel32.dll").expect("Could not open kernel32");
+    assert!(system_lib.entry_point().unwrap().is_some());
+}
+
+#[test]
+fn symbols_tuple() {
+    use dlopen2::symbols_tuple;
+
+    let lib = Library::open(example_lib_path()).expect("Could not open library");
+    let (add_two, rust_i32) = unsafe {
+        symbols_tuple!(lib, (
+            "c_fun_add_two": unsafe extern "C" fn(c_int) -> c_int,
+            "rust_i32": &i32,
+        ))
+    }
+    .unwrap();
+    assert_eq!(unsafe { add_two(2) }, 4);
+    assert_eq!(*rust_i32, 43);
+    //the first missing symbol stops the lookup
+    let missing = unsafe { symbols_tuple!(lib, ("rust_i32": &i32, "rust_i32_not_found": &i32)) };
+    assert!(matches!(
+        missing,
+        Err(dlopen2::Error::SymbolGettingError(_))
+    ));
+}
@@ -0,0 +1,80 @@
+//glibc exports its version definitions as absolute symbols with a null value
+#![cfg(all(target_os = "linux", target_env = "gnu", target_arch = "x86_64"))]
+
+use dlopen2::Error;
+use dlopen2::symbor::{Library, SymBorApi, Symbol};
+use dlopen2::wrapper::{Container, OptionalContainer, WrapperApi};
+use std::os::raw::c_int;
+
+const LIBC: &str = "libc.so.6";
+
+#[derive(WrapperApi)]
+struct OptionalNullApi {
+    abs: unsafe extern "C" fn(val: c_int) -> c_int,
+    #[dlopen2_name = "GLIBC_2.2.5"]
+    null_fun: Option<unsafe extern "C" fn()>,
+}
+
+#[derive(WrapperApi)]
+struct NullApi {
+    #[dlopen2_name = "GLIBC_2.2.5"]
+    null_fun: unsafe extern "C" fn(),
+}
+
+#[derive(WrapperApi)]
+struct AbsApi {
+    abs: unsafe extern "C" fn(val: c_int) -> c_int,
+}
+
+#[derive(WrapperApi)]
+struct AllowNullApi {
+    abs: unsafe extern "C" fn(val: c_int) -> c_int,
+    #[dlopen2_allow_null]
+    #[dlopen2_name = "GLIBC_2.2.5"]
+    null_ptr: *const c_int,
+}
+
+#[derive(SymBorApi)]
+struct SymBorNullApi<'a> {
+    #[dlopen2_name = "GLIBC_2.2.5"]
+    null_fun: Option<Symbol<'a, unsafe extern "C" fn()>>,
+}
+
+#[test]
+fn null_symbols_in_optional_contexts() {
+    let container: Result<Container<OptionalNullApi>, Error> = unsafe { Container::load(LIBC) };
+    let optional: Result<OptionalContainer<AbsApi, NullApi>, Error> =
+        unsafe { OptionalContainer::load(LIBC) };
+    let lib = Library::open(LIBC).unwrap();
+    let symbor = unsafe { SymBorNullApi::load(&lib) };
+
+    if cfg!(feature = "strict") {
+        assert!(matches!(container, Err(Error::NullSymbol)));
+        assert!(matches!(optional, Err(Error::NullSymbol)));
+        assert!(matches!(symbor, Err(Error::NullSymbol)));
+    } else {
+        let container = container.unwrap();
+        assert!(container.null_fun.is_none());
+        assert_eq!(unsafe { container.abs(-3) }, 3);
+        assert!(optional.unwrap().optional().is_none());
+        assert!(symbor.unwrap().null_fun.is_none());
+    }
+}
+
+#[test]
+fn null_symbols_in_obligatory_contexts() {
+    //null symbols are errors regardless of the mode
+    let container: Result<Container<NullApi>, Error> = unsafe { Container::load(LIBC) };
+    assert!(matches!(container, Err(Error::NullSymbol)));
+    let lib = dlopen2::raw::Library::open(LIBC).unwrap();
+    let raw: Result<*const (), Error> = unsafe { lib.symbol("GLIBC_2.2.5") };
+    assert!(matches!(raw, Err(Error::NullSymbol)));
+}
+
+#[test]
+fn null_symbols_in_allow_null_fields() {
+    //allowed null pointers are loaded and verified regardless of the mode
+    let container: Container<AllowNullApi> = unsafe { Container::load(LIBC) }.unwrap();
+    assert!(container.null_ptr.is_null());
+    container.verify().unwrap();
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "testing")]
+
+use dlopen2::Error;
+use dlopen2::raw::{Library, clear_symbol_overrides, remove_symbol_override, set_symbol_override};
+use dlopen2::wrapper::{Container, WrapperApi};
+use std::os::raw::c_int;
+
+mod commons;
+use commons::example_lib_path;
+
+#[derive(WrapperApi)]
+struct Api {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+}
+
+extern "C" fn add_ten(arg: c_int) -> c_int {
+    arg + 10
+}
+
+//overrides are global, so a single test avoids races between them
+#[test]
+fn override_symbols() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(&lib_path).expect("Could not open library");
+
+    set_symbol_override("c_fun_add_two", None);
+    let missing: Result<unsafe extern "C" fn(c_int) -> c_int, Error> =
+        unsafe { lib.symbol_cstr(c"c_fun_add_two") };
+    assert!(matches!(missing, Err(Error::NullSymbol)));
+    let cont: Result<Container<Api>, Error> = unsafe { Container::load(&lib_path) };
+    assert!(matches!(cont, Err(Error::NullSymbol)));
+    //other symbols are not affected
+    let add_one: fn(i32) -> i32 = unsafe { lib.symbol("rust_fun_add_one") }.unwrap();
+    assert_eq!(add_one(1), 2);
+
+    set_symbol_override("c_fun_add_two", Some(add_ten as *const ()));
+    let replaced: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { replaced(1) }, 11);
+
+    remove_symbol_override("c_fun_add_two");
+    let real: unsafe extern "C" fn(c_int) -> c_int =
+        unsafe { lib.symbol("c_fun_add_two") }.unwrap();
+    assert_eq!(unsafe { real(1) }, 3);
+
+    set_symbol_override("c_fun_add_two", None);
+    clear_symbol_overrides();
+    let cont: Container<Api> = unsafe { Container::load(&lib_path) }.unwrap();
+    assert_eq!(unsafe { cont.c_fun_add_two(1) }, 3);
+}
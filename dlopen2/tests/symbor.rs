@@ -1,6 +1,7 @@
-use dlopen2::symbor::Library;
+use dlopen2::symbor::{Library, OwnedSymbol};
 use std::ffi::CStr;
 use std::os::raw::{c_char, c_int};
+use std::sync::Arc;
 
 mod commons;
 use commons::{SomeData, example_lib_path};
@@ -51,3 +52,90 @@ fn open_play_close_symbor() {
         .unwrap();
     assert_eq!(converted, "Hi!");
 }
+
+#[test]
+fn compare_symbols_by_address() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let c_fun_add_two =
+        unsafe { lib.symbol_cstr::<unsafe extern "C" fn(c_int) -> c_int>(c"c_fun_add_two") }
+            .unwrap();
+    let rust_fun_add_one =
+        unsafe { lib.symbol_cstr::<unsafe extern "C" fn(c_int) -> c_int>(c"rust_fun_add_one") }
+            .unwrap();
+    assert_eq!(c_fun_add_two, c_fun_add_two);
+    assert_ne!(c_fun_add_two, rust_fun_add_one);
+
+    let c_int = unsafe { lib.ptr_or_null_cstr::<c_int>(c"c_int") }.unwrap();
+    let c_int_mut = unsafe { lib.ptr_or_null_cstr::<c_int>(c"c_int_mut") }.unwrap();
+    assert_eq!(c_int, c_int);
+    assert_ne!(c_int, c_int_mut);
+
+    //symbols that are not pointer-sized have no address
+    let first = dlopen2::symbor::Symbol::new([0usize; 2]);
+    let second = first;
+    assert_eq!(first, first);
+    assert_ne!(first, second);
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn compare_aliased_symbols() {
+    use dlopen2::symbor::Ref;
+
+    //glibc exports getpid() under a second name at the same address
+    let libc = Library::open("libc.so.6").expect("Could not open libc");
+    let getpid = unsafe { libc.symbol_cstr::<unsafe extern "C" fn() -> c_int>(c"getpid") }.unwrap();
+    let getpid_alias =
+        unsafe { libc.symbol_cstr::<unsafe extern "C" fn() -> c_int>(c"__getpid") }.unwrap();
+    let malloc = unsafe { libc.symbol_cstr::<unsafe extern "C" fn() -> c_int>(c"malloc") }.unwrap();
+    assert_eq!(getpid, getpid_alias);
+    assert_ne!(getpid, malloc);
+
+    //as are the data symbols environ and __environ
+    let environ: Ref<*const c_char> = Ref::new(unsafe { libc.reference_cstr(c"environ") }.unwrap());
+    let environ_alias: Ref<*const c_char> =
+        Ref::new(unsafe { libc.reference_cstr(c"__environ") }.unwrap());
+    assert_eq!(environ, environ_alias);
+    let environ_ptr = unsafe { libc.ptr_or_null_cstr::<*const c_char>(c"environ") }.unwrap();
+    let environ_alias_ptr =
+        unsafe { libc.ptr_or_null_cstr::<*const c_char>(c"__environ") }.unwrap();
+    assert_eq!(environ_ptr, environ_alias_ptr);
+}
+
+struct Adder {
+    add_two: OwnedSymbol<unsafe extern "C" fn(c_int) -> c_int>,
+}
+
+#[test]
+fn owned_symbol_outlives_binding() {
+    let lib_path = example_lib_path();
+    let adder = {
+        let lib = Arc::new(Library::open(lib_path).expect("Could not open library"));
+        let add_two = unsafe { OwnedSymbol::new(lib.clone(), "c_fun_add_two") }.unwrap();
+        let missing = unsafe { OwnedSymbol::<fn()>::new(lib.clone(), "rust_fun_not_found") };
+        assert!(missing.is_err());
+        drop(lib);
+        Adder { add_two }
+    };
+    assert_eq!(Arc::strong_count(adder.add_two.library()), 1);
+    assert_eq!(unsafe { (adder.add_two)(2) }, 4);
+    let copy = adder.add_two.clone();
+    drop(adder);
+    assert_eq!(unsafe { copy(3) }, 5);
+}
+
+#[test]
+fn convert_from_raw_library() {
+    let lib_path = example_lib_path();
+    let raw = dlopen2::raw::Library::open(lib_path).expect("Could not open library");
+    let lib = Library::from(raw);
+    let c_fun_add_two =
+        unsafe { lib.symbol_cstr::<unsafe extern "C" fn(c_int) -> c_int>(c"c_fun_add_two") }
+            .unwrap();
+    assert_eq!(unsafe { c_fun_add_two(2) }, 4);
+    let handle = unsafe { lib.into_raw() };
+    let raw: dlopen2::raw::Library = lib.into();
+    assert_eq!(unsafe { raw.into_raw() }, handle);
+    assert!(raw.has_symbol("c_fun_add_two"));
+}
@@ -1,4 +1,6 @@
-use dlopen2::symbor::{Library, PtrOrNull, Ref, RefMut, SymBorApi, Symbol};
+use dlopen2::symbor::{
+    Library, NamedSymbol, PtrOrNull, Ref, RefMut, SymBorApi, Symbol, dlopen2_all_optional,
+};
 use std::ffi::CStr;
 use std::os::raw::{c_char, c_int};
 
@@ -11,6 +13,8 @@ struct Api<'a> {
     pub rust_fun_add_one: Symbol<'a, fn(i32) -> i32>,
     pub c_fun_print_something_else: Symbol<'a, unsafe extern "C" fn()>,
     pub c_fun_add_two: Symbol<'a, unsafe extern "C" fn(c_int) -> c_int>,
+    #[dlopen2_name = concat!("c_fun_", "add_two")]
+    pub c_fun_add_two_concat: Symbol<'a, unsafe extern "C" fn(c_int) -> c_int>,
     pub rust_i32: Ref<'a, i32>,
     pub rust_i32_mut: RefMut<'a, i32>,
     #[dlopen2_name = "rust_i32_mut"]
@@ -21,6 +25,15 @@ struct Api<'a> {
     pub c_const_char_ptr: PtrOrNull<'a, c_char>,
 }
 
+#[dlopen2_all_optional]
+#[derive(SymBorApi)]
+struct BestEffortApi<'a> {
+    pub rust_fun_add_one: Symbol<'a, fn(i32) -> i32>,
+    pub rust_fun_not_found: Symbol<'a, fn()>,
+    pub rust_i32: Ref<'a, i32>,
+    pub rust_i32_not_found: Option<Ref<'a, i32>>,
+}
+
 #[test]
 fn open_play_close_symbor_api() {
     let lib_path = example_lib_path();
@@ -30,6 +43,8 @@ fn open_play_close_symbor_api() {
     assert_eq!((api.rust_fun_add_one)(5), 6);
     unsafe { (api.c_fun_print_something_else)() }; //should not crash
     assert_eq!(unsafe { (api.c_fun_add_two)(2) }, 4);
+    assert_eq!(unsafe { (api.c_fun_add_two_concat)(2) }, 4);
+    assert_eq!(api.c_fun_add_two, api.c_fun_add_two_concat);
     assert_eq!(43, *api.rust_i32);
     assert_eq!(42, *api.rust_i32_mut);
     *api.rust_i32_mut = 55; //should not crash
@@ -48,3 +63,92 @@ fn open_play_close_symbor_api() {
         .unwrap();
     assert_eq!(converted, "Hi!");
 }
+
+#[test]
+fn all_optional_symbor_api() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let api = unsafe { BestEffortApi::load(&lib) }.expect("Could not load symbols");
+    assert_eq!(api.rust_fun_add_one.map(|fun| fun(5)), Some(6));
+    assert!(api.rust_fun_not_found.is_none());
+    assert_eq!(api.rust_i32.map(|val| *val), Some(43));
+    assert!(api.rust_i32_not_found.is_none());
+}
+
+#[test]
+fn ptr_or_null_arithmetic() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let api = unsafe { Api::load(&lib) }.expect("Could not load symbols");
+    let bytes: Vec<u8> = (0..4)
+        .map(|index| unsafe { **api.c_const_char_ptr.add(index) } as u8)
+        .collect();
+    assert_eq!(bytes, b"Hi!\0");
+    let last = unsafe { api.c_const_char_ptr.add(3) };
+    assert_eq!(unsafe { **last.offset(-1) } as u8, b'!');
+    assert_eq!(unsafe { last.offset(-3) }, api.c_const_char_ptr);
+}
+
+type AddOne = fn(i32) -> i32;
+
+#[derive(SymBorApi)]
+struct NamedApi<'a> {
+    pub rust_fun_add_one: NamedSymbol<'a, AddOne>,
+    #[dlopen2_name = concat!("c_fun_", "add_two")]
+    pub c_fun_add_two_concat: NamedSymbol<'a, unsafe extern "C" fn(c_int) -> c_int>,
+    #[dlopen2_name = "rust_i32_mut"]
+    pub rust_i32_ptr: NamedSymbol<'a, *const i32>,
+    pub rust_fun_not_found: Option<NamedSymbol<'a, fn()>>,
+    #[dlopen2_name = "rust_fun_add_one"]
+    pub optional_add_one: Option<NamedSymbol<'a, AddOne>>,
+}
+
+fn c_fun_prefix(name: &str) -> String {
+    format!("c_fun_{name}")
+}
+
+#[derive(SymBorApi)]
+#[dlopen2_mangle = "c_fun_prefix"]
+struct MangledNamedApi<'a> {
+    pub add_two: NamedSymbol<'a, unsafe extern "C" fn(c_int) -> c_int>,
+}
+
+#[test]
+fn symbor_api_symbol_names() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let api = unsafe { NamedApi::load(&lib) }.expect("Could not load symbols");
+    assert_eq!(api.rust_fun_add_one.name(), Some("rust_fun_add_one"));
+    assert_eq!((api.rust_fun_add_one)(1), 2);
+    assert_eq!(api.c_fun_add_two_concat.name(), Some("c_fun_add_two"));
+    assert_eq!(api.rust_i32_ptr.name(), Some("rust_i32_mut"));
+    assert!(api.rust_fun_not_found.is_none());
+    assert_eq!(
+        api.optional_add_one.as_ref().and_then(|fun| fun.name()),
+        Some("rust_fun_add_one")
+    );
+
+    //the name that was looked up is recorded
+    let mangled = unsafe { MangledNamedApi::load(&lib) }.expect("Could not load symbols");
+    assert_eq!(mangled.add_two.name(), Some("c_fun_add_two"));
+    assert_eq!(unsafe { (mangled.add_two)(2) }, 4);
+
+    let symbol = unsafe { lib.symbol::<fn(i32) -> i32>("rust_fun_add_one") }.unwrap();
+    let named = symbol.with_name("add_one");
+    assert_eq!(named.name(), Some("add_one"));
+    assert!(named.into_symbol() == symbol);
+    //the plain symbol does not pay for the name
+    assert_eq!(size_of::<Symbol<fn()>>(), size_of::<fn()>());
+}
+
+#[test]
+fn ref_with_library_lifetime() {
+    let lib_path = example_lib_path();
+    let lib = Library::open(lib_path).expect("Could not open library");
+    let value: &i32 = {
+        let api = unsafe { Api::load(&lib) }.expect("Could not load symbols");
+        let rust_i32 = api.rust_i32;
+        rust_i32.get()
+    };
+    assert_eq!(*value, 43);
+}
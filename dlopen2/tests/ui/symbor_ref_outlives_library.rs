@@ -0,0 +1,10 @@
+use dlopen2::symbor::{Library, Ref};
+
+fn main() {
+    let value: &i32 = {
+        let lib = Library::open("libexample.so").unwrap();
+        let rust_i32: Ref<i32> = Ref::new(unsafe { lib.reference("rust_i32") }.unwrap());
+        rust_i32.get()
+    };
+    println!("{value}");
+}
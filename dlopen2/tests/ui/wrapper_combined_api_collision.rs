@@ -0,0 +1,15 @@
+use dlopen2::wrapper::WrapperApi;
+
+#[derive(WrapperApi)]
+struct Extension {
+    ext_init: unsafe extern "C" fn(),
+}
+
+#[derive(WrapperApi)]
+struct Api {
+    #[dlopen2_flatten]
+    ext: Extension,
+    as_ext: unsafe extern "C" fn(),
+}
+
+fn main() {}
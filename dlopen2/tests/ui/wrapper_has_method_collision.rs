@@ -0,0 +1,9 @@
+use dlopen2::wrapper::WrapperApi;
+
+#[derive(WrapperApi)]
+struct Api {
+    callback: Option<unsafe extern "C" fn()>,
+    has_callback: unsafe extern "C" fn() -> bool,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use dlopen2::wrapper::WrapperApi;
+
+#[derive(WrapperApi)]
+struct Api<'a> {
+    counter: &'a mut i32,
+    counter_mut: unsafe extern "C" fn(),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use dlopen2::wrapper::WrapperApi;
+
+const RUST_I32_NAME: &str = "rust\0i32";
+
+#[derive(WrapperApi)]
+struct Api<'a> {
+    #[dlopen2_name(RUST_I32_NAME)]
+    rust_i32: &'a i32,
+}
+
+fn main() {}
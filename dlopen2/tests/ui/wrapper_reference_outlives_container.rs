@@ -0,0 +1,15 @@
+use dlopen2::wrapper::{Container, WrapperApi};
+
+#[derive(WrapperApi)]
+struct Api<'a> {
+    rust_i32: &'a i32,
+    rust_i32_option: Option<&'a i32>,
+}
+
+fn main() {
+    let (value, option): (&i32, Option<&i32>) = {
+        let cont: Container<Api> = unsafe { Container::load("libexample.so") }.unwrap();
+        (cont.rust_i32(), cont.rust_i32_option())
+    };
+    println!("{value} {option:?}");
+}
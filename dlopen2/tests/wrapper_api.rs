@@ -1,10 +1,13 @@
-use dlopen2::wrapper::{Container, WrapperApi};
+use dlopen2::wrapper::{Container, LazyContainer, MappedContainer, OptionalContainer, WrapperApi};
 use std::ffi::CStr;
 use std::os::raw::{c_char, c_int};
+use std::sync::atomic::{AtomicI32, Ordering};
 
 mod commons;
 use commons::{SomeData, example_lib_path};
 
+const RUST_I32_NAME: &str = "rust_i32";
+
 #[derive(WrapperApi)]
 struct Api<'a> {
     rust_fun_print_something: fn(),
@@ -14,6 +17,13 @@ struct Api<'a> {
     c_fun_print_something_else_optional: Option<unsafe extern "C" fn()>,
     c_fun_add_two: Option<unsafe extern "C" fn(arg: c_int) -> c_int>,
     c_fun_add_two_not_found: Option<unsafe extern "C" fn(arg: c_int)>,
+    #[dlopen2_name = "c_fun_add_two"]
+    c_fun_add_two_addr: usize,
+    #[dlopen2_name = concat!("c_fun_", "add_two")]
+    c_fun_add_two_concat: unsafe extern "C" fn(arg: c_int) -> c_int,
+    c_unwind_fun_add_three: unsafe extern "C-unwind" fn(arg: c_int) -> c_int,
+    #[dlopen2_name = "c_unwind_fun_add_three"]
+    c_unwind_fun_add_three_optional: Option<unsafe extern "C-unwind" fn(arg: c_int) -> c_int>,
     rust_i32: &'a i32,
     rust_i32_mut: &'a mut i32,
     #[dlopen2_name = "rust_i32_mut"]
@@ -21,12 +31,110 @@ struct Api<'a> {
     #[dlopen2_name = "rust_i32"]
     rust_i32_optional: Option<&'a i32>,
     rust_i32_not_found: Option<&'a i32>,
+    #[dlopen2_name(RUST_I32_NAME)]
+    rust_i32_const_name: &'a i32,
     c_int: &'a c_int,
     c_struct: &'a SomeData,
     rust_str: &'a &'static str,
     c_const_char_ptr: *const c_char,
 }
 
+fn uppercase(name: &str) -> String {
+    name.to_uppercase()
+}
+
+#[derive(WrapperApi)]
+#[dlopen2_mangle = "uppercase"]
+struct UppercaseApi<'a> {
+    rust_i32_uppercase: &'a i32,
+    #[dlopen2_name = "rust_i32_uppercase"]
+    rust_i32_renamed: Option<&'a i32>,
+}
+
+#[derive(WrapperApi)]
+struct SubApi {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+}
+
+#[derive(WrapperApi)]
+struct FullApi<'a> {
+    rust_i32: &'a i32,
+    #[dlopen2_flatten]
+    sub: SubApi,
+}
+
+#[derive(WrapperApi)]
+#[dlopen2_forbid("rust_i32_not_exported", "rust_i32_mut")]
+struct ForbiddenApi {
+    rust_fun_add_one: fn(arg: i32) -> i32,
+}
+
+#[derive(WrapperApi)]
+#[dlopen2_forbid("rust_i32_not_exported")]
+// exported by a dependency of the library only
+#[cfg_attr(
+    any(all(target_os = "linux", target_env = "gnu"), windows),
+    dlopen2_forbid("malloc")
+)]
+struct NotForbiddenApi {
+    rust_fun_add_one: fn(arg: i32) -> i32,
+}
+
+#[derive(WrapperApi)]
+struct ResultApi {
+    #[dlopen2_result]
+    rust_fun_add_one: Option<fn(arg: i32) -> i32>,
+}
+
+#[derive(WrapperApi)]
+struct MissingResultApi {
+    #[dlopen2_result]
+    rust_fun_not_found: Option<fn(arg: i32) -> Option<i32>>,
+}
+
+#[derive(WrapperApi)]
+struct CatchUnwindApi {
+    #[dlopen2_catch_unwind]
+    rust_fun_add_one: fn(arg: i32) -> i32,
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+}
+
+#[derive(WrapperApi)]
+#[dlopen2_catch_unwind]
+struct CatchAllUnwindApi {
+    rust_fun_print_something: fn(),
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+}
+
+#[derive(WrapperApi)]
+struct MissingApi {
+    c_fun_add_two_not_found: unsafe extern "C" fn(arg: c_int),
+}
+
+#[derive(WrapperApi)]
+struct FallbackApi {
+    #[dlopen2_fallback = "stub_add_two"]
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+    #[dlopen2_fallback = "stub_add_two"]
+    c_fun_add_two_not_found: unsafe extern "C" fn(arg: c_int) -> c_int,
+}
+
+unsafe extern "C" fn stub_add_two(_arg: c_int) -> c_int {
+    -1
+}
+
+struct Adder(SubApi);
+
+impl Adder {
+    fn add_two(&self, value: c_int) -> c_int {
+        unsafe { self.0.c_fun_add_two(value) }
+    }
+}
+
+fn add_two_using_sub_api(api: &SubApi) -> c_int {
+    unsafe { api.c_fun_add_two(2) }
+}
+
 //those methods won't be generated
 impl<'a> Api<'a> {
     fn rust_i32_ptr(&self) -> *const i32 {
@@ -52,12 +160,18 @@ fn open_play_close_wrapper_api() {
     assert_eq!(unsafe { cont.c_fun_add_two(2) }, Some(4));
     assert!(!cont.has_c_fun_add_two_not_found());
     assert_eq!(unsafe { cont.c_fun_add_two_not_found(2) }, None);
+    assert_eq!(unsafe { cont.c_fun_add_two_concat(2) }, 4);
+    let c_fun_add_two: unsafe extern "C" fn(c_int) -> c_int = cont.c_fun_add_two_concat;
+    assert_eq!(cont.c_fun_add_two_addr(), c_fun_add_two as usize);
+    assert_eq!(unsafe { cont.c_unwind_fun_add_three(2) }, 5);
+    assert_eq!(unsafe { cont.c_unwind_fun_add_three_optional(2) }, Some(5));
     assert_eq!(43, *cont.rust_i32());
     assert_eq!(42, *cont.rust_i32_mut_mut());
     *cont.rust_i32_mut_mut() = 55; // should not crash
     assert_eq!(55, unsafe { *cont.rust_i32_ptr() });
     assert_eq!(cont.rust_i32_optional(), Some(&43));
     assert_eq!(cont.rust_i32_not_found(), None);
+    assert_eq!(43, *cont.rust_i32_const_name());
 
     // the same with C
     assert_eq!(45, *cont.c_int());
@@ -71,3 +185,463 @@ fn open_play_close_wrapper_api() {
     let converted = cont.c_const_str().to_str().unwrap();
     assert_eq!(converted, "Hi!");
 }
+
+#[test]
+fn mangle_wrapper_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<UppercaseApi> =
+        unsafe { Container::load(lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(46, *cont.rust_i32_uppercase());
+    assert_eq!(cont.rust_i32_renamed(), Some(&46));
+}
+
+#[test]
+fn flatten_wrapper_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<FullApi> =
+        unsafe { Container::load(lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(43, *cont.rust_i32());
+    assert_eq!(add_two_using_sub_api(cont.as_sub()), 4);
+}
+
+#[test]
+fn wrapper_api_symbol_lists() {
+    assert_eq!(
+        Api::required_symbols(),
+        [
+            "rust_fun_print_something",
+            "rust_fun_add_one",
+            "c_fun_print_something_else",
+            "c_fun_add_two",
+            "c_unwind_fun_add_three",
+            "rust_i32",
+            "rust_i32_mut",
+            "c_int",
+            "c_struct",
+            "rust_str",
+            "c_const_char_ptr",
+        ]
+    );
+    assert_eq!(
+        Api::optional_symbols(),
+        ["c_fun_add_two_not_found", "rust_i32_not_found"]
+    );
+    //listed at compile time, duplicates spelled differently are removed too
+    const REQUIRED: &[&str] = Api::REQUIRED_SYMBOLS;
+    assert_eq!(REQUIRED, Api::required_symbols());
+    assert_eq!(Api::OPTIONAL_SYMBOLS, Api::optional_symbols());
+    assert_eq!(SubApi::REQUIRED_SYMBOLS, ["c_fun_add_two"]);
+    assert!(SubApi::OPTIONAL_SYMBOLS.is_empty());
+    //mangled and flattened names are only known at run time
+    assert!(UppercaseApi::REQUIRED_SYMBOLS.is_empty());
+    assert!(FullApi::REQUIRED_SYMBOLS.is_empty());
+    assert_eq!(FullApi::required_symbols(), ["rust_i32", "c_fun_add_two"]);
+    assert!(FullApi::optional_symbols().is_empty());
+    assert_eq!(SubApi::required_symbols(), ["c_fun_add_two"]);
+    assert_eq!(UppercaseApi::required_symbols(), ["RUST_I32_UPPERCASE"]);
+    assert!(UppercaseApi::optional_symbols().is_empty());
+    assert!(<Option<SubApi>>::required_symbols().is_empty());
+    assert_eq!(<Option<SubApi>>::optional_symbols(), ["c_fun_add_two"]);
+    assert_eq!(
+        <(SubApi, FullApi)>::required_symbols(),
+        ["c_fun_add_two", "rust_i32"]
+    );
+}
+
+#[test]
+fn map_wrapper_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<SubApi> =
+        unsafe { Container::load(lib_path) }.expect("Could not open library or load symbols");
+    let adder: MappedContainer<Adder> = cont.map(Adder);
+    assert_eq!(adder.add_two(2), 4);
+    let doubled = adder.map(|adder| move |value| adder.add_two(value) * 2);
+    assert_eq!(doubled(1), 6);
+}
+
+#[test]
+fn forbidden_symbol_wrapper_api() {
+    let lib_path = example_lib_path();
+    match unsafe { Container::<ForbiddenApi>::load(&lib_path) } {
+        Err(dlopen2::Error::ForbiddenSymbol(name)) => assert_eq!(name, "rust_i32_mut"),
+        _ => panic!("Loading an API with a forbidden symbol should fail"),
+    }
+    let cont: Container<NotForbiddenApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(cont.rust_fun_add_one(5), 6);
+}
+
+#[test]
+fn verify_wrapper_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<Api> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    cont.verify().expect("Healthy container should verify");
+    let cont: Container<FullApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    cont.verify().expect("Healthy container should verify");
+    let cont: Container<UppercaseApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    cont.verify().expect("Healthy container should verify");
+
+    unsafe extern "C" fn fake_add_two(arg: c_int) -> c_int {
+        arg + 2
+    }
+    let mut cont: Container<SubApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    cont.c_fun_add_two = fake_add_two;
+    match cont.verify() {
+        Err(dlopen2::Error::SymbolAddressMismatch(name)) => assert_eq!(name, "c_fun_add_two"),
+        _ => panic!("Replaced symbol should not verify"),
+    }
+}
+
+#[test]
+fn load_and_init_wrapper_api() {
+    let lib_path = example_lib_path();
+    let (cont, state): (Container<SubApi>, i32) = unsafe {
+        Container::load_and_init(&lib_path, "rust_fun_add_one", |init: fn(i32) -> i32| {
+            init(5)
+        })
+    }
+    .expect("Could not open library, load symbols or call the init function");
+    assert_eq!(state, 6);
+    assert_eq!(unsafe { cont.c_fun_add_two(state) }, 8);
+
+    let result = unsafe {
+        Container::<SubApi>::load_and_init(&lib_path, "rust_init_not_found", |init: fn()| init())
+    };
+    assert!(matches!(result, Err(dlopen2::Error::SymbolGettingError(_))));
+}
+
+#[test]
+fn load_now_wrapper_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<FullApi> =
+        unsafe { Container::load_now(&lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(43, *cont.rust_i32());
+    assert_eq!(add_two_using_sub_api(cont.as_sub()), 4);
+}
+
+#[test]
+fn result_optional_wrapper_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<ResultApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(cont.rust_fun_add_one(5).unwrap(), 6);
+    let cont: Container<MissingResultApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    assert!(!cont.has_rust_fun_not_found());
+    assert!(matches!(
+        cont.rust_fun_not_found(5),
+        Err(dlopen2::Error::SymbolNotLoaded)
+    ));
+}
+
+#[test]
+fn catch_unwind_wrapper_api() {
+    let lib_path = example_lib_path();
+    let mut cont: Container<CatchUnwindApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(cont.rust_fun_add_one(5).unwrap(), 6);
+    assert_eq!(unsafe { cont.c_fun_add_two(2) }, 4);
+
+    //a panic raised by a cdylib can't be caught, so use a function sharing our standard library
+    fn panicking_add_one(_arg: i32) -> i32 {
+        panic!("plugin function failed");
+    }
+    cont.rust_fun_add_one = panicking_add_one;
+    let payload = cont.rust_fun_add_one(5).unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<&str>(),
+        Some(&"plugin function failed")
+    );
+
+    let cont: Container<CatchAllUnwindApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    assert!(cont.rust_fun_print_something().is_ok());
+    assert_eq!(unsafe { cont.c_fun_add_two(2) }, 4);
+}
+
+#[test]
+fn fallback_wrapper_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<FallbackApi> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(unsafe { cont.c_fun_add_two(2) }, 4);
+    assert_eq!(unsafe { cont.c_fun_add_two_not_found(2) }, -1);
+    cont.verify().expect("Fallback should verify");
+    assert!(FallbackApi::required_symbols().is_empty());
+    assert_eq!(
+        FallbackApi::OPTIONAL_SYMBOLS,
+        ["c_fun_add_two", "c_fun_add_two_not_found"]
+    );
+    assert_eq!(
+        FallbackApi::optional_symbols(),
+        ["c_fun_add_two", "c_fun_add_two_not_found"]
+    );
+}
+
+#[test]
+fn tuple_wrapper_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<(SubApi, ResultApi)> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(add_two_using_sub_api(&cont.0), 4);
+    assert_eq!(cont.1.rust_fun_add_one(5).unwrap(), 6);
+    cont.verify().expect("Healthy container should verify");
+
+    let result = unsafe { Container::<(SubApi, MissingApi)>::load(&lib_path) };
+    assert!(result.is_err());
+}
+
+#[test]
+fn optional_container_combinators() {
+    let lib_path = example_lib_path();
+    let cont: OptionalContainer<SubApi, ResultApi> = unsafe { OptionalContainer::load(&lib_path) }
+        .expect("Could not open library or load symbols");
+    assert!(cont.has_optional());
+    assert_eq!(
+        cont.optional_map(|opt| opt.rust_fun_add_one(5).unwrap()),
+        Some(6)
+    );
+    assert_eq!(
+        cont.optional_and_then(|opt| opt.rust_fun_add_one(5).ok()),
+        Some(6)
+    );
+
+    let cont: OptionalContainer<SubApi, MissingApi> = unsafe { OptionalContainer::load(&lib_path) }
+        .expect("Could not open library or load symbols");
+    assert!(!cont.has_optional());
+    assert_eq!(cont.optional_map(|_| 1), None);
+    assert_eq!(cont.optional_and_then(|_| Some(1)), None);
+    assert_eq!(add_two_using_sub_api(&cont), 4);
+}
+
+//calls into the library when dropped, which requires the library to still be loaded
+struct DropCheckApi {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+}
+
+static DROP_CHECK_SUM: AtomicI32 = AtomicI32::new(0);
+
+impl WrapperApi for DropCheckApi {
+    unsafe fn load(lib: &impl dlopen2::raw::SymbolSource) -> Result<Self, dlopen2::Error> {
+        Ok(DropCheckApi {
+            c_fun_add_two: unsafe { lib.symbol_cstr(c"c_fun_add_two") }?,
+        })
+    }
+}
+
+impl Drop for DropCheckApi {
+    fn drop(&mut self) {
+        DROP_CHECK_SUM.fetch_add(unsafe { (self.c_fun_add_two)(1) }, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn optional_container_drop_order() {
+    let lib_path = example_lib_path();
+    let cont: OptionalContainer<DropCheckApi, DropCheckApi> =
+        unsafe { OptionalContainer::load(&lib_path) }
+            .expect("Could not open library or load symbols");
+    assert!(cont.has_optional());
+    drop(cont);
+    assert_eq!(DROP_CHECK_SUM.load(Ordering::SeqCst), 6);
+}
+
+#[test]
+fn teardown_wrapper_api() {
+    let lib_path = example_lib_path();
+    //keeps the library loaded after the container closes it, so that the count can be read
+    let raw_lib = dlopen2::raw::Library::open(&lib_path).expect("Could not open library");
+    let count: &AtomicI32 = unsafe { raw_lib.symbol("c_teardown_count") }.unwrap();
+    let before = count.load(Ordering::SeqCst);
+
+    let cont = unsafe { Container::<SubApi>::load(&lib_path) }
+        .and_then(|cont| unsafe { cont.with_teardown("c_fun_teardown") })
+        .expect("Could not open library or load symbols");
+    assert_eq!(unsafe { cont.c_fun_add_two(1) }, 3);
+    assert_eq!(count.load(Ordering::SeqCst), before);
+    drop(cont);
+    assert_eq!(count.load(Ordering::SeqCst), before + 1);
+
+    //the teardown function is kept when the container gets mapped
+    let mapped = unsafe { Container::<SubApi>::load(&lib_path) }
+        .and_then(|cont| unsafe { cont.with_teardown("c_fun_teardown") })
+        .unwrap()
+        .map(|api| add_two_using_sub_api(&api));
+    assert_eq!(*mapped, 4);
+    assert_eq!(count.load(Ordering::SeqCst), before + 1);
+    drop(mapped);
+    assert_eq!(count.load(Ordering::SeqCst), before + 2);
+
+    let missing = unsafe { Container::<SubApi>::load(&lib_path) }
+        .and_then(|cont| unsafe { cont.with_teardown("c_fun_missing_teardown") });
+    assert!(missing.is_err());
+    assert_eq!(count.load(Ordering::SeqCst), before + 2);
+}
+
+//a slice reference is twice the size of the pointer the symbol gets transmuted from
+#[derive(WrapperApi)]
+struct WrongSizeApi<'a> {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+    c_string_table: &'a [u8],
+}
+
+#[test]
+fn checked_wrapper_api() {
+    let lib_path = example_lib_path();
+    let lib = dlopen2::raw::Library::open(&lib_path).expect("Could not open library");
+    assert!(SubApi::check(&lib).is_ok());
+    let api = unsafe { SubApi::try_load_checked(&lib) }.unwrap();
+    assert_eq!(add_two_using_sub_api(&api), 4);
+    assert!(ResultApi::check(&lib).is_ok());
+
+    match unsafe { WrongSizeApi::try_load_checked(&lib) } {
+        Err(dlopen2::Error::SymbolSizeMismatch(name)) => assert_eq!(name, "c_string_table"),
+        _ => panic!("The size mismatch was not reported"),
+    }
+    assert!(matches!(
+        Option::<WrongSizeApi>::check(&lib),
+        Err(dlopen2::Error::SymbolSizeMismatch(_))
+    ));
+    assert!(matches!(
+        MissingApi::check(&lib),
+        Err(dlopen2::Error::SymbolGettingError(_))
+    ));
+    //a missing optional API is acceptable
+    assert!(Option::<MissingApi>::check(&lib).is_ok());
+}
+
+#[test]
+fn lazy_wrapper_api() {
+    let lib_path = example_lib_path();
+    let lazy: LazyContainer<FullApi> = unsafe { LazyContainer::new(&lib_path) };
+    assert!(!lazy.is_loaded());
+    assert_eq!(43, *lazy.rust_i32());
+    assert!(lazy.is_loaded());
+    assert_eq!(add_two_using_sub_api(lazy.get().unwrap().as_sub()), 4);
+
+    let missing: LazyContainer<FullApi> =
+        unsafe { LazyContainer::new(lib_path.with_file_name("libnot_existing_library.so")) };
+    assert!(!missing.is_loaded());
+    assert!(matches!(
+        missing.get(),
+        Err(dlopen2::Error::OpeningLibraryError(_))
+    ));
+    assert!(!missing.is_loaded());
+}
+
+#[derive(WrapperApi)]
+struct ManifestApi {
+    c_fun_manifest_mul: unsafe extern "C" fn(a: c_int, b: c_int) -> c_int,
+    rust_fun_manifest_len: fn(text: &str) -> usize,
+    c_fun_manifest_reset: Option<unsafe extern "C" fn(value: *mut c_int)>,
+    //optional functions may be missing from the manifest
+    c_fun_add_two: Option<unsafe extern "C" fn(arg: c_int) -> c_int>,
+}
+
+#[derive(WrapperApi)]
+struct WrongManifestApi {
+    c_fun_manifest_mul: unsafe extern "C" fn(a: c_int) -> c_int,
+}
+
+#[derive(WrapperApi)]
+struct UnlistedManifestApi {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+}
+
+fn c_fun_prefix(name: &str) -> String {
+    format!("c_fun_{name}")
+}
+
+#[derive(WrapperApi)]
+#[dlopen2_mangle = "c_fun_prefix"]
+struct MangledManifestApi {
+    manifest_mul: unsafe extern "C" fn(a: c_int, b: c_int) -> c_int,
+}
+
+#[test]
+fn manifest_wrapper_api() {
+    let lib_path = example_lib_path();
+    let lib = dlopen2::raw::Library::open(&lib_path).expect("Could not open library");
+    ManifestApi::check_manifest(&lib).unwrap();
+    <(ManifestApi, ManifestApi)>::check_manifest(&lib).unwrap();
+    MangledManifestApi::check_manifest(&lib).unwrap();
+    let cont: Container<ManifestApi> =
+        unsafe { Container::from_raw_library(lib) }.expect("Could not load symbols");
+    assert_eq!(unsafe { cont.c_fun_manifest_mul(6, 7) }, 42);
+    assert_eq!(cont.rust_fun_manifest_len("four"), 4);
+    let mut value = 5;
+    assert_eq!(unsafe { cont.c_fun_manifest_reset(&mut value) }, Some(()));
+    assert_eq!(value, 0);
+
+    let lib = dlopen2::raw::Library::open(&lib_path).expect("Could not open library");
+    match WrongManifestApi::check_manifest(&lib) {
+        Err(dlopen2::Error::SignatureMismatch(name)) => assert_eq!(name, "c_fun_manifest_mul"),
+        other => panic!("Unexpected result {other:?}"),
+    }
+    match UnlistedManifestApi::check_manifest(&lib) {
+        Err(dlopen2::Error::SignatureMismatch(name)) => assert_eq!(name, "c_fun_add_two"),
+        other => panic!("Unexpected result {other:?}"),
+    }
+    //optional APIs may be missing from the manifest, but not have other signatures
+    <Option<UnlistedManifestApi>>::check_manifest(&lib).unwrap();
+    match <Option<WrongManifestApi>>::check_manifest(&lib) {
+        Err(dlopen2::Error::SignatureMismatch(name)) => assert_eq!(name, "c_fun_manifest_mul"),
+        other => panic!("Unexpected result {other:?}"),
+    }
+    let entries = dlopen2::wrapper::ApiManifest::from_library(&lib)
+        .unwrap()
+        .entries();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].name(), b"c_fun_manifest_mul");
+    assert_eq!(
+        entries[0].signature(),
+        b"extern \"C\" fn(c_int, c_int) -> c_int"
+    );
+    assert_eq!(entries[2].signature(), b"extern \"C\" fn(*mut c_int)");
+}
+
+struct MockSource;
+
+extern "C" fn mock_add_two(arg: c_int) -> c_int {
+    arg + 20
+}
+
+static MOCK_I32: i32 = 7;
+
+impl dlopen2::raw::SymbolSource for MockSource {
+    fn get_symbol(&self, name: &CStr) -> Result<*const (), dlopen2::Error> {
+        match name.to_bytes() {
+            b"c_fun_add_two" => Ok(mock_add_two as *const ()),
+            b"rust_i32" => Ok(&MOCK_I32 as *const i32 as *const ()),
+            _ => Err(dlopen2::Error::SymbolGettingError(
+                std::io::ErrorKind::NotFound.into(),
+            )),
+        }
+    }
+}
+
+#[derive(WrapperApi)]
+struct MockedApi<'a> {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+    rust_i32: &'a i32,
+    c_fun_missing: Option<unsafe extern "C" fn()>,
+    #[dlopen2_flatten]
+    sub: SubApi,
+}
+
+#[test]
+fn mock_symbol_source_wrapper_api() {
+    let api = unsafe { MockedApi::load(&MockSource) }.expect("Could not load mocked symbols");
+    assert_eq!(unsafe { api.c_fun_add_two(1) }, 21);
+    assert_eq!(*api.rust_i32(), 7);
+    assert!(api.c_fun_missing.is_none());
+    assert_eq!(add_two_using_sub_api(&api.sub), 22);
+    assert!(matches!(
+        unsafe { MissingApi::load(&MockSource) },
+        Err(dlopen2::Error::SymbolGettingError(_))
+    ));
+}
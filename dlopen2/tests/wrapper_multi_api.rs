@@ -0,0 +1,88 @@
+use dlopen2::wrapper::{Container, WrapperApi, WrapperMultiApi};
+use std::os::raw::c_int;
+
+mod commons;
+use commons::example_lib_path;
+
+#[derive(WrapperApi)]
+struct CoreApi {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+    rust_fun_add_one: fn(arg: i32) -> i32,
+}
+
+#[derive(WrapperApi)]
+struct ExtensionApi {
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+    #[dlopen2_name = "rust_fun_add_one"]
+    add_one: fn(arg: i32) -> i32,
+}
+
+#[derive(WrapperApi)]
+struct MissingApi {
+    c_fun_add_two_not_found: unsafe extern "C" fn(arg: c_int),
+}
+
+#[derive(WrapperMultiApi)]
+struct Api {
+    core: CoreApi,
+    extension: Option<ExtensionApi>,
+    missing: Option<MissingApi>,
+}
+
+#[derive(WrapperApi)]
+struct ManifestApi {
+    c_fun_manifest_mul: unsafe extern "C" fn(a: c_int, b: c_int) -> c_int,
+}
+
+#[derive(WrapperApi)]
+struct WrongManifestApi {
+    c_fun_manifest_mul: unsafe extern "C" fn(a: c_int) -> c_int,
+}
+
+#[derive(WrapperMultiApi)]
+struct ManifestMultiApi {
+    manifest: ManifestApi,
+    //the functions of the core API are not listed in the manifest
+    core: Option<CoreApi>,
+}
+
+#[derive(WrapperMultiApi)]
+struct WrongManifestMultiApi {
+    manifest: ManifestApi,
+    wrong: Option<WrongManifestApi>,
+}
+
+#[test]
+fn namespaced_multi_api() {
+    let lib_path = example_lib_path();
+    let cont: Container<Api> =
+        unsafe { Container::load(&lib_path) }.expect("Could not open library or load symbols");
+    assert_eq!(unsafe { cont.core.c_fun_add_two(2) }, 4);
+    assert_eq!(cont.core.rust_fun_add_one(5), 6);
+    let extension = cont.extension.as_ref().expect("Extension should be loaded");
+    assert_eq!(unsafe { extension.c_fun_add_two(3) }, 5);
+    assert_eq!(extension.add_one(1), 2);
+    assert!(cont.missing.is_none());
+    cont.verify().expect("Healthy container should verify");
+}
+
+#[test]
+fn multi_api_symbol_lists() {
+    assert_eq!(
+        Api::required_symbols(),
+        ["c_fun_add_two", "rust_fun_add_one"]
+    );
+    //the symbols of the optional extension are required by the core API
+    assert_eq!(Api::optional_symbols(), ["c_fun_add_two_not_found"]);
+}
+
+#[test]
+fn multi_api_manifest() {
+    let lib_path = example_lib_path();
+    let lib = dlopen2::raw::Library::open(&lib_path).expect("Could not open library");
+    ManifestMultiApi::check_manifest(&lib).unwrap();
+    match WrongManifestMultiApi::check_manifest(&lib) {
+        Err(dlopen2::Error::SignatureMismatch(name)) => assert_eq!(name, "c_fun_manifest_mul"),
+        other => panic!("Unexpected result {other:?}"),
+    }
+}
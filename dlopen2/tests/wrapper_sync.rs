@@ -0,0 +1,38 @@
+use dlopen2::wrapper::{Container, WrapperApi};
+use std::sync::Mutex;
+use std::thread;
+
+mod commons;
+use commons::example_lib_path;
+
+#[derive(WrapperApi)]
+struct SyncApi<'a> {
+    #[dlopen2_sync]
+    rust_i32_mut: Mutex<&'a mut i32>,
+}
+
+// runs in its own process, because other tests assert the value of rust_i32_mut
+#[test]
+fn sync_access_from_threads() {
+    const INCREMENTS: i32 = 1000;
+
+    let lib_path = example_lib_path();
+    let cont: Container<SyncApi> =
+        unsafe { Container::load(lib_path) }.expect("Could not open library or load symbols");
+    cont.verify()
+        .expect("The synchronized reference should point to the symbol");
+    let initial = *cont.rust_i32_mut();
+    thread::scope(|scope| {
+        for _ in 0..2 {
+            scope.spawn(|| {
+                for _ in 0..INCREMENTS {
+                    let mut guard = cont.rust_i32_mut();
+                    let value = *guard;
+                    thread::yield_now();
+                    *guard = value + 1;
+                }
+            });
+        }
+    });
+    assert_eq!(*cont.rust_i32_mut(), initial + 2 * INCREMENTS);
+}
@@ -0,0 +1,35 @@
+use dlopen2::wrapper::{VersionedContainer, WrapperApi};
+use std::os::raw::c_int;
+
+mod commons;
+use commons::example_lib_path;
+
+#[derive(WrapperApi)]
+struct ApiV1 {
+    rust_fun_add_one: fn(arg: i32) -> i32,
+}
+
+#[derive(WrapperApi)]
+struct ApiV2 {
+    rust_fun_add_one: fn(arg: i32) -> i32,
+    c_fun_add_two: unsafe extern "C" fn(arg: c_int) -> c_int,
+    c_fun_from_the_future: unsafe extern "C" fn(),
+}
+
+#[test]
+fn older_version_gets_selected() {
+    let lib_path = example_lib_path();
+    let cont: VersionedContainer<(ApiV2, ApiV1)> =
+        unsafe { VersionedContainer::load(lib_path) }.expect("Could not load any version");
+    assert_eq!(cont.version(), 1);
+    assert!(cont.0.is_none());
+    let v1 = cont.1.as_ref().unwrap();
+    assert_eq!(v1.rust_fun_add_one(5), 6);
+}
+
+#[test]
+fn no_version_can_be_loaded() {
+    let lib_path = example_lib_path();
+    let result = unsafe { VersionedContainer::<(ApiV2,)>::load(lib_path) };
+    assert!(matches!(result, Err(dlopen2::Error::SymbolGettingError(_))));
+}
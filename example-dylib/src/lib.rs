@@ -5,6 +5,7 @@
 //! It's main purpose is to be used in tests of dynlib crate.
 
 use std::os::raw::{c_char, c_int};
+use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
 
 //FUNCTIONS
 #[unsafe(no_mangle)]
@@ -27,6 +28,23 @@ pub extern "C" fn c_fun_add_two(arg: c_int) -> c_int {
     arg + 2
 }
 
+#[unsafe(no_mangle)]
+pub extern "C-unwind" fn c_unwind_fun_add_three(arg: c_int) -> c_int {
+    arg + 3
+}
+
+//counts its calls in c_teardown_count - for testing automatic teardown
+#[unsafe(no_mangle)]
+pub extern "C" fn c_fun_teardown() {
+    c_teardown_count.fetch_add(1, Ordering::SeqCst);
+}
+
+//exported with a Rust-mangled name - for testing demangling
+#[unsafe(export_name = "_ZN13example_dylib11mangled_fun17h0123456789abcdefE")]
+pub fn rust_fun_mangled() -> i32 {
+    7
+}
+
 #[allow(unused_variables)]
 #[unsafe(no_mangle)]
 pub extern "C" fn c_fun_variadic(txt: *const c_char) {
@@ -44,6 +62,13 @@ pub static mut c_int_mut: c_int = 44;
 #[unsafe(no_mangle)]
 pub static c_int: c_int = 45;
 
+#[unsafe(no_mangle)]
+pub static c_teardown_count: AtomicI32 = AtomicI32::new(0);
+
+//exported only in upper case - for testing custom name mangling
+#[unsafe(no_mangle)]
+pub static RUST_I32_UPPERCASE: i32 = 46;
+
 #[repr(C)]
 pub struct SomeData {
     first: c_int,
@@ -65,3 +90,65 @@ pub static rust_str: &str = "Hello!";
 
 #[unsafe(no_mangle)]
 pub static c_const_char_ptr: [u8; 4] = [b'H', b'i', b'!', 0];
+
+//double-null-terminated lists of strings
+#[unsafe(no_mangle)]
+pub static c_string_table: [u8; 20] = *b"first\0second\0third\0\0";
+#[unsafe(no_mangle)]
+pub static c_string_table_empty: [u8; 1] = [0];
+
+//tables with their length exported separately
+#[unsafe(no_mangle)]
+pub static c_u16_table: [u16; 5] = [1, 2, 3, 5, 8];
+#[unsafe(no_mangle)]
+pub static c_u16_table_len: u32 = 5;
+#[unsafe(no_mangle)]
+pub static c_invalid_table_len: i32 = -1;
+#[unsafe(no_mangle)]
+pub static c_huge_table_len: usize = usize::MAX;
+
+//UNLOAD NOTIFICATION - for testing the order in which libraries get closed
+static UNLOAD_CALLBACK: AtomicUsize = AtomicUsize::new(0);
+static UNLOAD_ID: AtomicI32 = AtomicI32::new(0);
+
+//makes the library call `callback(id)` when it gets unloaded
+#[unsafe(no_mangle)]
+pub extern "C" fn c_fun_notify_unload(callback: extern "C" fn(c_int), id: c_int) {
+    UNLOAD_ID.store(id, Ordering::SeqCst);
+    UNLOAD_CALLBACK.store(callback as usize, Ordering::SeqCst);
+}
+
+#[cfg(target_os = "linux")]
+#[used]
+#[unsafe(link_section = ".fini_array")]
+static NOTIFY_UNLOAD: extern "C" fn() = notify_unload;
+
+#[cfg(target_os = "linux")]
+extern "C" fn notify_unload() {
+    let callback = UNLOAD_CALLBACK.load(Ordering::SeqCst);
+    if callback != 0 {
+        let callback: extern "C" fn(c_int) = unsafe { std::mem::transmute(callback) };
+        callback(UNLOAD_ID.load(Ordering::SeqCst));
+    }
+}
+
+//ABI TAG
+dlopen2::export_abi_tag!();
+
+//API MANIFEST
+dlopen2::export_api! {
+    pub extern "C" fn c_fun_manifest_mul(a: c_int, b: c_int) -> c_int {
+        a * b
+    }
+
+    pub fn rust_fun_manifest_len(text: &str) -> usize {
+        text.len()
+    }
+
+    /// # Safety
+    ///
+    /// `value` must be valid for writes.
+    pub unsafe extern "C" fn c_fun_manifest_reset(value: *mut c_int) {
+        unsafe { *value = 0 };
+    }
+}